@@ -0,0 +1,293 @@
+use std::fmt;
+
+use pokemon_battle_system::models::trainer::AiLevel;
+
+use super::print::DEFAULT_TEXT_SPEED;
+
+/// JSON ファイルのディレクトリの既定値
+pub const DEFAULT_DATA_DIR: &str = "./json";
+
+/// バイナリファイルのパスの既定値
+pub const DEFAULT_BIN_PATH: &str = "./bin/game_data.bin";
+
+/// 使い方
+pub const USAGE: &str = "\
+使い方: pokemon-battle-system [コマンド] [オプション]
+
+コマンド:
+  play       バトルを遊ぶ（既定）
+  simulate   コンピュータ同士でバトルを行い結果を表示する（--battles で複数回の統計）
+  replay     --replay-file に記録したバトルを再生する
+  save       --trainer のトレーナーで --save-file に新しいセーブデータを作成する
+  load       --save-file のセーブデータの内容を表示する
+  use-item   --save-file のバッグの --item の道具を手持ちのポケモンに使う（進化のいしなど）
+  validate   JSON ファイルを検証する
+  convert    JSON ファイルをバイナリファイルに変換する
+
+オプション:
+  --data-dir <DIR>       JSON ファイルのディレクトリ（既定: ./json）
+  --bin-file <PATH>      バイナリファイルのパス（既定: ./bin/game_data.bin）
+  --format <FORMAT>      読み込むデータの形式: json, bin, hard_code（既定: json）
+  --seed <SEED>          乱数のシード値（既定: BATTLE_SEED、未設定なら現在時刻）
+  --trainer <ID>         自分のトレーナー（trainer_data.json のキー）
+  --opponent <ID>        対戦相手のトレーナー（wild でやせいのポケモン）
+  --text-speed <MS>      1 文字あたりの表示時間（ミリ秒、0 で待ち時間なし、既定: 60）
+  --battles <N>          simulate で行うバトルの回数（既定: 1）
+  --threads <N>          simulate で使用するスレッド数（既定: 1）
+  --player-ai <AI>       simulate での自分のトレーナーの AI: random, greedy, heuristic, expectiminimax
+  --opponent-ai <AI>     対戦相手の AI（既定: どちらも trainer_data.json の設定）
+  --replay-file <PATH>   リプレイファイルのパス（play, simulate では記録先、replay では読み込み元）
+  --step                 replay で 1 ターンごとに Enter キーの入力を待つ
+  --save-file <PATH>     セーブファイルのパス（play ではセーブデータの手持ちで戦い、バトル後に書き込む）
+  --item <ID>            use-item で使う道具のキー（thunder_stone など）
+  -h, --help             この使い方を表示する
+
+コマンドを指定しない場合は環境変数 RUN_MODE（json, bin, hard_code, convert, validate）に従う";
+
+/// 実行するコマンド
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Command {
+    /// バトルを遊ぶ
+    Play,
+    /// コンピュータ同士でバトルを行う
+    Simulate,
+    /// 記録したバトルを再生する
+    Replay,
+    /// 新しいセーブデータを作成する
+    Save,
+    /// セーブデータの内容を表示する
+    Load,
+    /// セーブデータのバッグの道具を使う
+    UseItem,
+    /// JSON ファイルを検証する
+    Validate,
+    /// JSON ファイルをバイナリファイルに変換する
+    Convert,
+}
+
+/// 読み込むデータの形式
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DataFormat {
+    /// JSON ファイル
+    Json,
+    /// バイナリファイル
+    Bin,
+    /// ハードコードしたデータ
+    HardCode,
+}
+
+/// コマンドライン引数
+#[derive(Debug, Clone, PartialEq)]
+pub struct Args {
+    pub command: Command,
+    pub format: DataFormat,
+    pub data_dir: String,
+    pub bin_path: String,
+    pub seed: Option<u64>,
+    pub trainer: Option<String>,
+    pub opponent: Option<String>,
+    pub text_speed: u64,
+    pub battles: u32,
+    pub threads: u32,
+    pub player_ai: Option<AiLevel>,
+    pub opponent_ai: Option<AiLevel>,
+    pub replay_file: Option<String>,
+    pub step: bool,
+    pub save_file: Option<String>,
+    pub item: Option<String>,
+}
+
+impl Default for Args {
+    fn default() -> Self {
+        Args {
+            command: Command::Play,
+            format: DataFormat::Json,
+            data_dir: DEFAULT_DATA_DIR.to_string(),
+            bin_path: DEFAULT_BIN_PATH.to_string(),
+            seed: None,
+            trainer: None,
+            opponent: None,
+            text_speed: DEFAULT_TEXT_SPEED,
+            battles: 1,
+            threads: 1,
+            player_ai: None,
+            opponent_ai: None,
+            replay_file: None,
+            step: false,
+            save_file: None,
+            item: None,
+        }
+    }
+}
+
+/// コマンドライン引数のエラー
+#[derive(Debug, Clone, PartialEq)]
+pub enum ArgsError {
+    /// 使い方の表示が要求された
+    Help,
+    /// 未定義のコマンド
+    UnknownCommand(String),
+    /// 未定義のオプション
+    UnknownOption(String),
+    /// オプションの値がない
+    MissingValue(String),
+    /// オプションの値が不正
+    InvalidValue { option: String, value: String },
+    /// RUN_MODE の値が不正
+    UnknownRunMode(String),
+    /// 指定したトレーナーがいない
+    UnknownTrainer { trainer: String, candidates: Vec<String> },
+    /// 同時に指定できないオプション
+    Conflict(String, String),
+}
+
+impl fmt::Display for ArgsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ArgsError::Help => write!(f, "{}", USAGE),
+            ArgsError::UnknownCommand(command) => write!(f, "未定義のコマンド {} が指定されました", command),
+            ArgsError::UnknownOption(option) => write!(f, "未定義のオプション {} が指定されました", option),
+            ArgsError::MissingValue(option) => write!(f, "{} に値が指定されていません", option),
+            ArgsError::InvalidValue { option, value } => write!(f, "{} の値 {} が不正です", option, value),
+            ArgsError::UnknownRunMode(run_mode) => write!(f, "RUN_MODE の値 {} が不正です", run_mode),
+            ArgsError::UnknownTrainer { trainer, candidates } => {
+                write!(f, "トレーナー {} が見つかりません（指定できるトレーナー: {}）", trainer, candidates.join(", "))
+            }
+            ArgsError::Conflict(first, second) => write!(f, "{} と {} は同時に指定できません", first, second),
+        }
+    }
+}
+
+impl std::error::Error for ArgsError {}
+
+/// コマンドライン引数を解析
+///
+/// `--option value` と `--option=value` のどちらの書き方も受け付ける
+///
+/// # 引数
+/// * `args`     - プログラム名を除いたコマンドライン引数
+/// * `run_mode` - 環境変数 RUN_MODE の値（コマンドを指定しなかった場合に使用）
+///
+/// # 戻り値
+/// * `Args` - 解析したコマンドライン引数
+pub fn parse_args<I>(args: I, run_mode: Option<&str>) -> Result<Args, ArgsError>
+where
+    I: IntoIterator<Item = String>
+{
+    let mut parsed = Args::default();
+    let mut command = None;
+    let mut format = None;
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        if arg == "-h" || arg == "--help" {
+            return Err(ArgsError::Help);
+        }
+
+        if !arg.starts_with('-') {
+            if command.is_some() {
+                return Err(ArgsError::UnknownCommand(arg));
+            }
+            command = Some(match arg.as_str() {
+                "play" => Command::Play,
+                "simulate" => Command::Simulate,
+                "replay" => Command::Replay,
+                "save" => Command::Save,
+                "load" => Command::Load,
+                "use-item" => Command::UseItem,
+                "validate" => Command::Validate,
+                "convert" => Command::Convert,
+                _ => return Err(ArgsError::UnknownCommand(arg)),
+            });
+            continue;
+        }
+
+        // 値をとらないオプション
+        if arg == "--step" {
+            parsed.step = true;
+            continue;
+        }
+
+        let (option, inline_value) = match arg.split_once('=') {
+            Some((option, value)) => (option.to_string(), Some(value.to_string())),
+            None => (arg, None),
+        };
+        let value = match inline_value.or_else(|| args.next()) {
+            Some(value) => value,
+            None => return Err(ArgsError::MissingValue(option)),
+        };
+
+        match option.as_str() {
+            "--data-dir" => parsed.data_dir = value,
+            "--bin-file" => parsed.bin_path = value,
+            "--format" => format = Some(parse_format(&option, &value)?),
+            "--seed" => parsed.seed = Some(parse_number(&option, &value)?),
+            "--trainer" => parsed.trainer = Some(value),
+            "--opponent" => parsed.opponent = Some(value),
+            "--text-speed" => parsed.text_speed = parse_number(&option, &value)?,
+            "--battles" => parsed.battles = parse_count(&option, &value)?,
+            "--threads" => parsed.threads = parse_count(&option, &value)?,
+            "--player-ai" => parsed.player_ai = Some(parse_ai(&option, &value)?),
+            "--opponent-ai" => parsed.opponent_ai = Some(parse_ai(&option, &value)?),
+            "--replay-file" => parsed.replay_file = Some(value),
+            "--save-file" => parsed.save_file = Some(value),
+            "--item" => parsed.item = Some(value),
+            _ => return Err(ArgsError::UnknownOption(option)),
+        }
+    }
+
+    // コマンドを指定しなかった場合は RUN_MODE に従う
+    let (command, run_mode_format) = match command {
+        Some(command) => (command, None),
+        None => match run_mode.map(str::trim) {
+            None | Some("") => (Command::Play, None),
+            Some("json") => (Command::Play, Some(DataFormat::Json)),
+            Some("bin") => (Command::Play, Some(DataFormat::Bin)),
+            Some("hard_code") => (Command::Play, Some(DataFormat::HardCode)),
+            Some("convert") => (Command::Convert, None),
+            Some("validate") => (Command::Validate, None),
+            Some(run_mode) => return Err(ArgsError::UnknownRunMode(run_mode.to_string())),
+        },
+    };
+    parsed.command = command;
+    parsed.format = format.or(run_mode_format).unwrap_or(DataFormat::Json);
+
+    Ok(parsed)
+}
+
+/// データ形式のオプションを解析
+fn parse_format(option: &str, value: &str) -> Result<DataFormat, ArgsError> {
+    match value {
+        "json" => Ok(DataFormat::Json),
+        "bin" => Ok(DataFormat::Bin),
+        "hard_code" => Ok(DataFormat::HardCode),
+        _ => Err(ArgsError::InvalidValue { option: option.to_string(), value: value.to_string() }),
+    }
+}
+
+/// 0 以上の整数のオプションを解析
+fn parse_number(option: &str, value: &str) -> Result<u64, ArgsError> {
+    value
+        .trim()
+        .parse::<u64>()
+        .map_err(|_| ArgsError::InvalidValue { option: option.to_string(), value: value.to_string() })
+}
+
+/// 1 以上の回数のオプションを解析
+fn parse_count(option: &str, value: &str) -> Result<u32, ArgsError> {
+    match value.trim().parse::<u32>() {
+        Ok(count) if count > 0 => Ok(count),
+        _ => Err(ArgsError::InvalidValue { option: option.to_string(), value: value.to_string() }),
+    }
+}
+
+/// AI の強さのオプションを解析
+fn parse_ai(option: &str, value: &str) -> Result<AiLevel, ArgsError> {
+    match value {
+        "random" => Ok(AiLevel::Random),
+        "greedy" => Ok(AiLevel::Greedy),
+        "heuristic" => Ok(AiLevel::Heuristic),
+        "expectiminimax" => Ok(AiLevel::Expectiminimax),
+        _ => Err(ArgsError::InvalidValue { option: option.to_string(), value: value.to_string() }),
+    }
+}
@@ -1,109 +1,561 @@
-use std::{thread, time::Duration};
+use std::collections::HashMap;
 
-use crate::{logic::rand::XorShift128, models::pokemon::Pokemon, services::battle::pokemon::PokemonActions};
-use super::print::{clear_screen, clear_and_print_current_battle_status, print_letter_with_delay, print_skill_list};
+use pokemon_battle_system::{
+    models::{
+        item::{Item, ItemKind},
+        pokemon::{Pokemon, Screen, StatusAilment},
+        replay::Replay,
+        save::SaveData,
+        trainer::BattleAction
+    },
+    services::{
+        ai::strategy::{decide_action, decide_replacement, interactive_strategy_for, AiStrategy},
+        battle::{
+            element::Effectiveness,
+            engine::{Battle, TurnActions},
+            event::{BattleEvent, CantMoveReason, Side},
+            pokemon::PokemonActions,
+            trainer::TrainerActions
+        },
+        replay::ReplayActions,
+        save::SaveActions
+    }
+};
+use super::{
+    print::{clear_screen, clear_and_print_current_battle_status, print_action_menu, print_letter_with_delay, print_skill_list, wait},
+    prompt::{select_ball, select_skill_to_forget, select_switch_pokemon}
+};
+
+/// 画面に表示しているポケモンの状態
+///
+/// イベントを順番に描画するため、エンジンの最終状態とは別に保持する
+struct BattleView {
+    player: Pokemon,
+    opponent: Pokemon,
+}
+
+impl BattleView {
+    /// 現在の場のポケモンから表示用の状態を作成
+    fn new(battle: &Battle) -> Self {
+        BattleView {
+            player: battle.player.active_pokemon().clone(),
+            opponent: battle.opponent.active_pokemon().clone(),
+        }
+    }
+
+    /// 陣営の表示中のポケモンを可変参照で取得
+    fn pokemon_mut(&mut self, side: Side) -> &mut Pokemon {
+        match side {
+            Side::Player => &mut self.player,
+            Side::Opponent => &mut self.opponent,
+        }
+    }
+
+    /// 画面をクリアしHPバーを描画
+    fn redraw(&self) {
+        clear_and_print_current_battle_status(&self.player, &self.opponent);
+    }
+}
 
 /// バトル開始
-/// 
+///
+/// どちらかのトレーナーの手持ちが全員ひんしになるか、やせいのポケモンを捕まえるまで続ける
+///
 /// # 引数
-/// * `self_` - 自分のポケモン
-/// * `enemy` - 敵のポケモン
-pub fn start_battle(self_: &mut Pokemon, enemy: &mut Pokemon) {
+/// * `battle` - バトルの状態
+/// * `replay` - 行動を記録するリプレイ
+/// * `save`   - ボールを取り出すバッグを持つセーブデータ
+/// * `items`  - 道具データ
+pub fn start_battle(battle: &mut Battle, replay: &mut Replay, save: &mut SaveData, items: &HashMap<String, Item>) {
+    print_battle_start(battle);
+
+    let strategy = interactive_strategy_for(&battle.opponent);
+    while !battle.is_over() {
+        // 現在のHPバーを描画
+        clear_and_print_current_battle_status(battle.player.active_pokemon(), battle.opponent.active_pokemon());
+
+        // 自分の行動を選択
+        wait(1000);
+        let Some(action) = select_action(battle, save, items) else {
+            continue;
+        };
+
+        // 相手の行動をトレーナーの AI で選択
+        let enemy_action = decide_action(battle, Side::Opponent, strategy.as_ref());
+
+        // 1ターン分の行動を処理して描画
+        let actions = TurnActions { player: action, opponent: enemy_action };
+        replay.record_turn(actions);
+        let mut view = BattleView::new(battle);
+        let events = battle.submit_actions(actions);
+        render_events(battle, &mut view, &events);
+
+        // レベルアップで覚えようとしている技を処理
+        learn_pending_skills(battle, replay);
+
+        // ひんしになったポケモンを入れ替える
+        replace_fainted_pokemon(battle, strategy.as_ref(), replay);
+    }
+
+    print_battle_result(battle);
+}
+
+/// リプレイを再生
+///
+/// 記録した行動をバトルエンジンで処理し直し、通常のバトルと同じように描画する
+///
+/// # 引数
+/// * `replay` - リプレイ
+/// * `step`   - 1 ターンごとに Enter キーの入力を待つかどうか
+pub fn play_replay(replay: &Replay, step: bool) {
+    let mut battle = replay.initial_battle();
+    print_battle_start(&battle);
+
+    for turn in &replay.turns {
+        if battle.is_over() {
+            break;
+        }
+
+        // 現在のHPバーを描画
+        clear_and_print_current_battle_status(battle.player.active_pokemon(), battle.opponent.active_pokemon());
+        if step {
+            println!("{} ターン目（Enter で次のターンへ）", battle.turn + 1);
+            let mut input = String::new();
+            std::io::stdin()
+                .read_line(&mut input)
+                .expect("Failed to read line");
+        } else {
+            wait(1000);
+        }
+
+        // 1ターン分の行動を処理して描画
+        if let BattleAction::Ball(_) = turn.player {
+            print_letter_with_delay(&format!("{}は\nボールを　なげた！", battle.player.name));
+        }
+        let mut view = BattleView::new(&battle);
+        let events = battle.submit_actions(TurnActions { player: turn.player, opponent: turn.opponent });
+        render_events(&battle, &mut view, &events);
+
+        // 覚えようとしている技を記録どおりに処理
+        for &forget in &turn.skill_choices {
+            let mut view = BattleView::new(&battle);
+            let events = battle.resolve_pending_skill(forget);
+            render_events(&battle, &mut view, &events);
+        }
+
+        // ひんしになったポケモンを記録どおりに入れ替える
+        for (side, idx) in [(Side::Opponent, turn.opponent_replacement), (Side::Player, turn.player_replacement)] {
+            if let Some(idx) = idx {
+                let mut view = BattleView::new(&battle);
+                let events = battle.submit_replacement(side, idx);
+                render_events(&battle, &mut view, &events);
+            }
+        }
+    }
+
+    if battle.is_over() {
+        print_battle_result(&battle);
+    } else {
+        clear_and_print_current_battle_status(battle.player.active_pokemon(), battle.opponent.active_pokemon());
+        print_letter_with_delay("リプレイは　ここで　おわっている");
+    }
+}
+
+/// バトル開始時のメッセージを描画
+///
+/// # 引数
+/// * `battle` - バトルの状態
+fn print_battle_start(battle: &Battle) {
     // 画面クリア
     clear_screen();
 
     // テキストアニメーションを描画
-    print_letter_with_delay("あ!　やせいの");
-    print_letter_with_delay(&format!("{}が　とびだしてきた！", enemy.name));
+    let opponent = &battle.opponent;
+    if battle.wild {
+        print_letter_with_delay("あ!　やせいの");
+        print_letter_with_delay(&format!("{}が　とびだしてきた！", opponent.active_pokemon().name()));
+    } else {
+        print_letter_with_delay(&format!("{}が\nしょうぶを しかけてきた！", opponent.name));
+        print_letter_with_delay(&format!("{}は\n{}を くりだした！", opponent.name, opponent.active_pokemon().name()));
+    }
+    print_letter_with_delay(&format!("ゆけっ！　{}！", battle.player.active_pokemon().name()));
 
     // 演出上の遅延
-    thread::sleep(Duration::from_millis(2000));
-
-    // 現在のHPバーを描画
-    clear_and_print_current_battle_status(&self_, &enemy);
-
-    while self_.status.current_hp > 0 && enemy.status.current_hp > 0 {
-        // 技リストの描画
-        thread::sleep(Duration::from_millis(1000));
-        print_skill_list(&self_);
-
-        // 自分のターン
-        let mut input = String::new();
-        std::io::stdin()
-            .read_line(&mut input)
-            .expect("Failed to read line");
-
-        let skill_idx = match input.trim() {
-            "1" => 0,
-            "2" => 1,
-            "3" => 2,
-            "4" => 3,
-            "5" => 4,
-            _ => continue,
-        };
+    wait(2000);
+}
+
+/// バトルの勝敗を描画
+///
+/// # 引数
+/// * `battle` - 終了したバトルの状態
+fn print_battle_result(battle: &Battle) {
+    // 捕まえた場合はボールのイベントで表示済み
+    if battle.caught {
+        return;
+    }
+
+    clear_and_print_current_battle_status(battle.player.active_pokemon(), battle.opponent.active_pokemon());
+    if battle.winner() == Some(Side::Player) {
+        let opponent_name = if battle.wild { battle.opponent.active_pokemon().name() } else { &battle.opponent.name };
+        print_letter_with_delay(&format!("{}との　しょうぶに かった!", opponent_name));
+    } else {
+        print_letter_with_delay(&format!("{}の　てもとには\nたたかえる　ポケモンが　いない！", battle.player.name));
+        wait(1000);
+        print_letter_with_delay("めのまえが　まっくらに　なった");
+    }
+}
+
+/// 自分の行動を選択
+///
+/// ボールはやせいのポケモンとのバトルでのみ選択でき、選択した時点でバッグから取り出す
+/// 力を溜めている場合は選択せずに溜めている技を使う
+///
+/// # 引数
+/// * `battle` - バトルの状態
+/// * `save`   - ボールを取り出すバッグを持つセーブデータ
+/// * `items`  - 道具データ
+///
+/// # 戻り値
+/// * `Option<BattleAction>` - 選択した行動、選びなおす場合は None
+fn select_action(battle: &Battle, save: &mut SaveData, items: &HashMap<String, Item>) -> Option<BattleAction> {
+    // 力を溜めている場合は溜めている技を使う
+    if let Some(skill_idx) = battle.player.active_pokemon().volatile.charging {
+        return Some(BattleAction::Fight(skill_idx));
+    }
 
-        // 攻撃
-        clear_and_print_current_battle_status(&self_, &enemy);
-        self_.attack(skill_idx, enemy);
-        clear_and_print_current_battle_status(self_, enemy);
+    print_action_menu(battle.wild);
 
-        // 相手のターン
-        let skill_idx = XorShift128::random_in_range(0, 4) as usize;
+    let mut input = String::new();
+    std::io::stdin()
+        .read_line(&mut input)
+        .expect("Failed to read line");
 
-        if enemy.status.current_hp > 0 {
-            enemy.attack(skill_idx, self_);
-            clear_and_print_current_battle_status(self_, enemy);
+    match input.trim() {
+        "1" => select_skill(battle, Side::Player).map(BattleAction::Fight),
+        "2" => select_switch_pokemon(&battle.player, false).map(BattleAction::Switch),
+        "3" if battle.wild => {
+            let key = select_ball(&save.bag, items)?;
+            let ItemKind::Ball(ball) = items[&key].kind else {
+                return None;
+            };
+            save.take_item(&key);
+            print_letter_with_delay(&format!("{}は\n{}を　なげた！", battle.player.name, items[&key].name));
+            Some(BattleAction::Ball(ball))
         }
+        _ => None,
     }
+}
 
-    if self_.status.current_hp > 0 {
-        print_letter_with_delay(&format!("てきの　{}　はたおれた！", enemy.name));
-        thread::sleep(Duration::from_millis(2000));
-        clear_and_print_current_battle_status(&self_, &enemy);
-        print_letter_with_delay(&format!("{}との　しょうぶに かった!", enemy.name));
-    } else {
-        print_letter_with_delay(&format!("{}は　たおれた！", self_.name));
-        thread::sleep(Duration::from_millis(2000));
-        clear_and_print_current_battle_status(&self_, &enemy);
-        print_letter_with_delay(&format!("めのまえが　まっくらに　なった"));
+/// 使用する技を選択
+///
+/// すべての技の PP がない場合はわるあがきになるため選択しない
+///
+/// # 引数
+/// * `battle` - バトルの状態
+/// * `side`   - 技を選択する陣営
+///
+/// # 戻り値
+/// * `Option<usize>` - 選択した技のインデックス、選びなおす場合は None
+fn select_skill(battle: &Battle, side: Side) -> Option<usize> {
+    let pokemon = battle.trainer(side).active_pokemon();
+    if !pokemon.has_usable_skill() {
+        return Some(0);
+    }
+
+    // 技リストの描画
+    print_skill_list(pokemon);
+
+    let mut input = String::new();
+    std::io::stdin()
+        .read_line(&mut input)
+        .expect("Failed to read line");
+
+    let skill_idx = match input.trim() {
+        "1" => 0,
+        "2" => 1,
+        "3" => 2,
+        "4" => 3,
+        _ => return None,
+    };
+
+    match pokemon.skills.get(skill_idx) {
+        Some(learned) if learned.current_pp == 0 => {
+            print_letter_with_delay("わざの のこりポイントが ない！");
+            None
+        }
+        Some(_) => Some(skill_idx),
+        None => None,
     }
 }
 
-// TODO ポケモンのすばやさ技の優先度を比較して行動順を決定
-// pub fn battle_round(self_: &mut Pokemon, enemy: &mut Pokemon) {
-//     // 並び順の取得
-//     let order = Pokemon::get_battle_order(self_.clone(), enemy.clone());
-
-//     // ポケモンのターンを順番に処理
-//     for pokemon in &order {
-//         // 自分のターン
-//         if pokemon == self_ {
-//             let mut input = String::new();
-//             std::io::stdin()
-//                 .read_line(&mut input)
-//                 .expect("Failed to read line");
-
-//             let skill_idx = match input.trim() {
-//                 "1" => 0,
-//                 "2" => 1,
-//                 "3" => 2,
-//                 "4" => 3,
-//                 "5" => 4,
-//                 _ => continue,
-//             };
-
-//             // 攻撃を行う
-//             clear_and_print_current_battle_status(&self_, &enemy);
-//             self_.attack(skill_idx, enemy);
-//             clear_and_print_current_battle_status(self_, enemy);
-//         } else if pokemon == enemy {
-//             // 相手のターン
-//             let skill_idx = XorShift128::random_in_range(0, 4) as usize;
-
-//             if enemy.status.current_hp > 0 {
-//                 enemy.attack(skill_idx, self_);
-//                 clear_and_print_current_battle_status(self_, enemy);
-//             }
-//         }
-//     }
-// }
\ No newline at end of file
+/// レベルアップで覚えようとしている技を、忘れる技を選んで覚える
+///
+/// # 引数
+/// * `battle` - バトルの状態
+/// * `replay` - 選択を記録するリプレイ
+fn learn_pending_skills(battle: &mut Battle, replay: &mut Replay) {
+    while let Some(pending) = battle.pending_skills.first() {
+        let forget = select_skill_to_forget(&battle.player.pokemons[pending.idx], &pending.skill);
+        replay.record_skill_choice(forget);
+        let mut view = BattleView::new(battle);
+        let events = battle.resolve_pending_skill(forget);
+        render_events(battle, &mut view, &events);
+    }
+}
+
+/// ひんしになったポケモンを次のポケモンと入れ替える
+///
+/// 自分は手持ちから選択し、相手はトレーナーの AI で選択する
+///
+/// # 引数
+/// * `battle`   - バトルの状態
+/// * `strategy` - 相手の AI
+/// * `replay`   - 交代先を記録するリプレイ
+fn replace_fainted_pokemon(battle: &mut Battle, strategy: &dyn AiStrategy, replay: &mut Replay) {
+    if battle.is_over() {
+        return;
+    }
+
+    if battle.needs_replacement(Side::Opponent) {
+        if let Some(idx) = decide_replacement(battle, Side::Opponent, strategy) {
+            replay.record_replacement(Side::Opponent, idx);
+            let mut view = BattleView::new(battle);
+            let events = battle.submit_replacement(Side::Opponent, idx);
+            render_events(battle, &mut view, &events);
+        }
+    }
+
+    if battle.needs_replacement(Side::Player) {
+        if let Some(idx) = select_switch_pokemon(&battle.player, true) {
+            replay.record_replacement(Side::Player, idx);
+            let mut view = BattleView::new(battle);
+            let events = battle.submit_replacement(Side::Player, idx);
+            render_events(battle, &mut view, &events);
+        }
+    }
+}
+
+/// バトルのイベントを順番に描画
+///
+/// # 引数
+/// * `battle` - イベント処理後のバトルの状態
+/// * `view`   - 表示中のポケモンの状態
+/// * `events` - 描画するイベント
+fn render_events(battle: &Battle, view: &mut BattleView, events: &[BattleEvent]) {
+    for event in events {
+        match event {
+            BattleEvent::SkillUsed { pokemon, skill, .. } => {
+                print_letter_with_delay(&format!("{}の\n{}！", pokemon, skill));
+                wait(1000);
+            }
+            BattleEvent::Struggle { pokemon, .. } => {
+                print_letter_with_delay(&format!("{}は\nだせる わざが ない！", pokemon));
+            }
+            BattleEvent::NoPp { .. } => {
+                print_letter_with_delay("しかし わざの のこりポイントが なかった！");
+                wait(1000);
+            }
+            BattleEvent::InvalidAction { side } => {
+                eprintln!("Error: Invalid action for {:?}", side);
+            }
+            BattleEvent::Charging { pokemon, .. } => {
+                print_letter_with_delay(&format!("{}は\nちからを ためている！", pokemon));
+                wait(1000);
+            }
+            BattleEvent::Missed { pokemon, .. } => {
+                print_letter_with_delay(&format!("{}には\n当たらなかった！", pokemon));
+                wait(1000);
+            }
+            BattleEvent::Effectiveness { pokemon, effectiveness, .. } => {
+                if let Some(message) = effectiveness.message() {
+                    if *effectiveness == Effectiveness::NoEffect {
+                        print_letter_with_delay(&format!("{}には\n{}", pokemon, message));
+                    } else {
+                        print_letter_with_delay(message);
+                    }
+                }
+            }
+            BattleEvent::Critical { .. } => {
+                print_letter_with_delay("きゅうしょに あたった！");
+            }
+            BattleEvent::Damage { side, current_hp, .. } => {
+                view.pokemon_mut(*side).status.current_hp = *current_hp;
+                view.redraw();
+            }
+            BattleEvent::OneHitKO { side, .. } => {
+                view.pokemon_mut(*side).status.current_hp = 0;
+                view.redraw();
+                print_letter_with_delay("一撃必殺！");
+            }
+            BattleEvent::StatChanged { pokemon, status, value, .. } => {
+                let status_change = if *value > 0 { "あがった" } else { "さがった" };
+                let status_change_more = if value.abs() >= 2 { "ぐーんと" } else { "" };
+                print_letter_with_delay(&format!("{}の{}が{}{}", pokemon, status, status_change_more, status_change));
+            }
+            BattleEvent::ScreenSet { screen, .. } => {
+                let message = match screen {
+                    Screen::Reflect => "リフレクターで\nぶつりこうげきに つよくなった！",
+                    Screen::LightScreen => "ひかりのかべで\nとくしゅこうげきに つよくなった！",
+                };
+                print_letter_with_delay(message);
+            }
+            BattleEvent::ScreenBroken { side, screen } => {
+                print_letter_with_delay(&format!("{}{}が\nこわれた！", side_prefix(*side), screen.as_str()));
+            }
+            BattleEvent::ScreenEnded { side, screen } => {
+                print_letter_with_delay(&format!("{}{}の\nこうかが きれた！", side_prefix(*side), screen.as_str()));
+            }
+            BattleEvent::CriticalStageRaised { pokemon, .. } => {
+                print_letter_with_delay(&format!("{}は\nはりきっている！", pokemon));
+            }
+            BattleEvent::AilmentInflicted { side, pokemon, ailment } => {
+                view.pokemon_mut(*side).ailment = Some(*ailment);
+                let message = match ailment {
+                    StatusAilment::Burn => "やけどを おった！",
+                    StatusAilment::Poison => "どくを あびた！",
+                    StatusAilment::BadPoison => "もうどくを あびた！",
+                    StatusAilment::Paralysis => "まひして わざが でにくくなった！",
+                    StatusAilment::Sleep => "ねむってしまった！",
+                    StatusAilment::Freeze => "こおりづけに なった！",
+                };
+                print_letter_with_delay(&format!("{}は\n{}", pokemon, message));
+            }
+            BattleEvent::AilmentCured { side, pokemon, ailment } => {
+                view.pokemon_mut(*side).ailment = None;
+                let message = match ailment {
+                    StatusAilment::Sleep => "めを さました！",
+                    StatusAilment::Freeze => "こおりが とけた！",
+                    _ => "じょうたいいじょうが なおった！",
+                };
+                print_letter_with_delay(&format!("{}は\n{}", pokemon, message));
+            }
+            BattleEvent::Failed { .. } => {
+                print_letter_with_delay("しかし うまく きまらなかった！");
+            }
+            BattleEvent::Confused { pokemon, .. } => {
+                print_letter_with_delay(&format!("{}は\nこんらんした！", pokemon));
+            }
+            BattleEvent::IsConfused { pokemon, .. } => {
+                print_letter_with_delay(&format!("{}は\nこんらんしている！", pokemon));
+            }
+            BattleEvent::ConfusionEnded { pokemon, .. } => {
+                print_letter_with_delay(&format!("{}の\nこんらんが とけた！", pokemon));
+            }
+            BattleEvent::HurtItself { side, current_hp, .. } => {
+                print_letter_with_delay("わけも わからず\nじぶんを こうげきした！");
+                wait(1000);
+                view.pokemon_mut(*side).status.current_hp = *current_hp;
+                view.redraw();
+            }
+            BattleEvent::CantMove { pokemon, reason, .. } => {
+                let message = match reason {
+                    CantMoveReason::Sleep => "ぐうぐう ねむっている",
+                    CantMoveReason::Freeze => "こおってしまって うごかない！",
+                    CantMoveReason::Flinch => "ひるんで わざが だせない！",
+                    CantMoveReason::Paralysis => "からだが しびれて うごけない！",
+                };
+                print_letter_with_delay(&format!("{}は\n{}", pokemon, message));
+                wait(1000);
+            }
+            BattleEvent::ResidualDamage { side, pokemon, ailment, current_hp, .. } => {
+                view.pokemon_mut(*side).status.current_hp = *current_hp;
+                view.redraw();
+                let message = match ailment {
+                    StatusAilment::Burn => "やけどの",
+                    _ => "どくの",
+                };
+                print_letter_with_delay(&format!("{}は\n{} ダメージを うけている！", pokemon, message));
+                wait(1000);
+            }
+            BattleEvent::Recoil { side, pokemon, current_hp, .. } => {
+                view.pokemon_mut(*side).status.current_hp = *current_hp;
+                view.redraw();
+                print_letter_with_delay(&format!("{}は\nはんどうで ダメージを うけた！", pokemon));
+            }
+            BattleEvent::SwitchedOut { side, pokemon } => match side {
+                Side::Player => print_letter_with_delay(&format!("もどれ！　{}！", pokemon)),
+                Side::Opponent => print_letter_with_delay(&format!("{}は\n{}を ひっこめた！", battle.opponent.name, pokemon)),
+            },
+            BattleEvent::SwitchedIn { side, pokemon, idx, current_hp } => {
+                match side {
+                    Side::Player => print_letter_with_delay(&format!("ゆけっ！　{}！", pokemon)),
+                    Side::Opponent => print_letter_with_delay(&format!("{}は\n{}を くりだした！", battle.opponent.name, pokemon)),
+                }
+                let shown = view.pokemon_mut(*side);
+                *shown = battle.trainer(*side).pokemons[*idx].clone();
+                shown.status.current_hp = *current_hp;
+                view.redraw();
+            }
+            BattleEvent::Fainted { side, pokemon } => {
+                match side {
+                    Side::Opponent => {
+                        let prefix = if battle.wild { "やせいの" } else { "てきの" };
+                        print_letter_with_delay(&format!("{}　{}は　たおれた！", prefix, pokemon));
+                    }
+                    Side::Player => print_letter_with_delay(&format!("{}は　たおれた！", pokemon)),
+                }
+                wait(1000);
+            }
+            BattleEvent::ExpGained { pokemon, exp, .. } => {
+                print_letter_with_delay(&format!("{}は　{}　けいけんちを\nもらった！", pokemon, exp));
+                wait(1000);
+            }
+            BattleEvent::LevelUp { side, pokemon, idx, level, max_hp, current_hp } => {
+                // 場に出ているポケモンならHPバーとレベルの表示を更新
+                if battle.trainer(*side).active_idx == *idx {
+                    let shown = view.pokemon_mut(*side);
+                    shown.level = *level;
+                    shown.status.hp.value = *max_hp;
+                    shown.status.current_hp = *current_hp;
+                    view.redraw();
+                }
+                print_letter_with_delay(&format!("{}は\nレベル {} に あがった！", pokemon, level));
+                wait(1000);
+            }
+            BattleEvent::SkillLearned { pokemon, skill, .. } => {
+                print_letter_with_delay(&format!("{}は　あたらしく\n{}を　おぼえた！", pokemon, skill));
+                wait(1000);
+            }
+            BattleEvent::SkillForgotten { pokemon, skill, .. } => {
+                print_letter_with_delay("1　2の　……　ポカン！");
+                print_letter_with_delay(&format!("{}は　{}の\nつかいかたを　きれいに　わすれた！　そして……", pokemon, skill));
+            }
+            BattleEvent::SkillNotLearned { pokemon, skill, .. } => {
+                print_letter_with_delay(&format!("{}は　{}を\nおぼえずに　おわった！", pokemon, skill));
+                wait(1000);
+            }
+            BattleEvent::BallThrown { pokemon, shakes, caught, .. } => {
+                for _ in 0..*shakes {
+                    wait(1000);
+                    print_letter_with_delay("……ゆらっ");
+                }
+                wait(1000);
+                let message = match (caught, shakes) {
+                    (true, _) => format!("やったー！\n{}を　つかまえたぞ！", pokemon),
+                    (false, 0) => "ああ！\nポケモンが　ボールから　でてしまった！".to_string(),
+                    (false, 1) => "ああっ！\nつかまえたと　おもったのに！".to_string(),
+                    (false, 2) => "ざんねん！\nもうすこしで　つかまえられたのに！".to_string(),
+                    (false, _) => "あーっ！\nおしい！　もうちょっと　だったのに！".to_string(),
+                };
+                print_letter_with_delay(&message);
+                wait(1000);
+            }
+            BattleEvent::BattleEnded { .. } => {}
+        }
+    }
+    wait(1000);
+}
+
+/// 陣営を表す接頭辞を取得
+///
+/// # 引数
+/// * `side` - 陣営
+///
+/// # 戻り値
+/// * `&'static str` - 自分の陣営は「みかたの」、相手の陣営は「あいての」
+fn side_prefix(side: Side) -> &'static str {
+    match side {
+        Side::Player => "みかたの　",
+        Side::Opponent => "あいての　",
+    }
+}
@@ -0,0 +1,91 @@
+use std::{collections::HashMap, sync::Arc};
+
+use pokemon_battle_system::{
+    models::{pokemon::{Pokemon, Species}, trainer::Trainer},
+    services::{
+        battle::learnset::LearnsetActions,
+        evolution::{EvolutionActions, EvolutionTrigger}
+    }
+};
+use super::{
+    print::{clear_screen, print_letter_with_delay, wait},
+    prompt::select_skill_to_forget
+};
+
+/// バトル中にレベルが上がったポケモンのうち、進化の条件を満たしたポケモンを進化させる
+///
+/// ひんしのポケモンは進化しない
+///
+/// # 引数
+/// * `trainer`       - バトル後の自分のトレーナー
+/// * `levels_before` - バトル開始時の手持ちのレベル（手持ちの順）
+/// * `species`       - 図鑑番号をキーにした種族
+pub fn evolve_party(trainer: &mut Trainer, levels_before: &[u8], species: &HashMap<String, Arc<Species>>) {
+    for (idx, pokemon) in trainer.pokemons.iter_mut().enumerate() {
+        let leveled_up = levels_before.get(idx).is_some_and(|&level| pokemon.level > level);
+        if !leveled_up || pokemon.status.current_hp == 0 {
+            continue;
+        }
+
+        if let Some(into) = pokemon.evolution_target(EvolutionTrigger::LevelUp).and_then(|id| species.get(id)) {
+            evolve_pokemon(pokemon, into.clone());
+        }
+    }
+}
+
+/// 進化の演出を表示し、キャンセルしなければ進化させる
+///
+/// 進化した場合は、進化先の種族が今のレベルで覚える技を覚える（技が 4 つの場合は忘れる技を選ぶ）
+///
+/// # 引数
+/// * `pokemon` - 進化するポケモン
+/// * `species` - 進化先の種族
+///
+/// # 戻り値
+/// * `bool` - 進化したかどうか（キャンセルした場合は false）
+pub fn evolve_pokemon(pokemon: &mut Pokemon, species: Arc<Species>) -> bool {
+    clear_screen();
+    print_letter_with_delay(&format!("おや……!?\n{}の　ようすが……！", pokemon.name()));
+    wait(1000);
+
+    loop {
+        println!("1: みまもる　0: しんかを　とめる");
+        println!("=============================================");
+
+        let mut choice = String::new();
+        std::io::stdin().read_line(&mut choice).unwrap();
+        match choice.trim().parse::<usize>() {
+            Ok(1) => break,
+            Ok(0) => {
+                print_letter_with_delay(&format!("あれ……？\n{}の　へんかが　とまった！", pokemon.name()));
+                wait(1000);
+                return false;
+            }
+            _ => {
+                println!("もう一度選びなおしてください。");
+                wait(1000);
+            }
+        }
+    }
+
+    let before = pokemon.name().to_string();
+    pokemon.evolve(species);
+    print_letter_with_delay(&format!("おめでとう！　{}は\n{}に　しんかした！", before, pokemon.species.name));
+    wait(1000);
+
+    for skill in pokemon.skills_learned_at(pokemon.level) {
+        if !pokemon.learn_skill(skill.clone()) {
+            let Some(forgotten) = select_skill_to_forget(pokemon, &skill).and_then(|slot| pokemon.replace_skill(slot, skill.clone())) else {
+                print_letter_with_delay(&format!("{}は　{}を\nおぼえずに　おわった！", pokemon.name(), skill.name));
+                wait(1000);
+                continue;
+            };
+            print_letter_with_delay("1　2の　……　ポカン！");
+            print_letter_with_delay(&format!("{}は　{}の\nつかいかたを　きれいに　わすれた！　そして……", pokemon.name(), forgotten.name));
+        }
+        print_letter_with_delay(&format!("{}は　あたらしく\n{}を　おぼえた！", pokemon.name(), skill.name));
+        wait(1000);
+    }
+
+    true
+}
@@ -1,3 +1,7 @@
+pub mod args;
 pub mod battle;
+pub mod evolution;
 pub mod print;
-pub mod prompt;
\ No newline at end of file
+pub mod prompt;
+pub mod save;
+pub mod simulate;
\ No newline at end of file
@@ -1,8 +1,35 @@
 use std::io::stdout;
 use std::io::Write;
-use std::{thread, time::Duration};
+use std::{sync::atomic::{AtomicU64, Ordering}, thread, time::Duration};
 
-use crate::models::pokemon::Pokemon;
+use pokemon_battle_system::models::pokemon::Pokemon;
+
+/// 1 文字あたりの表示時間の既定値（ミリ秒）
+pub const DEFAULT_TEXT_SPEED: u64 = 60;
+
+/// 1 文字あたりの表示時間（ミリ秒）
+static TEXT_SPEED: AtomicU64 = AtomicU64::new(DEFAULT_TEXT_SPEED);
+
+/// テキストの表示速度を設定
+///
+/// 演出上の待ち時間も同じ比率で変わる
+///
+/// # 引数
+/// * `millis` - 1 文字あたりの表示時間（ミリ秒、0 で待ち時間なし）
+pub fn set_text_speed(millis: u64) {
+    TEXT_SPEED.store(millis, Ordering::Relaxed);
+}
+
+/// 演出上の待ち時間だけ待つ
+///
+/// # 引数
+/// * `millis` - 既定の表示速度での待ち時間（ミリ秒）
+pub fn wait(millis: u64) {
+    let millis = millis * TEXT_SPEED.load(Ordering::Relaxed) / DEFAULT_TEXT_SPEED;
+    if millis > 0 {
+        thread::sleep(Duration::from_millis(millis));
+    }
+}
 
 /// 現在のHPバーを出力
 /// 
@@ -11,19 +38,19 @@ use crate::models::pokemon::Pokemon;
 /// * `enemy_poke`   - 敵のポケモン
 #[rustfmt::skip]
 pub fn print_current_battle_status(self_pokemon: &Pokemon, enemy_poke: &Pokemon) {
-  println!("　{}:L{}", enemy_poke.name, enemy_poke.level);
+  println!("　{}:L{}{}", enemy_poke.name(), enemy_poke.level, get_ailment_label(enemy_poke));
   println!("|　HP: {}", get_hp_bar(enemy_poke.status.current_hp, enemy_poke.status.hp.value, 15));
   println!("------------------------▶");
 
-  println!("");
-  println!("");
+  println!();
+  println!();
   
-  println!("　　　　　　　　{}:L{}", self_pokemon.name, self_pokemon.level);
+  println!("　　　　　　　　{}:L{}{}", self_pokemon.name(), self_pokemon.level, get_ailment_label(self_pokemon));
   println!("　　　　　　　　HP: {}", get_hp_bar(self_pokemon.status.current_hp, self_pokemon.status.hp.value, 15));
   println!("　　　　　　　　　　　{}/ 　{}　　　　|", self_pokemon.status.current_hp, self_pokemon.status.hp.value);
   println!("　　　　　　　◀------------------------");
 
-  println!("");
+  println!();
   println!("=============================================");
 }
 
@@ -33,7 +60,7 @@ pub fn print_current_battle_status(self_pokemon: &Pokemon, enemy_poke: &Pokemon)
 /// * `hp`          - 現在のHP
 /// * `max_hp`      - 最大HP
 /// * `max_bar_len` - 最大HPバー
-fn get_hp_bar(hp: u8, max_hp: u8, max_bar_len: u8) -> String {
+fn get_hp_bar(hp: u16, max_hp: u16, max_bar_len: u8) -> String {
     let mut bar = String::new();
     let bar_len = (hp as f32 / max_hp as f32 * max_bar_len as f32) as u8;
     for _ in 0..bar_len {
@@ -45,14 +72,41 @@ fn get_hp_bar(hp: u8, max_hp: u8, max_bar_len: u8) -> String {
     bar
 }
 
+/// 状態異常の表示ラベルを取得
+/// 
+/// # 引数
+/// * `pokemon` - ポケモン
+fn get_ailment_label(pokemon: &Pokemon) -> String {
+    match pokemon.ailment {
+        Some(ailment) => format!("　[{}]", ailment.as_str()),
+        None => String::new(),
+    }
+}
+
 /// 技一覧を出力
 /// 
 /// # 引数
 /// * `self_pokemon` - 自分のポケモン
 pub fn print_skill_list(self_pokemon: &Pokemon) {
     let pd = "　　　　　　　　　　　　";
-    for (i, skill) in self_pokemon.skills.iter().enumerate() {
-        println!("{}||　{}.{}", pd, i + 1, skill.name);
+    for (i, learned) in self_pokemon.skills.iter().enumerate() {
+        // PP がない技は選択不可として表示
+        let disabled = if learned.current_pp == 0 { "　×" } else { "" };
+        println!(
+            "{}||　{}.{}　{}/{}{}",
+            pd, i + 1, learned.skill.name, learned.current_pp, learned.max_pp, disabled
+        );
+    }
+    println!("{}========================", pd)
+}
+
+/// 行動の選択肢を出力（やせいのポケモンとのバトルではボールも選べる）
+pub fn print_action_menu(wild: bool) {
+    let pd = "　　　　　　　　　　　　";
+    if wild {
+        println!("{}||　1.たたかう　2.ポケモン　3.ボール", pd);
+    } else {
+        println!("{}||　1.たたかう　2.ポケモン", pd);
     }
     println!("{}========================", pd)
 }
@@ -65,9 +119,9 @@ pub fn print_letter_with_delay(text: &str) {
     for c in text.chars() {
         print!("{}", c);
         let _ = stdout().flush();
-        thread::sleep(Duration::from_millis(60));
+        wait(DEFAULT_TEXT_SPEED);
     }
-    println!("");
+    println!();
 }
 
 /// 画面をクリア
@@ -1,7 +1,10 @@
-use std::{thread, time::Duration};
+use std::collections::{BTreeMap, HashMap};
 
-use crate::{models::trainer::Trainer, services::battle::trainer::TrainerActions};
-use super::print::{clear_screen, print_letter_with_delay};
+use pokemon_battle_system::{
+    models::{item::{Item, ItemKind}, pokemon::{Pokemon, Skill}, trainer::Trainer},
+    services::battle::trainer::TrainerActions
+};
+use super::print::{clear_screen, print_letter_with_delay, wait};
 
 /// トレーナーを選択
 /// 
@@ -9,8 +12,8 @@ use super::print::{clear_screen, print_letter_with_delay};
 /// * `trainers` - トレーナーのリスト
 /// 
 /// # 戻り値
-/// * `&mut Trainer` - 選択したトレーナー
-pub fn select_trainer(trainers: &mut [Trainer]) -> &mut Trainer {
+/// * `usize` - 選択したトレーナーのインデックス
+pub fn select_trainer(trainers: &[Trainer]) -> usize {
     loop {
         clear_screen();
         print_letter_with_delay("トレーナーを選んでください：");
@@ -37,15 +40,54 @@ pub fn select_trainer(trainers: &mut [Trainer]) -> &mut Trainer {
                 "{} はポケモンを所持していません。\n別のトレーナーを選んでください。",
                 trainers[choice].name
             );
-            thread::sleep(Duration::from_millis(2000));
+            wait(2000);
             continue;
         }
 
-        return &mut trainers[choice];
+        return choice;
     }
 }
 
-/// ポケモンを選択
+/// 対戦相手を選択
+/// 
+/// # 引数
+/// * `trainers`  - トレーナーのリスト
+/// * `player_id` - プレイヤーのトレーナーのキー
+/// 
+/// # 戻り値
+/// * `Option<usize>` - 選択したトレーナーのインデックス、やせいのポケモンと戦う場合は None
+pub fn select_opponent(trainers: &[Trainer], player_id: &str) -> Option<usize> {
+    // 自分以外でポケモンを所持しているトレーナー
+    let candidates: Vec<usize> = (0..trainers.len())
+        .filter(|&idx| trainers[idx].id != player_id && !trainers[idx].pokemons.is_empty())
+        .collect();
+
+    loop {
+        clear_screen();
+        print_letter_with_delay("たいせんあいてを選んでください：");
+
+        println!("0: やせいのポケモン");
+        for (num, &idx) in candidates.iter().enumerate() {
+            println!("{}: {}", num + 1, trainers[idx].name);
+        }
+        println!("=============================================");
+
+        let mut choice = String::new();
+        std::io::stdin().read_line(&mut choice).unwrap();
+        match choice.trim().parse::<usize>() {
+            Ok(0) => return None,
+            Ok(num) if num <= candidates.len() => return Some(candidates[num - 1]),
+            _ => {
+                println!("もう一度選びなおしてください。");
+                wait(2000);
+            }
+        }
+    }
+}
+
+/// 先頭に出すポケモンを選択
+/// 
+/// 選択したポケモンを手持ちの先頭に並べ替える
 /// 
 /// # 引数
 /// * `trainer` - トレーナー
@@ -53,11 +95,11 @@ pub fn select_pokemon(trainer: &mut Trainer) {
     loop {
         clear_screen();
         println!("選んだトレーナー: {}\n", trainer.name);
-        print_letter_with_delay("ポケモンを選んでください：\n");
+        print_letter_with_delay("先頭に出すポケモンを選んでください：\n");
         print_letter_with_delay("所持ポケモン：");
     
         for (idx, pokemon) in trainer.pokemons.iter().enumerate() {
-            println!("{}: {}", idx + 1, pokemon.name);
+            println!("{}: {}", idx + 1, pokemon.name());
         }
         println!("=============================================");
 
@@ -67,13 +109,172 @@ pub fn select_pokemon(trainer: &mut Trainer) {
             Ok(num) if num > 0 && num <= trainer.pokemons.len() => num,
             _ => {
                 println!("もう一度選びなおしてください。");
-                thread::sleep(Duration::from_millis(2000));
+                wait(2000);
                 continue;
             }
         };
 
-        let selected_pokemon = trainer.pokemons.remove(choice.saturating_sub(1));
-        trainer.set_active_pokemon(selected_pokemon);
+        // セーブデータから続ける場合はひんしのポケモンがいることがある
+        if trainer.pokemons[choice - 1].status.current_hp == 0 {
+            println!("{} はたたかえません。\n別のポケモンを選んでください。", trainer.pokemons[choice - 1].name());
+            wait(2000);
+            continue;
+        }
+
+        let selected_pokemon = trainer.pokemons.remove(choice - 1);
+        trainer.pokemons.insert(0, selected_pokemon);
+        trainer.set_active_pokemon(0);
         return;
     }
-}
\ No newline at end of file
+}
+
+/// 交代するポケモンを選択
+/// 
+/// # 引数
+/// * `trainer` - トレーナー
+/// * `forced`  - ひんしによる交代かどうか（キャンセル不可）
+/// 
+/// # 戻り値
+/// * `Option<usize>` - 交代先の手持ちのインデックス、キャンセルした場合は None
+pub fn select_switch_pokemon(trainer: &Trainer, forced: bool) -> Option<usize> {
+    loop {
+        if forced {
+            print_letter_with_delay("つぎの ポケモンを 選んでください：");
+        } else {
+            print_letter_with_delay("交代する ポケモンを 選んでください：");
+            println!("0: もどる");
+        }
+
+        for (idx, pokemon) in trainer.pokemons.iter().enumerate() {
+            let note = if pokemon.status.current_hp == 0 {
+                "（ひんし）"
+            } else if idx == trainer.active_idx {
+                "（せんとうちゅう）"
+            } else {
+                ""
+            };
+            println!(
+                "{}: {}　HP {}/{}{}",
+                idx + 1, pokemon.name(), pokemon.status.current_hp, pokemon.status.hp.value, note
+            );
+        }
+        println!("=============================================");
+
+        let mut choice = String::new();
+        std::io::stdin().read_line(&mut choice).unwrap();
+        match choice.trim().parse::<usize>() {
+            Ok(0) if !forced => return None,
+            Ok(num) if num > 0 && trainer.can_switch_to(num - 1) => return Some(num - 1),
+            _ => {
+                println!("もう一度選びなおしてください。");
+                wait(1000);
+            }
+        }
+    }
+}
+
+/// 投げるボールを選択
+///
+/// バッグにあるボールをキーの順に表示する
+///
+/// # 引数
+/// * `bag`   - 道具のキーごとの所持数
+/// * `items` - 道具データ
+///
+/// # 戻り値
+/// * `Option<String>` - 投げるボールのキー、ボールがない場合やもどる場合は None
+pub fn select_ball(bag: &BTreeMap<String, u32>, items: &HashMap<String, Item>) -> Option<String> {
+    let balls: Vec<(&String, &Item, u32)> = bag
+        .iter()
+        .filter_map(|(key, &count)| items.get(key).map(|item| (key, item, count)))
+        .filter(|(_, item, count)| matches!(item.kind, ItemKind::Ball(_)) && *count > 0)
+        .collect();
+    if balls.is_empty() {
+        print_letter_with_delay("ボールを　もっていない！");
+        return None;
+    }
+
+    loop {
+        print_letter_with_delay("なげる ボールを 選んでください：");
+        println!("0: もどる");
+        for (idx, (_, item, count)) in balls.iter().enumerate() {
+            println!("{}: {}　x{}", idx + 1, item.name, count);
+        }
+        println!("=============================================");
+
+        let mut choice = String::new();
+        std::io::stdin().read_line(&mut choice).unwrap();
+        match choice.trim().parse::<usize>() {
+            Ok(0) => return None,
+            Ok(num) if num <= balls.len() => return Some(balls[num - 1].0.clone()),
+            _ => {
+                println!("もう一度選びなおしてください。");
+                wait(1000);
+            }
+        }
+    }
+}
+
+/// 新しい技を覚えるために忘れる技を選択
+///
+/// # 引数
+/// * `pokemon` - 技を覚えようとしているポケモン
+/// * `skill`   - 覚えようとしている技
+///
+/// # 戻り値
+/// * `Option<usize>` - 忘れる技のインデックス、覚えるのをあきらめた場合は None
+pub fn select_skill_to_forget(pokemon: &Pokemon, skill: &Skill) -> Option<usize> {
+    print_letter_with_delay(&format!("{}は　あたらしく\n{}を　おぼえたい……", pokemon.name(), skill.name));
+    print_letter_with_delay(&format!("しかし　{}は　わざを　{}つ\nおぼえるので　せいいっぱいだ！", pokemon.name(), pokemon.skills.len()));
+
+    loop {
+        print_letter_with_delay(&format!("{}の　かわりに\nほかの　わざを　わすれさせますか？", skill.name));
+        println!("0: あきらめる");
+        for (idx, learned) in pokemon.skills.iter().enumerate() {
+            println!("{}: {}　PP {}/{}", idx + 1, learned.skill.name, learned.current_pp, learned.max_pp);
+        }
+        println!("=============================================");
+
+        let mut choice = String::new();
+        std::io::stdin().read_line(&mut choice).unwrap();
+        match choice.trim().parse::<usize>() {
+            Ok(0) => return None,
+            Ok(num) if num <= pokemon.skills.len() => return Some(num - 1),
+            _ => {
+                println!("もう一度選びなおしてください。");
+                wait(1000);
+            }
+        }
+    }
+}
+
+/// 道具を使うポケモンを選択
+///
+/// # 引数
+/// * `trainer` - トレーナー
+/// * `item`    - 使う道具の名前
+///
+/// # 戻り値
+/// * `Option<usize>` - 道具を使うポケモンの手持ちのインデックス、やめた場合は None
+pub fn select_item_target(trainer: &Trainer, item: &str) -> Option<usize> {
+    loop {
+        clear_screen();
+        print_letter_with_delay(&format!("{}を　どの　ポケモンに　つかいますか？", item));
+        println!("0: やめる");
+        for (idx, pokemon) in trainer.pokemons.iter().enumerate() {
+            println!("{}: {}　L{}", idx + 1, pokemon.name(), pokemon.level);
+        }
+        println!("=============================================");
+
+        let mut choice = String::new();
+        std::io::stdin().read_line(&mut choice).unwrap();
+        match choice.trim().parse::<usize>() {
+            Ok(0) => return None,
+            Ok(num) if num <= trainer.pokemons.len() => return Some(num - 1),
+            _ => {
+                println!("もう一度選びなおしてください。");
+                wait(1000);
+            }
+        }
+    }
+}
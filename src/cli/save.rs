@@ -0,0 +1,56 @@
+use pokemon_battle_system::models::save::SaveData;
+
+/// セーブデータの内容を表示
+///
+/// 手持ちのポケモンのレベル、経験値、HP、状態異常、技の残りPP、ボックスのポケモン、バッグ、進行状況を表示する
+///
+/// # 引数
+/// * `save` - セーブデータ
+pub fn print_save(save: &SaveData) {
+    println!("トレーナー: {}", save.trainer.name);
+
+    println!("手持ち:");
+    for (idx, pokemon) in save.trainer.pokemons.iter().enumerate() {
+        let ailment = match pokemon.ailment {
+            _ if pokemon.status.current_hp == 0 => "　[ひんし]".to_string(),
+            Some(ailment) => format!("　[{}]", ailment.as_str()),
+            None => String::new(),
+        };
+        println!(
+            "  {}: {}　L{}　HP {}/{}　経験値 {}{}",
+            idx + 1,
+            pokemon.name(),
+            pokemon.level,
+            pokemon.status.current_hp,
+            pokemon.status.hp.value,
+            pokemon.exp,
+            ailment
+        );
+        for skill in &pokemon.skills {
+            println!("       {}　PP {}/{}", skill.skill.name, skill.current_pp, skill.max_pp);
+        }
+    }
+
+    if !save.storage.is_empty() {
+        println!("ボックス:");
+        for (idx, pokemon) in save.storage.iter().enumerate() {
+            println!("  {}: {}　L{}", idx + 1, pokemon.name(), pokemon.level);
+        }
+    }
+
+    println!("バッグ:");
+    if save.bag.is_empty() {
+        println!("  なし");
+    }
+    for (item, count) in &save.bag {
+        println!("  {}　x{}", item, count);
+    }
+
+    let progress = &save.progress;
+    println!("戦績: {} 戦 {} 勝 {} 敗　捕まえた数: {}", progress.battles, progress.wins, progress.losses, progress.caught);
+    if progress.defeated_trainers.is_empty() {
+        println!("倒したトレーナー: なし");
+    } else {
+        println!("倒したトレーナー: {}", progress.defeated_trainers.join(", "));
+    }
+}
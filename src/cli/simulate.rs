@@ -0,0 +1,80 @@
+use pokemon_battle_system::{
+    models::{replay::Replay, trainer::Trainer},
+    services::{
+        battle::{engine::Battle, event::Side},
+        simulation::{run_battle, SimulationStats}
+    }
+};
+
+/// コンピュータ同士でバトルを行い結果を表示
+///
+/// 演出や入力待ちを行わず、両陣営ともトレーナーの AI で行動を選択する
+///
+/// # 引数
+/// * `battle` - バトルの状態
+/// * `replay` - 行動を記録するリプレイ
+pub fn run_simulation(battle: &mut Battle, replay: &mut Replay) {
+    println!("{}（{:?}） vs {}（{:?}）", battle.player.name, battle.player.ai, battle.opponent.name, battle.opponent.ai);
+
+    let result = run_battle(battle, Some(replay));
+
+    let winner = match result.winner {
+        Some(side) => &battle.trainer(side).name,
+        None => "なし",
+    };
+    println!("勝者: {}（{} ターン）", winner, result.turns);
+
+    for trainer in [&battle.player, &battle.opponent] {
+        let remaining = trainer.pokemons.iter().filter(|pokemon| pokemon.status.current_hp > 0).count();
+        println!("{} の残りポケモン: {}/{}", trainer.name, remaining, trainer.pokemons.len());
+    }
+}
+
+/// 複数回のバトルの集計を表示
+///
+/// 勝率、平均ターン数、平均残りHP、ポケモンごとの技の使用回数を表示する
+///
+/// # 引数
+/// * `stats`    - バトルの結果の集計
+/// * `player`   - 自分のトレーナー
+/// * `opponent` - 相手のトレーナー
+pub fn print_statistics(stats: &SimulationStats, player: &Trainer, opponent: &Trainer) {
+    println!("{}（{:?}） vs {}（{:?}）: {} 戦", player.name, player.ai, opponent.name, opponent.ai, stats.battles);
+
+    println!("勝率:");
+    for (side, trainer) in [(Side::Player, player), (Side::Opponent, opponent)] {
+        println!("  {}: {:.1}%（{} 勝）", trainer.name, stats.win_rate(side) * 100.0, stats.side(side).wins);
+    }
+    if stats.draws > 0 {
+        println!("  引き分け: {} 戦", stats.draws);
+    }
+    println!("平均ターン数: {:.1}", stats.average_turns());
+
+    println!("平均残りHP:");
+    for (side, trainer) in [(Side::Player, player), (Side::Opponent, opponent)] {
+        println!(
+            "  {}: {:.1}（{:.1}%）",
+            trainer.name,
+            stats.average_remaining_hp(side),
+            stats.remaining_hp_ratio(side) * 100.0
+        );
+    }
+
+    println!("技の使用回数:");
+    for (side, trainer) in [(Side::Player, player), (Side::Opponent, opponent)] {
+        println!("  {}:", trainer.name);
+
+        // 使用回数の多い順に表示
+        let mut uses: Vec<_> = stats.side(side).skill_uses.iter().collect();
+        uses.sort_by(|a, b| b.1.cmp(a.1));
+        for ((pokemon, skill), count) in uses {
+            println!(
+                "    {} の {}: {} 回（1 戦あたり {:.2} 回）",
+                pokemon,
+                skill,
+                count,
+                *count as f64 / stats.battles.max(1) as f64
+            );
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use std::{collections::HashMap, sync::Arc};
+
+use crate::{file::error::LoadError, models::{game_data::GameData, item::Item, pokemon::{Skill, Species}, trainer::Trainer}};
+
+/// 変換したゲームデータ
+///
+/// 図鑑番号をキーにした種族、キーごとの技、キーの順に並べたトレーナー、キーごとの道具で構成
+pub type BattleData = (HashMap<String, Arc<Species>>, HashMap<String, Arc<Skill>>, Vec<Trainer>, HashMap<String, Item>);
+
+impl GameData {
+    /// バトルで使用する種族、技、トレーナー、道具に変換
+    ///
+    /// エラーの報告順と個体の ID を固定するため、種族とトレーナーはキーの順に変換する
+    /// 技にはキーを設定し、セーブデータに技のキーだけを記録できるようにする
+    ///
+    /// # 戻り値
+    /// * `BattleData` - (図鑑番号をキーとした種族, キーごとの技, キーの順に並べたトレーナー, 道具)
+    pub fn into_battle_data(self) -> Result<BattleData, LoadError> {
+        let GameData { species, mut skills, trainers, items } = self;
+
+        // 技にキーを設定
+        for (id, skill) in skills.iter_mut() {
+            skill.id = id.clone();
+        }
+
+        // 種族データを変換
+        let mut species_json: Vec<_> = species.into_iter().collect();
+        species_json.sort_by(|a, b| a.0.cmp(&b.0));
+        let mut species_data: HashMap<String, Arc<Species>> = HashMap::new();
+        for (id, species) in species_json {
+            // Species を HashMap に追加
+            let species = species.into_species(&id, &skills)?;
+            species_data.insert(id, Arc::new(species));
+        }
+
+        // 進化先がすべて定義されているかを確認（図鑑番号順）
+        let mut ids: Vec<&str> = species_data.keys().map(String::as_str).collect();
+        ids.sort();
+        for id in ids {
+            if let Some(evolution) = species_data[id].evolutions.iter().find(|e| !species_data.contains_key(e.into.as_str())) {
+                return Err(LoadError::UnknownEvolution { pokemon: id.to_string(), into: evolution.into.clone() });
+            }
+        }
+
+        // トレーナーデータを変換（表示順と個体の ID を固定するためキーでソート）
+        let mut trainer_json: Vec<_> = trainers.into_iter().collect();
+        trainer_json.sort_by(|a, b| a.0.cmp(&b.0));
+        let mut next_uid = 1;
+        let trainers = trainer_json
+            .into_iter()
+            .map(|(key, trainer_json)| trainer_json.into_trainer(&key, &mut next_uid, &species_data, &skills))
+            .collect::<Result<_, _>>()?;
+
+        let skill_data = skills.into_iter().map(|(id, skill)| (id, Arc::new(skill))).collect();
+
+        Ok((species_data, skill_data, trainers, items))
+    }
+}
\ No newline at end of file
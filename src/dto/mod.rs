@@ -1,3 +1,5 @@
+pub mod game_data;
 pub mod pokemon;
-pub mod skill;
+pub mod replay;
+pub mod save;
 pub mod trainer;
\ No newline at end of file
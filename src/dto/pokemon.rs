@@ -1,62 +1,282 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, sync::Arc};
 
-use crate::{logic::rand::XorShift128, models::pokemon::{Pokemon, PokemonJson, ElementType, Skill}};
+use crate::{
+    file::error::LoadError,
+    logic::rand::XorShift128,
+    models::pokemon::{
+        ElementType, LearnedSkill, Learnset, LearnsetJson, LevelUpSkill, Nature, Pokemon, PokemonJson, Skill, Species, SpeciesJson,
+        Status, StatusPoints, VolatileStatus
+    },
+    services::battle::learnset::MAX_SKILLS
+};
 
 
-/// 敵ポケモンをランダムに選択
+/// やせいで出現する種族から敵ポケモンをランダムに選択
+/// 
+/// # 引数
+/// * `species` - 図鑑番号をキーにした種族
+/// * `rng`     - 乱数生成器
 /// 
 /// # 戻り値
-/// * `Pokemon` - 初期化したポケモン
-pub fn select_random_enemy_pokemon(pokemon_data: HashMap<&'static str, Pokemon>) -> Pokemon {
-    let keys: Vec<&str> = pokemon_data.keys().cloned().collect();
+/// * `Pokemon` - 出現するレベルで初期化したポケモン
+pub fn select_random_enemy_pokemon(species: &HashMap<String, Arc<Species>>, rng: &mut XorShift128) -> Pokemon {
+    // 同じシード値で同じポケモンを選ぶため図鑑番号順に並べる
+    let mut keys: Vec<&str> = species
+        .iter()
+        .filter(|(_, species)| species.wild_level.is_some())
+        .map(|(id, _)| id.as_str())
+        .collect();
+    keys.sort();
 
     // 乱数生成
-    let rand_idx = XorShift128::random_in_range(0, keys.len() as u64 - 1) as usize;
+    let rand_idx = rng.random_in_range(0, keys.len() as u64 - 1) as usize;
 
-    // ランダムに選ばれたポケモン名でポケモンを取得
-    let selected_name = keys[rand_idx];
-    pokemon_data.get(selected_name).unwrap().clone()
+    // ランダムに選ばれた種族で個体を作成
+    let selected = &species[keys[rand_idx]];
+    Pokemon::from_species(0, selected.clone(), selected.wild_level.unwrap_or(1))
 }
 
-impl PokemonJson {
-    pub fn into_pokemon(self, id: &'static str, skill_json: &HashMap<String, Skill>) -> Pokemon {
-        let types: Vec<ElementType> = self.element
-            .into_iter()
-            .filter_map(|t| match t.as_str() {
-                "Normal" => Some(ElementType::Normal),
-                "Fire" => Some(ElementType::Fire),
-                "Water" => Some(ElementType::Water),
-                "Electric" => Some(ElementType::Electric),
-                "Grass" => Some(ElementType::Grass),
-                "Ice" => Some(ElementType::Ice),
-                "Fighting" => Some(ElementType::Fighting),
-                "Poison" => Some(ElementType::Poison),
-                "Ground" => Some(ElementType::Ground),
-                "Flying" => Some(ElementType::Flying),
-                "Psychic" => Some(ElementType::Psychic),
-                "Bug" => Some(ElementType::Bug),
-                "Rock" => Some(ElementType::Rock),
-                "Ghost" => Some(ElementType::Ghost),
-                "Dragon" => Some(ElementType::Dragon),
-                "Dark" => Some(ElementType::Dark),
-                "Steel" => Some(ElementType::Steel),
-                "Fairy" => Some(ElementType::Fairy),
-                _ => None,
-            })
-            .collect();
-        
-        let skills: Vec<Skill> = self.skills
+/// タイプ名の文字列を ElementType に変換
+///
+/// # 引数
+/// * `name` - JSON に記述したタイプ名（"Fire" など）
+///
+/// # 戻り値
+/// * `Option<ElementType>` - 対応するタイプ、未定義の場合は None
+pub fn parse_element(name: &str) -> Option<ElementType> {
+    match name {
+        "Normal" => Some(ElementType::Normal),
+        "Fire" => Some(ElementType::Fire),
+        "Water" => Some(ElementType::Water),
+        "Electric" => Some(ElementType::Electric),
+        "Grass" => Some(ElementType::Grass),
+        "Ice" => Some(ElementType::Ice),
+        "Fighting" => Some(ElementType::Fighting),
+        "Poison" => Some(ElementType::Poison),
+        "Ground" => Some(ElementType::Ground),
+        "Flying" => Some(ElementType::Flying),
+        "Psychic" => Some(ElementType::Psychic),
+        "Bug" => Some(ElementType::Bug),
+        "Rock" => Some(ElementType::Rock),
+        "Ghost" => Some(ElementType::Ghost),
+        "Dragon" => Some(ElementType::Dragon),
+        "Dark" => Some(ElementType::Dark),
+        "Steel" => Some(ElementType::Steel),
+        "Fairy" => Some(ElementType::Fairy),
+        _ => None,
+    }
+}
+
+impl Pokemon {
+    /// 種族とレベルから個体を作成
+    ///
+    /// 個体値は最大、努力値は 0、性格はがんばりや、HPは満タン
+    /// 技はレベルまでに覚えるレベルアップ技のうち、後から覚えるものを 4 つまで覚える
+    ///
+    /// # 引数
+    /// * `uid`     - 個体の ID（やせいのポケモンは 0）
+    /// * `species` - 種族
+    /// * `level`   - レベル
+    ///
+    /// # 戻り値
+    /// * `Pokemon` - 実数値を計算したポケモン
+    pub fn from_species(uid: u64, species: Arc<Species>, level: u8) -> Self {
+        let ivs = StatusPoints::max_ivs();
+        let evs = StatusPoints::default();
+        let nature = Nature::default();
+        let status = Status::from_base_status(&species.base_status, &ivs, &evs, nature, level);
+        let skills = species.learnset
+            .default_skills(level)
             .into_iter()
-            .filter_map(|skill_name| skill_json.get(&skill_name).cloned())
+            .map(LearnedSkill::from_shared)
             .collect();
 
         Pokemon {
-            id,
+            uid,
+            exp: species.growth_rate.exp_for_level(level),
+            species,
+            nickname: None,
+            level,
+            friendship: Pokemon::base_friendship(),
+            ivs,
+            evs,
+            nature,
+            status,
+            ailment: None,
+            ailment_turns: 0,
+            volatile: VolatileStatus::default(),
+            skills,
+        }
+    }
+}
+
+impl SpeciesJson {
+    /// 同じ種族のポケモンで共有する種族に変換
+    ///
+    /// # 引数
+    /// * `id`         - 図鑑番号
+    /// * `skill_json` - 技データ
+    ///
+    /// # 戻り値
+    /// * `Species` - 種族、未定義のタイプや技がある場合はエラー
+    pub fn into_species(self, id: &str, skill_json: &HashMap<String, Skill>) -> Result<Species, LoadError> {
+        let types: Vec<ElementType> = self.element
+            .into_iter()
+            .map(|t| parse_element(&t).ok_or_else(|| LoadError::UnknownElement { pokemon: id.to_string(), element: t }))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Species {
+            id: id.to_string(),
             name: self.name,
-            level: self.level,
             element: types,
-            status: self.status,
-            skills
+            base_exp: self.base_exp,
+            growth_rate: self.growth_rate,
+            base_status: self.base_status,
+            learnset: self.learnset.into_learnset(id, skill_json)?,
+            evolutions: self.evolutions,
+            wild_level: self.wild_level,
+            catch_rate: self.catch_rate,
+        })
+    }
+}
+
+impl PokemonJson {
+    /// トレーナーの手持ちの個体をバトルで使用するポケモンに変換
+    ///
+    /// 技を指定しない場合は種族とレベルから決まる技を覚える
+    ///
+    /// # 引数
+    /// * `uid`        - 個体の ID
+    /// * `trainer`    - 手持ちにするトレーナーのキー（エラーの報告用）
+    /// * `idx`        - 手持ちでの位置（エラーの報告用）
+    /// * `species`    - 図鑑番号をキーにした種族
+    /// * `skill_json` - 技データ
+    ///
+    /// # 戻り値
+    /// * `Pokemon` - 実数値を計算したポケモン、未定義の種族や技、覚えられない技がある場合はエラー
+    pub fn into_pokemon(
+        self,
+        uid: u64,
+        trainer: &str,
+        idx: usize,
+        species: &HashMap<String, Arc<Species>>,
+        skill_json: &HashMap<String, Skill>,
+    ) -> Result<Pokemon, LoadError> {
+        let Some(species) = species.get(self.species.as_str()) else {
+            return Err(LoadError::UnknownPokemon { trainer: trainer.to_string(), pokemon: self.species });
+        };
+        let mut pokemon = Pokemon::from_species(uid, species.clone(), self.level);
+
+        if let Some(skill_names) = self.skills {
+            pokemon.skills = skill_names
+                .into_iter()
+                .map(|skill_name| {
+                    let Some(skill) = skill_json.get(&skill_name) else {
+                        return Err(LoadError::UnknownSkill { trainer: trainer.to_string(), idx, skill: skill_name });
+                    };
+                    // 覚えている技がレベルまでに覚えられる技かを確認
+                    if !species.learnset.allows(skill, self.level) {
+                        return Err(LoadError::IllegalSkill { trainer: trainer.to_string(), idx, skill: skill_name, level: self.level });
+                    }
+                    Ok(LearnedSkill::new(skill.clone()))
+                })
+                .collect::<Result<_, _>>()?;
+        }
+
+        // 種族値、個体値、努力値、性格、レベルから実数値を計算
+        pokemon.status = Status::from_base_status(&species.base_status, &self.ivs, &self.evs, self.nature, self.level);
+        if let Some(current_hp) = self.current_hp {
+            pokemon.status.current_hp = current_hp;
+        }
+        pokemon.nickname = self.nickname;
+        pokemon.ivs = self.ivs;
+        pokemon.evs = self.evs;
+        pokemon.nature = self.nature;
+
+        Ok(pokemon)
+    }
+}
+
+impl Learnset {
+    /// 技をレベルまでに覚えられるかを判定
+    ///
+    /// レベルアップで覚える技はそのレベル以下、わざマシンとタマゴわざはレベルによらず覚えられる
+    ///
+    /// # 引数
+    /// * `skill` - 技の定義
+    /// * `level` - ポケモンのレベル
+    ///
+    /// # 戻り値
+    /// * `bool` - 覚えられるかどうか
+    pub fn allows(&self, skill: &Skill, level: u8) -> bool {
+        self.level_up.iter().any(|entry| *entry.skill == *skill && entry.level <= level)
+            || self.tm.iter().any(|tm| **tm == *skill)
+            || self.egg.iter().any(|egg| **egg == *skill)
+    }
+
+    /// レベルまでに覚えるレベルアップ技のうち、後から覚える技を 4 つまで取得
+    ///
+    /// # 引数
+    /// * `level` - ポケモンのレベル
+    ///
+    /// # 戻り値
+    /// * `Vec<Arc<Skill>>` - 覚える技（覚える順）
+    pub fn default_skills(&self, level: u8) -> Vec<Arc<Skill>> {
+        let mut skills: Vec<Arc<Skill>> = Vec::new();
+        for entry in self.level_up.iter().filter(|entry| entry.level <= level) {
+            // 同じ技を何度も覚える場合は最後に覚えた位置にする
+            skills.retain(|skill| skill.name != entry.skill.name);
+            skills.push(entry.skill.clone());
         }
+
+        let start = skills.len().saturating_sub(MAX_SKILLS);
+        skills.split_off(start)
+    }
+}
+
+impl LearnsetJson {
+    /// 技をレベルまでに覚えられるかを判定
+    ///
+    /// レベルアップで覚える技はそのレベル以下、わざマシンとタマゴわざはレベルによらず覚えられる
+    ///
+    /// # 引数
+    /// * `skill` - 技のキー
+    /// * `level` - ポケモンのレベル
+    ///
+    /// # 戻り値
+    /// * `bool` - 覚えられるかどうか
+    pub fn allows(&self, skill: &str, level: u8) -> bool {
+        self.level_up.iter().any(|entry| entry.skill == skill && entry.level <= level)
+            || self.tm.iter().any(|tm| tm == skill)
+            || self.egg.iter().any(|egg| egg == skill)
+    }
+
+    /// 技のキーを技の定義に置き換えて Learnset に変換
+    ///
+    /// レベルアップで覚える技はレベルの低い順に並べる
+    ///
+    /// # 引数
+    /// * `id`         - 図鑑番号
+    /// * `skill_json` - 技データ
+    ///
+    /// # 戻り値
+    /// * `Learnset` - 覚えられる技、未定義の技がある場合はエラー
+    pub fn into_learnset(self, id: &str, skill_json: &HashMap<String, Skill>) -> Result<Learnset, LoadError> {
+        let resolve = |skill: String| match skill_json.get(&skill) {
+            Some(definition) => Ok(Arc::new(definition.clone())),
+            None => Err(LoadError::UnknownLearnsetSkill { pokemon: id.to_string(), skill }),
+        };
+
+        let mut level_up: Vec<LevelUpSkill> = self.level_up
+            .into_iter()
+            .map(|entry| Ok(LevelUpSkill { level: entry.level, skill: resolve(entry.skill)? }))
+            .collect::<Result<_, LoadError>>()?;
+        level_up.sort_by_key(|entry| entry.level);
+
+        Ok(Learnset {
+            level_up,
+            tm: self.tm.into_iter().map(resolve).collect::<Result<_, _>>()?,
+            egg: self.egg.into_iter().map(resolve).collect::<Result<_, _>>()?,
+        })
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,39 @@
+use crate::{
+    models::replay::{Replay, REPLAY_VERSION},
+    services::battle::engine::Battle
+};
+
+impl Replay {
+    /// バトル開始時の状態からリプレイを作成
+    ///
+    /// ターンを進める前に作成し、`ReplayActions` で行動を記録する
+    ///
+    /// # 引数
+    /// * `battle`       - 開始時のバトルの状態
+    /// * `seed`         - 乱数のシード値
+    /// * `data_version` - ゲームデータのバージョン
+    pub fn new(battle: &Battle, seed: u64, data_version: Option<u32>) -> Self {
+        Replay {
+            version: REPLAY_VERSION,
+            data_version,
+            seed,
+            rng: battle.rng.clone(),
+            wild: battle.wild,
+            player: battle.player.clone(),
+            opponent: battle.opponent.clone(),
+            exp_gain: battle.exp_gain,
+            turns: Vec::new(),
+        }
+    }
+
+    /// 記録を始めたときのバトルの状態を復元
+    ///
+    /// # 戻り値
+    /// * `Battle` - 開始時のバトルの状態
+    pub fn initial_battle(&self) -> Battle {
+        let mut battle = Battle::new(self.player.clone(), self.opponent.clone(), self.wild, self.rng.clone());
+        battle.exp_gain = self.exp_gain;
+
+        battle
+    }
+}
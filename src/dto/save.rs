@@ -0,0 +1,257 @@
+use std::{collections::HashMap, sync::Arc};
+
+use crate::{
+    file::error::LoadError,
+    models::{
+        pokemon::{LearnedSkill, Pokemon, Skill, Species},
+        save::{Progress, SaveData, SaveDataJson, SavePokemonJson, SaveSkillJson, SaveTrainerJson, SAVE_VERSION, STARTER_ITEMS},
+        trainer::Trainer
+    }
+};
+
+impl SaveData {
+    /// ゲームデータのトレーナーから新しいセーブデータを作成
+    ///
+    /// バッグには `STARTER_ITEMS` の道具を入れておく
+    ///
+    /// # 引数
+    /// * `trainer` - 自分のトレーナー
+    pub fn new(trainer: Trainer) -> Self {
+        SaveData {
+            version: SAVE_VERSION,
+            trainer,
+            storage: Vec::new(),
+            bag: STARTER_ITEMS.iter().map(|&(item, count)| (item.to_string(), count)).collect(),
+            progress: Progress::default(),
+        }
+    }
+
+    /// セーブファイルに書き込む形式に変換
+    ///
+    /// # 戻り値
+    /// * `SaveDataJson` - ポケモンの種族を図鑑番号、技をキーにしたセーブデータ
+    pub fn to_json(&self) -> SaveDataJson {
+        let trainer = &self.trainer;
+
+        SaveDataJson {
+            version: SAVE_VERSION,
+            trainer: SaveTrainerJson {
+                id: trainer.id.clone(),
+                name: trainer.name.clone(),
+                pokemons: trainer.pokemons.iter().map(SavePokemonJson::from_pokemon).collect(),
+                active_idx: trainer.active_idx,
+                ai: trainer.ai,
+                search: trainer.search,
+            },
+            storage: self.storage.iter().map(SavePokemonJson::from_pokemon).collect(),
+            bag: self.bag.clone(),
+            progress: self.progress.clone(),
+        }
+    }
+}
+
+impl SaveDataJson {
+    /// 手持ちとボックスのポケモンの種族と技を現在のゲームデータの種族と技に置き換えてセーブデータに変換
+    ///
+    /// セーブした後に追加された進化先や覚えられる技、変更された技の性能も反映される
+    ///
+    /// # 引数
+    /// * `path`    - セーブファイルのパス（エラーの報告用）
+    /// * `species` - 図鑑番号をキーにした種族
+    /// * `skills`  - キーごとの技
+    ///
+    /// # 戻り値
+    /// * `SaveData` - セーブデータ、ゲームデータにない種族や技のポケモンがいる場合はエラー
+    pub fn into_save(
+        self,
+        path: &str,
+        species: &HashMap<String, Arc<Species>>,
+        skills: &HashMap<String, Arc<Skill>>
+    ) -> Result<SaveData, LoadError> {
+        let SaveDataJson { trainer, storage, bag, progress, .. } = self;
+        let link = |pokemons: Vec<SavePokemonJson>| {
+            pokemons
+                .into_iter()
+                .map(|pokemon| pokemon.into_pokemon(path, species, skills))
+                .collect::<Result<Vec<_>, _>>()
+        };
+
+        Ok(SaveData {
+            version: SAVE_VERSION,
+            trainer: Trainer {
+                id: trainer.id,
+                name: trainer.name,
+                pokemons: link(trainer.pokemons)?,
+                active_idx: trainer.active_idx,
+                ai: trainer.ai,
+                search: trainer.search,
+            },
+            storage: link(storage)?,
+            bag,
+            progress,
+        })
+    }
+}
+
+impl SavePokemonJson {
+    /// ポケモンの種族を図鑑番号、覚えている技をキーにして記録
+    ///
+    /// # 引数
+    /// * `pokemon` - 記録するポケモン
+    pub fn from_pokemon(pokemon: &Pokemon) -> Self {
+        SavePokemonJson {
+            uid: pokemon.uid,
+            species: pokemon.species.id.clone(),
+            nickname: pokemon.nickname.clone(),
+            level: pokemon.level,
+            exp: pokemon.exp,
+            friendship: pokemon.friendship,
+            ivs: pokemon.ivs,
+            evs: pokemon.evs,
+            nature: pokemon.nature,
+            status: pokemon.status.clone(),
+            ailment: pokemon.ailment,
+            ailment_turns: pokemon.ailment_turns,
+            volatile: pokemon.volatile,
+            skills: pokemon.skills.iter().map(SaveSkillJson::from_learned).collect(),
+        }
+    }
+
+    /// 図鑑番号と技のキーをゲームデータの種族と技に置き換えてポケモンに変換
+    ///
+    /// # 引数
+    /// * `path`    - セーブファイルのパス（エラーの報告用）
+    /// * `species` - 図鑑番号をキーにした種族
+    /// * `skills`  - キーごとの技
+    ///
+    /// # 戻り値
+    /// * `Pokemon` - ポケモン、ゲームデータにない種族や技の場合はエラー
+    pub fn into_pokemon(
+        self,
+        path: &str,
+        species: &HashMap<String, Arc<Species>>,
+        skills: &HashMap<String, Arc<Skill>>
+    ) -> Result<Pokemon, LoadError> {
+        let Some(species) = species.get(&self.species) else {
+            return Err(LoadError::UnknownSavedPokemon { path: path.to_string(), pokemon: self.species });
+        };
+        let learned = self.skills
+            .into_iter()
+            .map(|skill| skill.into_learned(path, skills))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Pokemon {
+            uid: self.uid,
+            species: species.clone(),
+            nickname: self.nickname,
+            level: self.level,
+            exp: self.exp,
+            friendship: self.friendship,
+            ivs: self.ivs,
+            evs: self.evs,
+            nature: self.nature,
+            status: self.status,
+            ailment: self.ailment,
+            ailment_turns: self.ailment_turns,
+            volatile: self.volatile,
+            skills: learned,
+        })
+    }
+}
+
+impl SaveSkillJson {
+    /// 覚えている技をキーと PP にして記録
+    ///
+    /// # 引数
+    /// * `learned` - 覚えている技
+    pub fn from_learned(learned: &LearnedSkill) -> Self {
+        SaveSkillJson {
+            skill: learned.skill.id.clone(),
+            current_pp: learned.current_pp,
+            max_pp: learned.max_pp,
+        }
+    }
+
+    /// 技のキーをゲームデータの技に置き換えて覚えている技に変換
+    ///
+    /// # 引数
+    /// * `path`   - セーブファイルのパス（エラーの報告用）
+    /// * `skills` - キーごとの技
+    ///
+    /// # 戻り値
+    /// * `LearnedSkill` - 覚えている技、ゲームデータにない技の場合はエラー
+    pub fn into_learned(self, path: &str, skills: &HashMap<String, Arc<Skill>>) -> Result<LearnedSkill, LoadError> {
+        let Some(skill) = skills.get(&self.skill) else {
+            return Err(LoadError::UnknownSavedSkill { path: path.to_string(), skill: self.skill });
+        };
+
+        Ok(LearnedSkill {
+            skill: skill.clone(),
+            current_pp: self.current_pp,
+            max_pp: self.max_pp,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file::json::fixture::battle_data;
+
+    /// 手持ちとボックスのポケモンは図鑑番号と技のキーで記録し、読み込むとゲームデータの種族と技を共有する
+    #[test]
+    fn round_trip_links_species() {
+        let (species, skills, trainers, _) = battle_data();
+        let mut save = SaveData::new(trainers[0].clone());
+
+        // 進化先を記録していない古い種族のポケモンをボックスに預ける
+        let mut stored = save.trainer.pokemons[0].clone();
+        let mut outdated = (*stored.species).clone();
+        outdated.evolutions.clear();
+        stored.species = Arc::new(outdated);
+        save.storage.push(stored);
+
+        let json = serde_json::to_string(&save.to_json()).expect("セーブデータを書き込めません");
+        assert!(!json.contains("base_status"));
+        assert!(!json.contains("base_atk"));
+        let json: SaveDataJson = serde_json::from_str(&json).expect("セーブデータを読み込めません");
+        let loaded = json.into_save("save.json", &species, &skills).expect("セーブデータを変換できません");
+
+        assert_eq!(loaded.trainer.pokemons.len(), save.trainer.pokemons.len());
+        assert_eq!(loaded.storage.len(), 1);
+        for pokemon in loaded.trainer.pokemons.iter().chain(&loaded.storage) {
+            assert!(Arc::ptr_eq(&pokemon.species, &species[&pokemon.species.id]));
+            for learned in &pokemon.skills {
+                assert!(Arc::ptr_eq(&learned.skill, &skills[&learned.skill.id]));
+            }
+        }
+        let pp = |pokemons: &[Pokemon]| pokemons[0].skills.iter().map(|learned| learned.current_pp).collect::<Vec<_>>();
+        assert_eq!(pp(&loaded.trainer.pokemons), pp(&save.trainer.pokemons));
+    }
+
+    /// ゲームデータにない種族のポケモンがいるセーブデータはエラー
+    #[test]
+    fn unknown_species_is_rejected() {
+        let (species, skills, trainers, _) = battle_data();
+        let mut json = SaveData::new(trainers[0].clone()).to_json();
+        json.storage.push(SavePokemonJson { species: "9999".to_string(), ..json.trainer.pokemons[0].clone() });
+
+        assert!(matches!(
+            json.into_save("save.json", &species, &skills),
+            Err(LoadError::UnknownSavedPokemon { pokemon, .. }) if pokemon == "9999"
+        ));
+    }
+
+    /// ゲームデータにない技を覚えているポケモンがいるセーブデータはエラー
+    #[test]
+    fn unknown_skill_is_rejected() {
+        let (species, skills, trainers, _) = battle_data();
+        let mut json = SaveData::new(trainers[0].clone()).to_json();
+        json.trainer.pokemons[0].skills[0].skill = "unknown_skill".to_string();
+
+        assert!(matches!(
+            json.into_save("save.json", &species, &skills),
+            Err(LoadError::UnknownSavedSkill { skill, .. }) if skill == "unknown_skill"
+        ));
+    }
+}
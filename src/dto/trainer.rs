@@ -1,18 +1,57 @@
-use crate::models::{pokemon::Pokemon, trainer::{Trainer, TrainerJson}};
+use std::{collections::HashMap, sync::Arc};
+
+use crate::{
+    file::error::LoadError,
+    models::{pokemon::{Pokemon, Skill, Species}, trainer::{Trainer, TrainerJson}}
+};
 
 impl TrainerJson {
-    pub fn into_trainer(self, pokemon_data: &std::collections::HashMap<&'static str, Pokemon>) -> Trainer {
-        let pokemons = self.pokemons.unwrap_or_default()
-            .iter()
-            .filter_map(|id| pokemon_data.get(id.as_str()).cloned())
-            .collect();
+    /// バトルで使用するトレーナーに変換
+    ///
+    /// 手持ちのポケモンには `next_uid` から順に個体の ID を割り当てる
+    ///
+    /// # 引数
+    /// * `key`        - トレーナーデータのキー
+    /// * `next_uid`   - 次に割り当てる個体の ID（割り当てた分だけ進める）
+    /// * `species`    - 図鑑番号をキーにした種族
+    /// * `skill_json` - 技データ
+    ///
+    /// # 戻り値
+    /// * `Trainer` - トレーナー、手持ちに未定義のポケモンがいる場合や手持ちが空の場合はエラー
+    pub fn into_trainer(
+        self,
+        key: &str,
+        next_uid: &mut u64,
+        species: &HashMap<String, Arc<Species>>,
+        skill_json: &HashMap<String, Skill>,
+    ) -> Result<Trainer, LoadError> {
+        let pokemons: Vec<Pokemon> = self.pokemons
+            .unwrap_or_default()
+            .into_iter()
+            .enumerate()
+            .map(|(idx, pokemon)| {
+                let uid = *next_uid;
+                *next_uid += 1;
+                pokemon.into_pokemon(uid, key, idx, species, skill_json)
+            })
+            .collect::<Result<_, _>>()?;
+        if pokemons.is_empty() {
+            return Err(LoadError::EmptyParty { trainer: key.to_string() });
+        }
 
-        let active_pokemon = self.active_pokemon.and_then(|id| pokemon_data.get(id.as_str()).cloned());
+        // 先頭に出すポケモンが指定されていない場合は手持ちの先頭
+        let active_idx = self.active_pokemon.unwrap_or(0);
+        if active_idx >= pokemons.len() {
+            return Err(LoadError::UnknownActivePokemon { trainer: key.to_string(), idx: active_idx });
+        }
 
-        Trainer {
-            name: Box::leak(self.name.into_boxed_str()),
+        Ok(Trainer {
+            id: key.to_string(),
+            name: self.name,
             pokemons,
-            active_pokemon,
-        }
+            active_idx,
+            ai: self.ai,
+            search: self.search,
+        })
     }
 }
\ No newline at end of file
@@ -0,0 +1,771 @@
+use std::{collections::HashMap, fmt, fs, path::Path};
+
+use crate::models::{
+    game_data::GameData,
+    item::{Ball, Item, ItemKind},
+    pokemon::{
+        EffectKind, ElementType, Evolution, EvolutionCondition, GrowthRate, LearnsetJson, LevelUpSkillJson, Nature, PokemonJson, Screen, SecondaryEffect, Skill, SkillEffect, SkillType, SpeciesJson,
+        StatusAilment, StatusEffect, StatusPoints, StatusType, Target
+    },
+    trainer::{AiLevel, SearchConfig, TrainerJson}
+};
+use super::error::LoadError;
+
+/// ファイル先頭の識別子
+const MAGIC: &[u8; 4] = b"PKBD";
+
+/// フォーマットのバージョン（レイアウトを変更したら上げる）
+const VERSION: u16 = 10;
+
+/// ヘッダーの長さ（識別子 4 + バージョン 2 + ペイロード長 4 + チェックサム 4）
+const HEADER_LEN: usize = 14;
+
+const ELEMENT_TYPES: [ElementType; 18] = [
+    ElementType::Normal, ElementType::Fire, ElementType::Water, ElementType::Electric,
+    ElementType::Grass, ElementType::Ice, ElementType::Fighting, ElementType::Poison,
+    ElementType::Ground, ElementType::Flying, ElementType::Psychic, ElementType::Bug,
+    ElementType::Rock, ElementType::Ghost, ElementType::Dragon, ElementType::Dark,
+    ElementType::Steel, ElementType::Fairy,
+];
+
+const NATURES: [Nature; 25] = [
+    Nature::Hardy, Nature::Lonely, Nature::Brave, Nature::Adamant, Nature::Naughty,
+    Nature::Bold, Nature::Docile, Nature::Relaxed, Nature::Impish, Nature::Lax,
+    Nature::Timid, Nature::Hasty, Nature::Serious, Nature::Jolly, Nature::Naive,
+    Nature::Modest, Nature::Mild, Nature::Quiet, Nature::Bashful, Nature::Rash,
+    Nature::Calm, Nature::Gentle, Nature::Sassy, Nature::Careful, Nature::Quirky,
+];
+
+const SKILL_TYPES: [SkillType; 5] = [
+    SkillType::PhysicalAttack, SkillType::SpecialAttack, SkillType::ChangeStatus,
+    SkillType::OneHitKO, SkillType::Struggle,
+];
+
+const TARGETS: [Target; 3] = [Target::Self_, Target::Ally, Target::Enemy];
+
+const STATUS_TYPES: [StatusType; 7] = [
+    StatusType::Hp, StatusType::Atk, StatusType::Def, StatusType::SpAtk,
+    StatusType::SpDef, StatusType::Spd, StatusType::Field,
+];
+
+const STATUS_AILMENTS: [StatusAilment; 6] = [
+    StatusAilment::Burn, StatusAilment::Poison, StatusAilment::BadPoison,
+    StatusAilment::Paralysis, StatusAilment::Sleep, StatusAilment::Freeze,
+];
+
+const SCREENS: [Screen; 2] = [Screen::Reflect, Screen::LightScreen];
+
+const GROWTH_RATES: [GrowthRate; 6] = [
+    GrowthRate::Erratic, GrowthRate::Fast, GrowthRate::MediumFast,
+    GrowthRate::MediumSlow, GrowthRate::Slow, GrowthRate::Fluctuating,
+];
+
+const AI_LEVELS: [AiLevel; 4] = [AiLevel::Random, AiLevel::Greedy, AiLevel::Heuristic, AiLevel::Expectiminimax];
+
+/// バイナリデータの読み込みエラー
+#[derive(Debug, Clone, PartialEq)]
+pub enum BinError {
+    /// 識別子が一致しない
+    BadMagic,
+    /// 対応していないバージョン
+    UnsupportedVersion(u16),
+    /// チェックサムが一致しない
+    ChecksumMismatch { expected: u32, actual: u32 },
+    /// データが途中で終わっている
+    UnexpectedEof,
+    /// ペイロードの後ろに余分なデータがある
+    TrailingBytes(usize),
+    /// 文字列が UTF-8 ではない
+    InvalidUtf8,
+    /// 列挙型のタグが範囲外
+    InvalidTag { kind: &'static str, tag: u8 },
+    /// 文字列や要素数、ペイロードの長さが書き込める上限を超えている
+    TooLong(usize),
+    /// 書き込む列挙子がタグの一覧に登録されていない
+    UnregisteredVariant(&'static str),
+}
+
+impl fmt::Display for BinError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BinError::BadMagic => write!(f, "バイナリデータの識別子が一致しません"),
+            BinError::UnsupportedVersion(version) => write!(f, "対応していないバージョンです: {}", version),
+            BinError::ChecksumMismatch { expected, actual } => {
+                write!(f, "チェックサムが一致しません（期待値: {:08x}、実際: {:08x}）", expected, actual)
+            }
+            BinError::UnexpectedEof => write!(f, "バイナリデータが途中で終わっています"),
+            BinError::TrailingBytes(len) => write!(f, "バイナリデータの末尾に {} バイトの余分なデータがあります", len),
+            BinError::InvalidUtf8 => write!(f, "文字列が UTF-8 ではありません"),
+            BinError::InvalidTag { kind, tag } => write!(f, "{} のタグが不正です: {}", kind, tag),
+            BinError::TooLong(len) => write!(f, "長さ {} がバイナリ形式の上限を超えています", len),
+            BinError::UnregisteredVariant(kind) => write!(f, "{} にタグが登録されていない列挙子があります", kind),
+        }
+    }
+}
+
+impl std::error::Error for BinError {}
+
+/// ゲームデータをバイナリファイルに書き込む関数
+///
+/// バイナリ形式に変換できない場合はファイルを作成しない
+///
+/// # 引数
+/// * `file_path` - 書き込み先のパス（親ディレクトリがなければ作成）
+/// * `data`      - ゲームデータ
+pub fn write_bin(file_path: &str, data: &GameData) -> Result<(), LoadError> {
+    let bytes = encode(data).map_err(|source| LoadError::Bin { path: file_path.to_string(), source })?;
+
+    let io_error = |source| LoadError::Io { path: file_path.to_string(), source };
+    if let Some(parent) = Path::new(file_path).parent() {
+        fs::create_dir_all(parent).map_err(io_error)?;
+    }
+    fs::write(file_path, bytes).map_err(io_error)?;
+
+    Ok(())
+}
+
+/// バイナリファイルからゲームデータを読み込む関数
+///
+/// # 引数
+/// * `file_path` - 読み込むファイルのパス
+///
+/// # 戻り値
+/// * `GameData` - ゲームデータ
+pub fn read_bin(file_path: &str) -> Result<GameData, LoadError> {
+    let bytes = fs::read(file_path).map_err(|source| LoadError::Io { path: file_path.to_string(), source })?;
+
+    decode(&bytes).map_err(|source| LoadError::Bin { path: file_path.to_string(), source })
+}
+
+/// ゲームデータをバイナリ形式に変換
+///
+/// 出力を安定させるため、各データはキーの順に並べる
+///
+/// # 引数
+/// * `data` - ゲームデータ
+///
+/// # 戻り値
+/// * `Vec<u8>` - ヘッダーを含むバイナリデータ
+pub fn encode(data: &GameData) -> Result<Vec<u8>, BinError> {
+    let payload = encode_payload(data)?;
+    let payload_len = u32::try_from(payload.len()).map_err(|_| BinError::TooLong(payload.len()))?;
+
+    let mut bytes = Vec::with_capacity(HEADER_LEN + payload.len());
+    bytes.extend_from_slice(MAGIC);
+    bytes.extend_from_slice(&VERSION.to_le_bytes());
+    bytes.extend_from_slice(&payload_len.to_le_bytes());
+    bytes.extend_from_slice(&crc32(&payload).to_le_bytes());
+    bytes.extend_from_slice(&payload);
+
+    Ok(bytes)
+}
+
+/// ゲームデータのバージョンを計算
+///
+/// バイナリ形式のペイロードのチェックサムで、JSON とバイナリのどちらから読み込んでも同じ値になる
+/// リプレイを記録したときとゲームデータが変わっていないかの確認に使用する
+///
+/// # 引数
+/// * `data` - ゲームデータ
+///
+/// # 戻り値
+/// * `u32` - ゲームデータのバージョン
+pub fn data_version(data: &GameData) -> Result<u32, BinError> {
+    Ok(crc32(&encode_payload(data)?))
+}
+
+/// ゲームデータをヘッダーを除いたバイナリ形式に変換
+fn encode_payload(data: &GameData) -> Result<Vec<u8>, BinError> {
+    let mut payload = Writer::default();
+    payload.map(&data.skills, Writer::skill)?;
+    payload.map(&data.species, Writer::species)?;
+    payload.map(&data.trainers, Writer::trainer)?;
+    payload.map(&data.items, Writer::item)?;
+
+    Ok(payload.buf)
+}
+
+/// バイナリ形式からゲームデータを復元
+///
+/// # 引数
+/// * `bytes` - ヘッダーを含むバイナリデータ
+///
+/// # 戻り値
+/// * `GameData` - ゲームデータ
+pub fn decode(bytes: &[u8]) -> Result<GameData, BinError> {
+    let mut header = Reader::new(bytes);
+    if header.bytes(MAGIC.len())? != MAGIC {
+        return Err(BinError::BadMagic);
+    }
+    let version = header.u16()?;
+    if version != VERSION {
+        return Err(BinError::UnsupportedVersion(version));
+    }
+    let payload_len = header.u32()? as usize;
+    let expected = header.u32()?;
+    let payload = header.bytes(payload_len)?;
+    if header.remaining() > 0 {
+        return Err(BinError::TrailingBytes(header.remaining()));
+    }
+
+    let actual = crc32(payload);
+    if actual != expected {
+        return Err(BinError::ChecksumMismatch { expected, actual });
+    }
+
+    let mut reader = Reader::new(payload);
+    let data = GameData {
+        skills: reader.map(Reader::skill)?,
+        species: reader.map(Reader::species)?,
+        trainers: reader.map(Reader::trainer)?,
+        items: reader.map(Reader::item)?,
+    };
+    if reader.remaining() > 0 {
+        return Err(BinError::TrailingBytes(reader.remaining()));
+    }
+
+    Ok(data)
+}
+
+/// CRC-32（IEEE 802.3）を計算
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for &byte in bytes {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+
+    !crc
+}
+
+/// リトルエンディアンでバイナリを書き込む構造体
+#[derive(Default)]
+struct Writer {
+    buf: Vec<u8>,
+}
+
+impl Writer {
+    fn u8(&mut self, value: u8) -> Result<(), BinError> {
+        self.buf.push(value);
+        Ok(())
+    }
+
+    fn i8(&mut self, value: i8) -> Result<(), BinError> {
+        self.u8(value as u8)
+    }
+
+    fn u16(&mut self, value: u16) -> Result<(), BinError> {
+        self.buf.extend_from_slice(&value.to_le_bytes());
+        Ok(())
+    }
+
+    fn u32(&mut self, value: u32) -> Result<(), BinError> {
+        self.buf.extend_from_slice(&value.to_le_bytes());
+        Ok(())
+    }
+
+    fn bool(&mut self, value: bool) -> Result<(), BinError> {
+        self.u8(value as u8)
+    }
+
+    fn len(&mut self, len: usize) -> Result<(), BinError> {
+        let len = u16::try_from(len).map_err(|_| BinError::TooLong(len))?;
+        self.u16(len)
+    }
+
+    fn str(&mut self, value: &str) -> Result<(), BinError> {
+        self.len(value.len())?;
+        self.buf.extend_from_slice(value.as_bytes());
+        Ok(())
+    }
+
+    fn tag<T: PartialEq>(&mut self, kind: &'static str, all: &[T], value: &T) -> Result<(), BinError> {
+        let tag = all.iter().position(|v| v == value).ok_or(BinError::UnregisteredVariant(kind))?;
+        self.u8(tag as u8)
+    }
+
+    fn option<T>(&mut self, value: &Option<T>, write: impl FnOnce(&mut Self, &T) -> Result<(), BinError>) -> Result<(), BinError> {
+        match value {
+            Some(value) => {
+                self.u8(1)?;
+                write(self, value)
+            }
+            None => self.u8(0),
+        }
+    }
+
+    fn strings(&mut self, values: &[String]) -> Result<(), BinError> {
+        self.len(values.len())?;
+        for value in values {
+            self.str(value)?;
+        }
+        Ok(())
+    }
+
+    fn map<T>(&mut self, map: &HashMap<String, T>, write: impl Fn(&mut Self, &T) -> Result<(), BinError>) -> Result<(), BinError> {
+        let mut entries: Vec<(&String, &T)> = map.iter().collect();
+        entries.sort_by(|a, b| a.0.cmp(b.0));
+
+        self.len(entries.len())?;
+        for (key, value) in entries {
+            self.str(key)?;
+            write(self, value)?;
+        }
+        Ok(())
+    }
+
+    fn status_points(&mut self, points: &StatusPoints) -> Result<(), BinError> {
+        for value in [points.hp, points.atk, points.def, points.sp_atk, points.sp_def, points.spd] {
+            self.u8(value)?;
+        }
+        Ok(())
+    }
+
+    fn status_effect(&mut self, effect: &StatusEffect) -> Result<(), BinError> {
+        self.tag("StatusType", &STATUS_TYPES, &effect.target)?;
+        self.i8(effect.effect_value)
+    }
+
+    fn skill(&mut self, skill: &Skill) -> Result<(), BinError> {
+        self.str(&skill.name)?;
+        self.tag("ElementType", &ELEMENT_TYPES, &skill.element)?;
+        self.u8(skill.base_atk)?;
+        self.u8(skill.accuracy)?;
+        self.u8(skill.pp)?;
+        self.i8(skill.priority)?;
+        self.u8(skill.critical_rank)?;
+        self.bool(skill.charge)?;
+        self.bool(skill.breaks_screens)?;
+        self.option(&skill.skill_effect, |w, effect: &SkillEffect| {
+            w.option(&effect.status_effect, Writer::status_effect)?;
+            w.option(&effect.ailment, |w, ailment| w.tag("StatusAilment", &STATUS_AILMENTS, ailment))?;
+            w.u8(effect.critical_stage)?;
+            w.option(&effect.screen, |w, screen| w.tag("Screen", &SCREENS, screen))?;
+            w.tag("Target", &TARGETS, &effect.target)
+        })?;
+        self.len(skill.secondary_effects.len())?;
+        for effect in &skill.secondary_effects {
+            self.u8(effect.chance)?;
+            self.tag("Target", &TARGETS, &effect.target)?;
+            match &effect.effect {
+                EffectKind::StatusChange(status_effect) => {
+                    self.u8(0)?;
+                    self.status_effect(status_effect)?;
+                }
+                EffectKind::Ailment(ailment) => {
+                    self.u8(1)?;
+                    self.tag("StatusAilment", &STATUS_AILMENTS, ailment)?;
+                }
+                EffectKind::Flinch => self.u8(2)?,
+                EffectKind::Confusion => self.u8(3)?,
+            }
+        }
+        self.tag("SkillType", &SKILL_TYPES, &skill.class)
+    }
+
+    fn species(&mut self, species: &SpeciesJson) -> Result<(), BinError> {
+        self.str(&species.id)?;
+        self.str(&species.name)?;
+        self.strings(&species.element)?;
+        self.u16(species.base_exp)?;
+        self.tag("GrowthRate", &GROWTH_RATES, &species.growth_rate)?;
+        self.status_points(&species.base_status)?;
+        self.learnset(&species.learnset)?;
+        self.evolutions(&species.evolutions)?;
+        self.option(&species.wild_level, |w, level| w.u8(*level))?;
+        self.u8(species.catch_rate)
+    }
+
+    fn pokemon(&mut self, pokemon: &PokemonJson) -> Result<(), BinError> {
+        self.str(&pokemon.species)?;
+        self.option(&pokemon.nickname, |w, nickname| w.str(nickname))?;
+        self.u8(pokemon.level)?;
+        self.status_points(&pokemon.ivs)?;
+        self.status_points(&pokemon.evs)?;
+        self.tag("Nature", &NATURES, &pokemon.nature)?;
+        self.option(&pokemon.current_hp, |w, hp| w.u16(*hp))?;
+        self.option(&pokemon.skills, |w, skills| w.strings(skills))
+    }
+
+    fn learnset(&mut self, learnset: &LearnsetJson) -> Result<(), BinError> {
+        self.len(learnset.level_up.len())?;
+        for entry in &learnset.level_up {
+            self.u8(entry.level)?;
+            self.str(&entry.skill)?;
+        }
+        self.strings(&learnset.tm)?;
+        self.strings(&learnset.egg)
+    }
+
+    fn evolutions(&mut self, evolutions: &[Evolution]) -> Result<(), BinError> {
+        self.len(evolutions.len())?;
+        for evolution in evolutions {
+            self.str(&evolution.into)?;
+            match &evolution.condition {
+                EvolutionCondition::Level(level) => {
+                    self.u8(0)?;
+                    self.u8(*level)?;
+                }
+                EvolutionCondition::Item(item) => {
+                    self.u8(1)?;
+                    self.str(item)?;
+                }
+                EvolutionCondition::Friendship(friendship) => {
+                    self.u8(2)?;
+                    self.u8(*friendship)?;
+                }
+                EvolutionCondition::Trade => self.u8(3)?,
+            }
+        }
+        Ok(())
+    }
+
+    fn item(&mut self, item: &Item) -> Result<(), BinError> {
+        self.str(&item.name)?;
+        match &item.kind {
+            ItemKind::Ball(ball) => {
+                self.u8(0)?;
+                self.u8(ball.modifier)?;
+                self.bool(ball.guaranteed)
+            }
+            ItemKind::Evolution => self.u8(1),
+        }
+    }
+
+    fn trainer(&mut self, trainer: &TrainerJson) -> Result<(), BinError> {
+        self.str(&trainer.name)?;
+        self.option(&trainer.pokemons, |w, pokemons| {
+            w.len(pokemons.len())?;
+            for pokemon in pokemons {
+                w.pokemon(pokemon)?;
+            }
+            Ok(())
+        })?;
+        self.option(&trainer.active_pokemon, |w, idx| w.len(*idx))?;
+        self.tag("AiLevel", &AI_LEVELS, &trainer.ai)?;
+        self.u8(trainer.search.depth)?;
+        self.u32(trainer.search.max_nodes)?;
+        self.u32(trainer.search.time_limit_ms)
+    }
+}
+
+/// リトルエンディアンでバイナリを読み込む構造体
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Reader { bytes, pos: 0 }
+    }
+
+    fn remaining(&self) -> usize {
+        self.bytes.len() - self.pos
+    }
+
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8], BinError> {
+        if self.remaining() < len {
+            return Err(BinError::UnexpectedEof);
+        }
+        let bytes = &self.bytes[self.pos..self.pos + len];
+        self.pos += len;
+
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8, BinError> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    fn i8(&mut self) -> Result<i8, BinError> {
+        Ok(self.u8()? as i8)
+    }
+
+    fn u16(&mut self) -> Result<u16, BinError> {
+        let bytes = self.bytes(2)?;
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn u32(&mut self) -> Result<u32, BinError> {
+        let bytes = self.bytes(4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn bool(&mut self) -> Result<bool, BinError> {
+        match self.u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            tag => Err(BinError::InvalidTag { kind: "bool", tag }),
+        }
+    }
+
+    fn str(&mut self) -> Result<String, BinError> {
+        let len = self.u16()? as usize;
+        let bytes = self.bytes(len)?;
+
+        String::from_utf8(bytes.to_vec()).map_err(|_| BinError::InvalidUtf8)
+    }
+
+    fn tag<T: Copy>(&mut self, kind: &'static str, all: &[T]) -> Result<T, BinError> {
+        let tag = self.u8()?;
+        all.get(tag as usize).copied().ok_or(BinError::InvalidTag { kind, tag })
+    }
+
+    fn option<T>(&mut self, read: impl FnOnce(&mut Self) -> Result<T, BinError>) -> Result<Option<T>, BinError> {
+        match self.u8()? {
+            0 => Ok(None),
+            1 => Ok(Some(read(self)?)),
+            tag => Err(BinError::InvalidTag { kind: "Option", tag }),
+        }
+    }
+
+    fn strings(&mut self) -> Result<Vec<String>, BinError> {
+        let len = self.u16()? as usize;
+        (0..len).map(|_| self.str()).collect()
+    }
+
+    fn map<T>(&mut self, read: impl Fn(&mut Self) -> Result<T, BinError>) -> Result<HashMap<String, T>, BinError> {
+        let len = self.u16()? as usize;
+        let mut map = HashMap::with_capacity(len);
+        for _ in 0..len {
+            let key = self.str()?;
+            map.insert(key, read(self)?);
+        }
+
+        Ok(map)
+    }
+
+    fn status_points(&mut self) -> Result<StatusPoints, BinError> {
+        Ok(StatusPoints {
+            hp: self.u8()?,
+            atk: self.u8()?,
+            def: self.u8()?,
+            sp_atk: self.u8()?,
+            sp_def: self.u8()?,
+            spd: self.u8()?,
+        })
+    }
+
+    fn status_effect(&mut self) -> Result<StatusEffect, BinError> {
+        Ok(StatusEffect {
+            target: self.tag("StatusType", &STATUS_TYPES)?,
+            effect_value: self.i8()?,
+        })
+    }
+
+    fn skill(&mut self) -> Result<Skill, BinError> {
+        let name = self.str()?;
+        let element = self.tag("ElementType", &ELEMENT_TYPES)?;
+        let base_atk = self.u8()?;
+        let accuracy = self.u8()?;
+        let pp = self.u8()?;
+        let priority = self.i8()?;
+        let critical_rank = self.u8()?;
+        let charge = self.bool()?;
+        let breaks_screens = self.bool()?;
+        let skill_effect = self.option(|r| {
+            Ok(SkillEffect {
+                status_effect: r.option(Reader::status_effect)?,
+                ailment: r.option(|r| r.tag("StatusAilment", &STATUS_AILMENTS))?,
+                critical_stage: r.u8()?,
+                screen: r.option(|r| r.tag("Screen", &SCREENS))?,
+                target: r.tag("Target", &TARGETS)?,
+            })
+        })?;
+        let secondary_len = self.u16()? as usize;
+        let mut secondary_effects = Vec::with_capacity(secondary_len);
+        for _ in 0..secondary_len {
+            let chance = self.u8()?;
+            let target = self.tag("Target", &TARGETS)?;
+            let effect = match self.u8()? {
+                0 => EffectKind::StatusChange(self.status_effect()?),
+                1 => EffectKind::Ailment(self.tag("StatusAilment", &STATUS_AILMENTS)?),
+                2 => EffectKind::Flinch,
+                3 => EffectKind::Confusion,
+                tag => return Err(BinError::InvalidTag { kind: "EffectKind", tag }),
+            };
+            secondary_effects.push(SecondaryEffect { chance, target, effect });
+        }
+        let class = self.tag("SkillType", &SKILL_TYPES)?;
+
+        Ok(Skill {
+            // 技のキーはマップのキーとして記録しているため、バトルで使用するデータに変換するときに設定する
+            id: String::new(),
+            name,
+            element,
+            base_atk,
+            accuracy,
+            pp,
+            priority,
+            critical_rank,
+            charge,
+            breaks_screens,
+            skill_effect,
+            secondary_effects,
+            class,
+        })
+    }
+
+    fn species(&mut self) -> Result<SpeciesJson, BinError> {
+        Ok(SpeciesJson {
+            id: self.str()?,
+            name: self.str()?,
+            element: self.strings()?,
+            base_exp: self.u16()?,
+            growth_rate: self.tag("GrowthRate", &GROWTH_RATES)?,
+            base_status: self.status_points()?,
+            learnset: self.learnset()?,
+            evolutions: self.evolutions()?,
+            wild_level: self.option(Reader::u8)?,
+            catch_rate: self.u8()?,
+        })
+    }
+
+    fn pokemon(&mut self) -> Result<PokemonJson, BinError> {
+        Ok(PokemonJson {
+            species: self.str()?,
+            nickname: self.option(Reader::str)?,
+            level: self.u8()?,
+            ivs: self.status_points()?,
+            evs: self.status_points()?,
+            nature: self.tag("Nature", &NATURES)?,
+            current_hp: self.option(Reader::u16)?,
+            skills: self.option(Reader::strings)?,
+        })
+    }
+
+    fn learnset(&mut self) -> Result<LearnsetJson, BinError> {
+        let level_up_len = self.u16()? as usize;
+        let mut level_up = Vec::with_capacity(level_up_len);
+        for _ in 0..level_up_len {
+            level_up.push(LevelUpSkillJson { level: self.u8()?, skill: self.str()? });
+        }
+
+        Ok(LearnsetJson { level_up, tm: self.strings()?, egg: self.strings()? })
+    }
+
+    fn evolutions(&mut self) -> Result<Vec<Evolution>, BinError> {
+        let len = self.u16()? as usize;
+        let mut evolutions = Vec::with_capacity(len);
+        for _ in 0..len {
+            let into = self.str()?;
+            let condition = match self.u8()? {
+                0 => EvolutionCondition::Level(self.u8()?),
+                1 => EvolutionCondition::Item(self.str()?),
+                2 => EvolutionCondition::Friendship(self.u8()?),
+                3 => EvolutionCondition::Trade,
+                tag => return Err(BinError::InvalidTag { kind: "EvolutionCondition", tag }),
+            };
+            evolutions.push(Evolution { into, condition });
+        }
+
+        Ok(evolutions)
+    }
+
+    fn item(&mut self) -> Result<Item, BinError> {
+        let name = self.str()?;
+        let kind = match self.u8()? {
+            0 => ItemKind::Ball(Ball { modifier: self.u8()?, guaranteed: self.bool()? }),
+            1 => ItemKind::Evolution,
+            tag => return Err(BinError::InvalidTag { kind: "ItemKind", tag }),
+        };
+
+        Ok(Item { name, kind })
+    }
+
+    fn trainer(&mut self) -> Result<TrainerJson, BinError> {
+        Ok(TrainerJson {
+            name: self.str()?,
+            pokemons: self.option(|r| {
+                let len = r.u16()? as usize;
+                (0..len).map(|_| r.pokemon()).collect()
+            })?,
+            active_pokemon: self.option(|r| Ok(r.u16()? as usize))?,
+            ai: self.tag("AiLevel", &AI_LEVELS)?,
+            search: SearchConfig {
+                depth: self.u8()?,
+                max_nodes: self.u32()?,
+                time_limit_ms: self.u32()?,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file::json::fixture::game_data;
+
+    /// 書き込んだバイナリを読み込むと元のデータに戻る
+    #[test]
+    fn round_trip_restores_game_data() {
+        let data = game_data();
+        let bytes = encode(&data).unwrap();
+
+        assert_eq!(decode(&bytes).unwrap(), data);
+        assert_eq!(encode(&decode(&bytes).unwrap()).unwrap(), bytes);
+    }
+
+    /// 空のゲームデータも書き込んで読み込める
+    #[test]
+    fn round_trip_restores_empty_game_data() {
+        let data = GameData::default();
+        assert_eq!(decode(&encode(&data).unwrap()).unwrap(), data);
+    }
+
+    /// ペイロードやチェックサムが壊れている場合は読み込まない
+    #[test]
+    fn corrupted_checksum_is_rejected() {
+        let bytes = encode(&game_data()).unwrap();
+
+        let mut corrupted_payload = bytes.clone();
+        corrupted_payload[HEADER_LEN + 3] ^= 0xFF;
+        assert!(matches!(decode(&corrupted_payload), Err(BinError::ChecksumMismatch { .. })));
+
+        let mut corrupted_checksum = bytes.clone();
+        corrupted_checksum[HEADER_LEN - 1] ^= 0x01;
+        assert!(matches!(decode(&corrupted_checksum), Err(BinError::ChecksumMismatch { .. })));
+    }
+
+    /// 識別子やバージョンが異なる場合、データが途中で終わっている場合は読み込まない
+    #[test]
+    fn bad_header_is_rejected() {
+        let bytes = encode(&game_data()).unwrap();
+
+        let mut bad_version = bytes.clone();
+        bad_version[4..6].copy_from_slice(&(VERSION + 1).to_le_bytes());
+        assert_eq!(decode(&bad_version), Err(BinError::UnsupportedVersion(VERSION + 1)));
+
+        let mut bad_magic = bytes.clone();
+        bad_magic[0] = b'X';
+        assert_eq!(decode(&bad_magic), Err(BinError::BadMagic));
+
+        assert_eq!(decode(&bytes[..bytes.len() - 1]), Err(BinError::UnexpectedEof));
+
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert_eq!(decode(&trailing), Err(BinError::TrailingBytes(1)));
+    }
+
+    /// 書き込める長さを超える文字列はパニックせずにエラーになる
+    #[test]
+    fn too_long_string_is_an_error() {
+        let mut data = game_data();
+        let skill = data.skills.values_mut().next().unwrap();
+        skill.name = "あ".repeat(30_000);
+
+        assert_eq!(encode(&data), Err(BinError::TooLong(90_000)));
+        assert!(data_version(&data).is_err());
+    }
+
+    /// 登録されていない列挙子はパニックせずにエラーになる
+    #[test]
+    fn unregistered_variant_is_an_error() {
+        let mut writer = Writer::default();
+        assert_eq!(writer.tag("Target", &TARGETS[..2], &Target::Enemy), Err(BinError::UnregisteredVariant("Target")));
+    }
+}
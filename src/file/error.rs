@@ -0,0 +1,96 @@
+use std::{fmt, io};
+
+use super::bin::BinError;
+
+/// ゲームデータの読み込みエラー
+///
+/// どのファイルのどのキーで失敗したかを保持する
+#[derive(Debug)]
+pub enum LoadError {
+    /// ファイルの読み書きに失敗した
+    Io { path: String, source: io::Error },
+    /// JSON の構文や値が不正（`key` が None の場合はファイル全体）
+    Parse { path: String, key: Option<String>, source: serde_json::Error },
+    /// バイナリデータが不正
+    Bin { path: String, source: BinError },
+    /// ポケモンに未定義のタイプが指定されている
+    UnknownElement { pokemon: String, element: String },
+    /// トレーナーの手持ちのポケモンに未定義の技が指定されている
+    UnknownSkill { trainer: String, idx: usize, skill: String },
+    /// ポケモンの覚えられる技に未定義の技が指定されている
+    UnknownLearnsetSkill { pokemon: String, skill: String },
+    /// トレーナーの手持ちのポケモンが覚えている技をレベルまでに覚えられない
+    IllegalSkill { trainer: String, idx: usize, skill: String, level: u8 },
+    /// ポケモンの進化先に未定義のポケモンが指定されている
+    UnknownEvolution { pokemon: String, into: String },
+    /// トレーナーの手持ちに未定義のポケモンが指定されている
+    UnknownPokemon { trainer: String, pokemon: String },
+    /// トレーナーの先頭のポケモンの位置が手持ちの範囲外
+    UnknownActivePokemon { trainer: String, idx: usize },
+    /// トレーナーの手持ちが空
+    EmptyParty { trainer: String },
+    /// 対応していないバージョンのリプレイ
+    UnsupportedReplayVersion { path: String, version: u16 },
+    /// 対応していないバージョンのセーブデータ
+    UnsupportedSaveVersion { path: String, version: u16 },
+    /// セーブデータのポケモンの種族がゲームデータにない
+    UnknownSavedPokemon { path: String, pokemon: String },
+    /// セーブデータのポケモンが覚えている技がゲームデータにない
+    UnknownSavedSkill { path: String, skill: String },
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadError::Io { path, source } => write!(f, "{}: ファイルを読み書きできませんでした: {}", path, source),
+            LoadError::Parse { path, key: Some(key), source } => write!(f, "{}: {} の値が不正です: {}", path, key, source),
+            LoadError::Parse { path, key: None, source } => write!(f, "{}: JSON が不正です: {}", path, source),
+            LoadError::Bin { path, source } => write!(f, "{}: {}", path, source),
+            LoadError::UnknownElement { pokemon, element } => {
+                write!(f, "pokemon_data.json: ポケモン {} の element に未定義のタイプ {} が指定されています", pokemon, element)
+            }
+            LoadError::UnknownSkill { trainer, idx, skill } => {
+                write!(f, "trainer_data.json: トレーナー {} の pokemons[{}] の skills に未定義の技 {} が指定されています", trainer, idx, skill)
+            }
+            LoadError::UnknownLearnsetSkill { pokemon, skill } => {
+                write!(f, "pokemon_data.json: ポケモン {} の learnset に未定義の技 {} が指定されています", pokemon, skill)
+            }
+            LoadError::IllegalSkill { trainer, idx, skill, level } => {
+                write!(f, "trainer_data.json: トレーナー {} の pokemons[{}] は技 {} をレベル {} までに覚えられません（learnset にありません）", trainer, idx, skill, level)
+            }
+            LoadError::UnknownEvolution { pokemon, into } => {
+                write!(f, "pokemon_data.json: ポケモン {} の evolutions に未定義のポケモン {} が指定されています", pokemon, into)
+            }
+            LoadError::UnknownPokemon { trainer, pokemon } => {
+                write!(f, "trainer_data.json: トレーナー {} の pokemons に未定義のポケモン {} が指定されています", trainer, pokemon)
+            }
+            LoadError::UnknownActivePokemon { trainer, idx } => {
+                write!(f, "trainer_data.json: トレーナー {} の active_pokemon {} が pokemons の範囲外です", trainer, idx)
+            }
+            LoadError::EmptyParty { trainer } => write!(f, "trainer_data.json: トレーナー {} の pokemons にポケモンがいません", trainer),
+            LoadError::UnsupportedReplayVersion { path, version } => {
+                write!(f, "{}: 対応していないリプレイのバージョンです: {}", path, version)
+            }
+            LoadError::UnsupportedSaveVersion { path, version } => {
+                write!(f, "{}: 対応していないセーブデータのバージョンです: {}", path, version)
+            }
+            LoadError::UnknownSavedPokemon { path, pokemon } => {
+                write!(f, "{}: セーブデータのポケモンの種族 {} がゲームデータにありません", path, pokemon)
+            }
+            LoadError::UnknownSavedSkill { path, skill } => {
+                write!(f, "{}: セーブデータのポケモンが覚えている技 {} がゲームデータにありません", path, skill)
+            }
+        }
+    }
+}
+
+impl std::error::Error for LoadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            LoadError::Io { source, .. } => Some(source),
+            LoadError::Parse { source, .. } => Some(source),
+            LoadError::Bin { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
@@ -1,18 +1,126 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, fmt, fs::File, io::BufReader};
 
-use serde::Deserialize;
+use serde::{de::{MapAccess, Visitor}, Deserialize, Deserializer};
+
+use crate::models::{game_data::GameData, item::Item, pokemon::{Skill, SpeciesJson}, trainer::TrainerJson};
+use super::error::LoadError;
 
 /// JSONファイルを読み込む関数
 /// 取得したJSON全文を返す
-/// 
+///
 /// 型キャストして使用すること
-/// read_json::<TrainerJson>("./json/trainer_data.json")
-pub fn read_json<T>(file_path: &str) -> Result<HashMap<String, T>, Box<dyn std::error::Error>>
+/// `read_json::<TrainerJson>("./json/trainer_data.json")`
+///
+/// 値が不正な場合はキーの順に最初に見つかったエラーを返す
+pub fn read_json<T>(file_path: &str) -> Result<HashMap<String, T>, LoadError>
 where
     T: for<'de> Deserialize<'de>
 {
-    let file = std::fs::File::open(file_path).expect("ファイルが開けませんでした");
-    let json_data: HashMap<String, T> = serde_json::from_reader(file)?;
+    // 重複したキーは後の値を優先する
+    let values: HashMap<String, serde_json::Value> = read_json_entries(file_path)?.into_iter().collect();
+
+    // キーごとに変換し、どのキーの値が不正かを報告する
+    let mut values: Vec<(String, serde_json::Value)> = values.into_iter().collect();
+    values.sort_by(|a, b| a.0.cmp(&b.0));
+
+    values
+        .into_iter()
+        .map(|(key, value)| match serde_json::from_value::<T>(value) {
+            Ok(value) => Ok((key, value)),
+            Err(source) => Err(LoadError::Parse { path: file_path.to_string(), key: Some(key), source }),
+        })
+        .collect()
+}
+
+/// JSONファイルのトップレベルのキーと値を記述順に読み込む関数
+///
+/// 重複したキーもそのまま返す
+///
+/// # 引数
+/// * `file_path` - 読み込むファイルのパス
+///
+/// # 戻り値
+/// * `Vec<(String, serde_json::Value)>` - キーと値の組
+pub fn read_json_entries(file_path: &str) -> Result<Vec<(String, serde_json::Value)>, LoadError> {
+    let file = File::open(file_path).map_err(|source| LoadError::Io { path: file_path.to_string(), source })?;
+    let entries: JsonEntries = serde_json::from_reader(BufReader::new(file))
+        .map_err(|source| LoadError::Parse { path: file_path.to_string(), key: None, source })?;
+
+    Ok(entries.0)
+}
+
+/// 記述順と重複を保持した JSON オブジェクト
+struct JsonEntries(Vec<(String, serde_json::Value)>);
+
+impl<'de> Deserialize<'de> for JsonEntries {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>
+    {
+        struct EntriesVisitor;
+
+        impl<'de> Visitor<'de> for EntriesVisitor {
+            type Value = JsonEntries;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a JSON object")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>
+            {
+                let mut entries = Vec::new();
+                while let Some(entry) = map.next_entry()? {
+                    entries.push(entry);
+                }
+
+                Ok(JsonEntries(entries))
+            }
+        }
+
+        deserializer.deserialize_map(EntriesVisitor)
+    }
+}
+
+/// ディレクトリ内の JSON ファイルからゲームデータ一式を読み込む関数
+///
+/// # 引数
+/// * `dir` - trainer_data.json、pokemon_data.json、skill_data.json、item_data.json を含むディレクトリ
+///
+/// # 戻り値
+/// * `GameData` - ゲームデータ
+pub fn read_game_data(dir: &str) -> Result<GameData, LoadError> {
+    Ok(GameData {
+        trainers: read_json::<TrainerJson>(&format!("{}/trainer_data.json", dir))?,
+        species: read_json::<SpeciesJson>(&format!("{}/pokemon_data.json", dir))?,
+        skills: read_json::<Skill>(&format!("{}/skill_data.json", dir))?,
+        items: read_json::<Item>(&format!("{}/item_data.json", dir))?,
+    })
+}
+
+/// テストで使用するリポジトリのゲームデータ
+#[cfg(test)]
+pub mod fixture {
+    use crate::{dto::game_data::BattleData, models::{game_data::GameData, trainer::Trainer}};
+    use super::read_game_data;
+
+    /// リポジトリの JSON データを読み込む
+    pub fn game_data() -> GameData {
+        read_game_data(concat!(env!("CARGO_MANIFEST_DIR"), "/json")).expect("ゲームデータを読み込めません")
+    }
+
+    /// リポジトリの JSON データを種族、技、トレーナー、道具に変換
+    pub fn battle_data() -> BattleData {
+        game_data().into_battle_data().expect("ゲームデータを変換できません")
+    }
 
-    Ok(json_data)
-}
\ No newline at end of file
+    /// リポジトリの JSON データからキーでトレーナーを取得
+    ///
+    /// # 引数
+    /// * `id` - トレーナーのキー
+    pub fn trainer(id: &str) -> Trainer {
+        let (_, _, trainers, _) = battle_data();
+        trainers.into_iter().find(|trainer| trainer.id == id).expect("トレーナーが見つかりません")
+    }
+}
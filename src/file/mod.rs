@@ -1 +1,5 @@
-pub mod json;
\ No newline at end of file
+pub mod bin;
+pub mod error;
+pub mod json;
+pub mod replay;
+pub mod save;
\ No newline at end of file
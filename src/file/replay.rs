@@ -0,0 +1,50 @@
+use std::{fs, path::Path};
+
+use serde::Deserialize;
+
+use crate::models::replay::{Replay, REPLAY_VERSION};
+use super::error::LoadError;
+
+/// バージョンだけを先に読み込むための構造体
+#[derive(Deserialize)]
+struct ReplayHeader {
+    version: u16,
+}
+
+/// リプレイを JSON ファイルに書き込む関数
+///
+/// # 引数
+/// * `file_path` - 書き込み先のパス（親ディレクトリがなければ作成）
+/// * `replay`    - リプレイ
+pub fn write_replay(file_path: &str, replay: &Replay) -> Result<(), LoadError> {
+    let io_error = |source| LoadError::Io { path: file_path.to_string(), source };
+
+    if let Some(parent) = Path::new(file_path).parent() {
+        fs::create_dir_all(parent).map_err(io_error)?;
+    }
+    let json = serde_json::to_string_pretty(replay)
+        .map_err(|source| LoadError::Parse { path: file_path.to_string(), key: None, source })?;
+    fs::write(file_path, json).map_err(io_error)?;
+
+    Ok(())
+}
+
+/// JSON ファイルからリプレイを読み込む関数
+///
+/// # 引数
+/// * `file_path` - 読み込むファイルのパス
+///
+/// # 戻り値
+/// * `Replay` - リプレイ
+pub fn read_replay(file_path: &str) -> Result<Replay, LoadError> {
+    let json = fs::read_to_string(file_path).map_err(|source| LoadError::Io { path: file_path.to_string(), source })?;
+    let parse_error = |source| LoadError::Parse { path: file_path.to_string(), key: None, source };
+
+    // 対応していないバージョンはレイアウトが異なる場合があるため、先にバージョンを確認する
+    let header: ReplayHeader = serde_json::from_str(&json).map_err(parse_error)?;
+    if header.version != REPLAY_VERSION {
+        return Err(LoadError::UnsupportedReplayVersion { path: file_path.to_string(), version: header.version });
+    }
+
+    serde_json::from_str(&json).map_err(parse_error)
+}
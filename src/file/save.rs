@@ -0,0 +1,61 @@
+use std::{collections::HashMap, fs, path::Path, sync::Arc};
+
+use serde::Deserialize;
+
+use crate::models::{pokemon::{Skill, Species}, save::{SaveData, SaveDataJson, SAVE_VERSION}};
+use super::error::LoadError;
+
+/// バージョンだけを先に読み込むための構造体
+#[derive(Deserialize)]
+struct SaveHeader {
+    version: u16,
+}
+
+/// セーブデータを JSON ファイルに書き込む関数
+///
+/// ポケモンの種族は図鑑番号、覚えている技はキーだけを書き込む
+///
+/// # 引数
+/// * `file_path` - 書き込み先のパス（親ディレクトリがなければ作成）
+/// * `save`      - セーブデータ
+pub fn write_save(file_path: &str, save: &SaveData) -> Result<(), LoadError> {
+    let io_error = |source| LoadError::Io { path: file_path.to_string(), source };
+
+    if let Some(parent) = Path::new(file_path).parent() {
+        fs::create_dir_all(parent).map_err(io_error)?;
+    }
+    let json = serde_json::to_string_pretty(&save.to_json())
+        .map_err(|source| LoadError::Parse { path: file_path.to_string(), key: None, source })?;
+    fs::write(file_path, json).map_err(io_error)?;
+
+    Ok(())
+}
+
+/// JSON ファイルからセーブデータを読み込む関数
+///
+/// 先にバージョンを確認し、対応していないバージョンの場合は読み込まない
+/// ポケモンの種族と覚えている技は現在のゲームデータの種族と技に置き換える
+///
+/// # 引数
+/// * `file_path` - 読み込むファイルのパス
+/// * `species`   - 図鑑番号をキーにした種族
+/// * `skills`    - キーごとの技
+///
+/// # 戻り値
+/// * `SaveData` - セーブデータ
+pub fn read_save(
+    file_path: &str,
+    species: &HashMap<String, Arc<Species>>,
+    skills: &HashMap<String, Arc<Skill>>
+) -> Result<SaveData, LoadError> {
+    let json = fs::read_to_string(file_path).map_err(|source| LoadError::Io { path: file_path.to_string(), source })?;
+    let parse_error = |source| LoadError::Parse { path: file_path.to_string(), key: None, source };
+
+    let header: SaveHeader = serde_json::from_str(&json).map_err(parse_error)?;
+    if header.version != SAVE_VERSION {
+        return Err(LoadError::UnsupportedSaveVersion { path: file_path.to_string(), version: header.version });
+    }
+    let save: SaveDataJson = serde_json::from_str(&json).map_err(parse_error)?;
+
+    save.into_save(file_path, species, skills)
+}
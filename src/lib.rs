@@ -0,0 +1,32 @@
+//! ポケモンのバトルシステム
+//!
+//! ゲームデータの読み込み、ポケモンとトレーナーのモデル、表示に依存しないバトルエンジンを提供する
+//! CLI はこのクレートの上に構築した薄いバイナリで、ボットや解析ツールも同じ API で作成できる
+//!
+//! 1. `file::json::read_game_data` または `file::bin::read_bin` で `GameData` を読み込む
+//! 2. `GameData::into_battle_data` でポケモンとトレーナーに変換する
+//! 3. `Battle::new` でバトルを作成し、`Battle::submit_actions` に両陣営の行動を渡してターンを進める
+//! 4. 返ってきた `BattleEvent` を表示や解析に使用する
+
+/// JSON 構造体からモデルへの変換
+pub mod dto;
+/// ゲームデータの読み書き
+pub mod file;
+/// 乱数生成などの汎用ロジック
+pub mod logic;
+/// ポケモン、技、トレーナーなどのデータ構造
+pub mod models;
+/// バトルエンジン、コンピュータの AI、対戦の統計、データ検証
+pub mod services;
+
+pub use file::error::LoadError;
+pub use logic::rand::XorShift128;
+pub use models::{game_data::GameData, pokemon::Pokemon, trainer::{AiLevel, BattleAction, Trainer}};
+pub use services::{
+    ai::strategy::{decide_action, decide_replacement, interactive_strategy_for, strategy_for, AiStrategy},
+    battle::{
+        engine::{Battle, TurnActions},
+        event::{BattleEvent, CantMoveReason, Side}
+    },
+    simulation::{run_battle, run_simulations, BattleResult, SimulationStats}
+};
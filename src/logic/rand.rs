@@ -1,24 +1,138 @@
+use serde::{Deserialize, Serialize};
+
+/// バトルエンジンが使用する乱数の取得元
+///
+/// 通常は `XorShift128` を使用し、探索 AI は起こりうる結果を列挙する実装を使用する
+pub trait RandomSource {
+    /// `min` 以上 `max` 以下の整数を生成
+    fn random_in_range(&mut self, min: u64, max: u64) -> u64;
+
+    /// 指定した確率で true を返す
+    fn random_bool(&mut self, probability: f64) -> bool;
+
+    /// `numerator / denominator` の確率で true を返す
+    fn random_ratio(&mut self, numerator: u64, denominator: u64) -> bool;
+}
+
+/// xorshift128+ による擬似乱数生成器
+///
+/// 同じシード値からは同じ乱数列を生成するため、バトルを再現できる
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct XorShift128 {
     state: [u64; 2],
 }
 
 impl XorShift128 {
     pub fn new(seed1: u64, seed2: u64) -> Self {
-        XorShift128 {
-            state: [seed1, seed2],
-        }
+        // 状態がすべて 0 の場合は 0 しか生成しないため避ける
+        let state = if seed1 == 0 && seed2 == 0 {
+            [0x9E37_79B9_7F4A_7C15, 1]
+        } else {
+            [seed1, seed2]
+        };
+
+        XorShift128 { state }
+    }
+
+    /// 1 つのシード値から乱数生成器を作成
+    ///
+    /// splitmix64 でシード値を 2 つの内部状態に展開する
+    ///
+    /// # 引数
+    /// * `seed` - シード値
+    pub fn from_seed(seed: u64) -> Self {
+        let mut x = seed;
+        let mut splitmix64 = || {
+            x = x.wrapping_add(0x9E37_79B9_7F4A_7C15);
+            let mut z = x;
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+            z ^ (z >> 31)
+        };
+
+        XorShift128::new(splitmix64(), splitmix64())
+    }
+
+    /// この乱数生成器を進めずに、別の乱数列を生成する乱数生成器を作成
+    ///
+    /// 同じ状態からは同じ乱数生成器を作成するため、元の乱数生成器と同様に再現できる
+    ///
+    /// # 戻り値
+    /// * `XorShift128` - 元の乱数列とは独立した乱数生成器
+    pub fn fork(&self) -> XorShift128 {
+        XorShift128::from_seed(self.state[0] ^ self.state[1].rotate_left(32) ^ 0xD1B5_4A32_D192_ED03)
     }
 
-    pub fn random_in_range(min: u64, max: u64) -> u64 {
-        // 乱数生成
-        let seed1 = 12345;
-        let seed2 = std::time::SystemTime::now()
+    /// 現在時刻からシード値を生成
+    ///
+    /// # 戻り値
+    /// * `u64` - シード値
+    pub fn entropy_seed() -> u64 {
+        let now = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
-        let mut rng = XorShift128::new(seed1, seed2);
+            .unwrap();
+
+        now.as_secs().wrapping_mul(1_000_000_000) ^ now.subsec_nanos() as u64 ^ ((std::process::id() as u64) << 32)
+    }
+
+    /// `min` 以上 `max` 以下の整数を一様に生成
+    ///
+    /// 剰余による偏りが出ないよう、範囲の倍数に収まらない値は棄却する
+    ///
+    /// # 引数
+    /// * `min` - 最小値
+    /// * `max` - 最大値
+    ///
+    /// # 戻り値
+    /// * `u64` - 生成した乱数
+    pub fn random_in_range(&mut self, min: u64, max: u64) -> u64 {
+        assert!(min <= max, "random_in_range: min({}) > max({})", min, max);
+
+        let range = (max - min).wrapping_add(1);
+        // 0..=u64::MAX の場合はそのまま返す
+        if range == 0 {
+            return self.next();
+        }
+
+        let limit = u64::MAX - u64::MAX % range;
+        loop {
+            let value = self.next();
+            if value < limit {
+                return value % range + min;
+            }
+        }
+    }
+
+    /// 0.0 以上 1.0 未満の浮動小数点数を生成
+    ///
+    /// # 戻り値
+    /// * `f64` - 生成した乱数
+    pub fn random_f64(&mut self) -> f64 {
+        // 上位 53 ビットを仮数部として使用
+        (self.next() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// 指定した確率で true を返す
+    ///
+    /// # 引数
+    /// * `probability` - true になる確率（0.0 〜 1.0）
+    ///
+    /// # 戻り値
+    /// * `bool` - 判定結果
+    pub fn random_bool(&mut self, probability: f64) -> bool {
+        self.random_f64() < probability
+    }
 
-        rng.next() % (max - min + 1) + min
+    /// `numerator / denominator` の確率で true を返す
+    ///
+    /// # 引数
+    /// * `numerator`   - 分子
+    /// * `denominator` - 分母（1 以上）
+    ///
+    /// # 戻り値
+    /// * `bool` - 判定結果
+    pub fn random_ratio(&mut self, numerator: u64, denominator: u64) -> bool {
+        self.random_in_range(0, denominator - 1) < numerator
     }
 
     fn next(&mut self) -> u64 {
@@ -29,4 +143,69 @@ impl XorShift128 {
         self.state[1] = s0 ^ s1 ^ (s0 >> 17) ^ (s1 >> 26);
         self.state[1].wrapping_add(s1)
     }
-}
\ No newline at end of file
+}
+
+impl RandomSource for XorShift128 {
+    fn random_in_range(&mut self, min: u64, max: u64) -> u64 {
+        XorShift128::random_in_range(self, min, max)
+    }
+
+    fn random_bool(&mut self, probability: f64) -> bool {
+        XorShift128::random_bool(self, probability)
+    }
+
+    fn random_ratio(&mut self, numerator: u64, denominator: u64) -> bool {
+        XorShift128::random_ratio(self, numerator, denominator)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 同じシード値からは同じ乱数列が生成される
+    #[test]
+    fn same_seed_gives_same_sequence() {
+        let mut a = XorShift128::from_seed(42);
+        let mut b = XorShift128::from_seed(42);
+        for _ in 0..1000 {
+            assert_eq!(a.random_in_range(0, 1_000_000), b.random_in_range(0, 1_000_000));
+        }
+    }
+
+    /// 狭い範囲では範囲内のすべての値が生成され、範囲外の値は生成されない
+    #[test]
+    fn random_in_range_stays_in_small_ranges() {
+        let mut rng = XorShift128::from_seed(1);
+        for _ in 0..1000 {
+            assert_eq!(rng.random_in_range(7, 7), 7);
+        }
+
+        let mut seen = [false; 3];
+        for _ in 0..1000 {
+            let value = rng.random_in_range(3, 5);
+            assert!((3..=5).contains(&value), "{} が範囲外です", value);
+            seen[(value - 3) as usize] = true;
+        }
+        assert!(seen.iter().all(|&seen| seen));
+    }
+
+    /// 広い範囲や上限が u64::MAX の範囲でも範囲内の値だけが生成される
+    #[test]
+    fn random_in_range_stays_in_large_ranges() {
+        let mut rng = XorShift128::from_seed(2);
+        for (min, max) in [(0, u64::MAX), (1 << 63, u64::MAX), (u64::MAX - 1, u64::MAX), (1, u64::MAX - 1), (0, (1 << 63) + 1)] {
+            for _ in 0..1000 {
+                let value = rng.random_in_range(min, max);
+                assert!(min <= value && value <= max, "{} が {}..={} の範囲外です", value, min, max);
+            }
+        }
+    }
+
+    /// すべて 0 のシード値でも 0 以外の値が生成される
+    #[test]
+    fn zero_seed_is_not_stuck() {
+        let mut rng = XorShift128::new(0, 0);
+        assert!((0..10).any(|_| rng.random_in_range(0, u64::MAX) != 0));
+    }
+}
@@ -1,86 +1,514 @@
 use dotenvy::dotenv;
-use test::hard_coded::test;
-use std::{collections::HashMap, env};
+use test::hard_coded::load_hard_coded_data;
+use std::{collections::HashMap, env, fmt, path::Path, sync::Arc};
 
-use cli::{battle::start_battle, prompt::{select_pokemon, select_trainer}};
-use dto::pokemon::select_random_enemy_pokemon;
-use file::json::read_json;
-use models::{pokemon::{Pokemon, PokemonJson, Skill}, trainer::{Trainer, TrainerJson}};
+use cli::{
+    args::{parse_args, Args, ArgsError, Command, DataFormat, USAGE},
+    battle::{play_replay, start_battle},
+    evolution::{evolve_party, evolve_pokemon},
+    print::set_text_speed,
+    prompt::{select_item_target, select_opponent, select_pokemon, select_trainer},
+    save::print_save,
+    simulate::{print_statistics, run_simulation}
+};
+use pokemon_battle_system::{
+    dto::pokemon::select_random_enemy_pokemon,
+    file::{
+        bin::{data_version, read_bin, write_bin},
+        error::LoadError,
+        json::read_game_data,
+        replay::{read_replay, write_replay},
+        save::{read_save, write_save}
+    },
+    logic::rand::XorShift128,
+    models::{item::{Item, ItemKind}, pokemon::{Skill, Species}, replay::Replay, save::SaveData, trainer::Trainer},
+    services::{
+        battle::{engine::Battle, trainer::{CaughtPlace, TrainerActions}},
+        evolution::{EvolutionActions, EvolutionTrigger},
+        save::SaveActions,
+        simulation::run_simulations,
+        validate::validate_game_data
+    }
+};
 
 mod cli;
-mod dto;
-mod file;
-mod logic;
-mod models;
-mod services;
 mod test;
 
-/// データはJSONから取得
-fn init() {
-    let trainer_json = match read_json::<TrainerJson>("./json/trainer_data.json") {
-        Ok(trainer) => trainer,
-        Err(err) => {
-            eprintln!("{}", err);
-            return;
+/// やせいのポケモンと戦う場合に `--opponent` に指定する値
+const WILD: &str = "wild";
+
+/// 実行時のエラー
+#[derive(Debug)]
+enum RunError {
+    /// コマンドライン引数が不正
+    Args(ArgsError),
+    /// ゲームデータの読み込みに失敗した
+    Load(LoadError),
+    /// 作成しようとしたセーブファイルが既にある
+    SaveExists(String),
+    /// 使おうとした道具をバッグに持っていない
+    NoItem(String),
+}
+
+impl fmt::Display for RunError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RunError::Args(err) => write!(f, "{}", err),
+            RunError::Load(err) => write!(f, "{}", err),
+            RunError::SaveExists(path) => write!(f, "{} には既にセーブデータがあります（上書きしないよう別のパスを指定してください）", path),
+            RunError::NoItem(item) => write!(f, "バッグに {} がありません", item),
+        }
+    }
+}
+
+impl From<ArgsError> for RunError {
+    fn from(err: ArgsError) -> Self {
+        RunError::Args(err)
+    }
+}
+
+impl From<LoadError> for RunError {
+    fn from(err: LoadError) -> Self {
+        RunError::Load(err)
+    }
+}
+
+/// 読み込んだゲームデータ
+///
+/// 図鑑番号をキーにした種族、キーごとの技、キーの順に並べたトレーナー、キーごとの道具、ゲームデータのバージョン（ハードコードしたデータの場合は None）で構成
+type BattleData = (HashMap<String, Arc<Species>>, HashMap<String, Arc<Skill>>, Vec<Trainer>, HashMap<String, Item>, Option<u32>);
+
+/// 指定した形式でゲームデータを読み込む
+///
+/// # 引数
+/// * `args` - コマンドライン引数
+///
+/// # 戻り値
+/// * `BattleData` - 読み込んだゲームデータ
+fn load(args: &Args) -> Result<BattleData, LoadError> {
+    let (data, path) = match args.format {
+        // JSON からデータを取得
+        DataFormat::Json => (read_game_data(&args.data_dir)?, &args.data_dir),
+        // バイナリファイルからデータを取得
+        DataFormat::Bin => (read_bin(&args.bin_path)?, &args.bin_path),
+        // ハードコードしたデータを使用
+        DataFormat::HardCode => {
+            let (species, skills, trainers, items) = load_hard_coded_data();
+            return Ok((species, skills, trainers, items, None));
         }
     };
 
-    let pokemon_json = match read_json::<PokemonJson>("./json/pokemon_data.json") {
-        Ok(pokemon) => pokemon,
-        Err(err) => {
-            eprintln!("{}", err);
-            return;
+    let version = data_version(&data).map_err(|source| LoadError::Bin { path: path.clone(), source })?;
+    let (species, skills, trainers, items) = data.into_battle_data()?;
+
+    Ok((species, skills, trainers, items, Some(version)))
+}
+
+/// キーでトレーナーを検索
+///
+/// # 引数
+/// * `trainers` - トレーナーのリスト
+/// * `id`       - トレーナーのキー
+///
+/// # 戻り値
+/// * `usize` - トレーナーのインデックス
+fn find_trainer(trainers: &[Trainer], id: &str) -> Result<usize, ArgsError> {
+    trainers.iter().position(|trainer| trainer.id == id).ok_or_else(|| ArgsError::UnknownTrainer {
+        trainer: id.to_string(),
+        candidates: trainers.iter().map(|trainer| trainer.id.to_string()).collect(),
+    })
+}
+
+/// 対戦相手を取得
+///
+/// # 引数
+/// * `opponent_idx` - 対戦相手のインデックス、やせいのポケモンと戦う場合は None
+/// * `trainers`     - トレーナーのリスト
+/// * `species`      - 図鑑番号をキーにした種族（やせいのポケモンの候補）
+/// * `rng`          - 乱数生成器
+///
+/// # 戻り値
+/// * `(Trainer, bool)` - (対戦相手, やせいのポケモンかどうか)
+fn opponent(
+    opponent_idx: Option<usize>,
+    trainers: &[Trainer],
+    species: &HashMap<String, Arc<Species>>,
+    rng: &mut XorShift128
+) -> (Trainer, bool) {
+    match opponent_idx {
+        Some(idx) => (trainers[idx].clone(), false),
+        None => (Trainer::new(WILD, "やせいのポケモン", vec![select_random_enemy_pokemon(species, rng)]), true),
+    }
+}
+
+/// `--opponent` の値から対戦相手のインデックスを取得
+///
+/// # 引数
+/// * `trainers` - トレーナーのリスト
+/// * `id`       - `--opponent` の値
+///
+/// # 戻り値
+/// * `Option<usize>` - 対戦相手のインデックス、やせいのポケモンと戦う場合は None
+fn find_opponent(trainers: &[Trainer], id: &str) -> Result<Option<usize>, ArgsError> {
+    if id == WILD {
+        return Ok(None);
+    }
+
+    find_trainer(trainers, id).map(Some)
+}
+
+/// 読み込んだゲームデータでバトルを開始
+///
+/// `--trainer`、`--opponent` を指定しなかった場合は画面で選択する
+/// `--save-file` を指定した場合はセーブデータの手持ちで戦い、バトル後の手持ちと進行状況を書き込む
+/// やせいのポケモンとのバトルではバッグのボールを投げられ（セーブファイルがない場合は新しいセーブデータの道具）、捕まえたポケモンは手持ちかボックスに加える
+/// バトル後、レベルが上がって進化の条件を満たしたポケモンは進化する（キャンセル可能）
+///
+/// # 引数
+/// * `args` - コマンドライン引数
+/// * `seed` - 乱数のシード値
+fn play(args: &Args, seed: u64) -> Result<(), RunError> {
+    if args.save_file.is_some() && args.trainer.is_some() {
+        return Err(ArgsError::Conflict("--save-file".to_string(), "--trainer".to_string()).into());
+    }
+
+    let mut rng = XorShift128::from_seed(seed);
+    let (species, skills, trainers, items, data_version) = load(args)?;
+
+    // 自分のトレーナーを選択
+    // セーブファイルを指定しない場合は書き込まないセーブデータを作成し、最初のバッグの道具でボールを投げられるようにする
+    let mut save = match &args.save_file {
+        Some(path) => read_save(path, &species, &skills)?,
+        None => match &args.trainer {
+            Some(id) => SaveData::new(trainers[find_trainer(&trainers, id)?].clone()),
+            None => SaveData::new(trainers[select_trainer(&trainers)].clone()),
+        },
+    };
+    let mut player = save.trainer.clone();
+
+    // 対戦相手のトレーナーを選択
+    let opponent_idx = match &args.opponent {
+        Some(id) => find_opponent(&trainers, id)?,
+        None => select_opponent(&trainers, &player.id),
+    };
+
+    // トレーナーが先頭に出すポケモンを選択
+    select_pokemon(&mut player);
+
+    // 対戦相手を取得（トレーナーを選ばなかった場合はやせいのポケモン）
+    let (mut opponent, wild) = opponent(opponent_idx, &trainers, &species, &mut rng);
+    if let Some(ai) = args.opponent_ai {
+        opponent.ai = ai;
+    }
+
+    // バトル開始（相手のポケモンを倒すと経験値をもらう）
+    let levels: Vec<u8> = player.pokemons.iter().map(|pokemon| pokemon.level).collect();
+    let mut battle = Battle::new(player, opponent, wild, rng);
+    battle.exp_gain = true;
+    let mut replay = Replay::new(&battle, seed, data_version);
+    start_battle(&mut battle, &mut replay, &mut save, &items);
+
+    // バトル中にレベルが上がって進化の条件を満たしたポケモンを進化させる
+    evolve_party(&mut battle.player, &levels, &species);
+
+    // 捕まえたポケモンを手持ちに加える（いっぱいの場合はセーブデータのボックスに預ける）
+    if battle.caught {
+        let pokemon = battle.opponent.active_pokemon().clone();
+        let name = pokemon.name().to_string();
+        match battle.player.add_caught(pokemon, &mut save.storage) {
+            CaughtPlace::Party => println!("{} を手持ちに加えました", name),
+            CaughtPlace::Storage => println!("手持ちがいっぱいのため {} をボックスに預けました", name),
         }
+    }
+
+    // 不具合の報告時にバトルを再現できるようシード値を表示
+    println!("シード値: {}", seed);
+    save_replay(args, &replay)?;
+
+    if let Some(path) = &args.save_file {
+        save.record_battle(&battle);
+        write_save(path, &save)?;
+        println!("{} にレポートを書き込みました", path);
+    }
+
+    Ok(())
+}
+
+/// コンピュータ同士でバトルを行う
+///
+/// `--opponent` を指定しなかった場合はやせいのポケモンと戦う
+/// `--battles` に 2 以上を指定した場合は繰り返しバトルを行い、結果の統計を表示する
+///
+/// # 引数
+/// * `args` - コマンドライン引数
+/// * `seed` - 乱数のシード値
+fn simulate(args: &Args, seed: u64) -> Result<(), RunError> {
+    if args.battles > 1 && args.replay_file.is_some() {
+        return Err(ArgsError::Conflict("--battles".to_string(), "--replay-file".to_string()).into());
+    }
+
+    let mut rng = XorShift128::from_seed(seed);
+    let (species, _, trainers, _, data_version) = load(args)?;
+
+    let player_idx = match &args.trainer {
+        Some(id) => find_trainer(&trainers, id)?,
+        None => return Err(ArgsError::MissingValue("--trainer".to_string()).into()),
     };
+    let opponent_idx = find_opponent(&trainers, args.opponent.as_deref().unwrap_or(WILD))?;
 
-    let skill_json = match read_json::<Skill>("./json/skill_data.json") {
-        Ok(skill) => skill,
-        Err(err) => {
-            eprintln!("{}", err);
-            return;
+    let mut player = trainers[player_idx].clone();
+    let (mut opponent, wild) = opponent(opponent_idx, &trainers, &species, &mut rng);
+    if let Some(ai) = args.player_ai {
+        player.ai = ai;
+    }
+    if let Some(ai) = args.opponent_ai {
+        opponent.ai = ai;
+    }
+
+    if args.battles > 1 {
+        let stats = run_simulations(&player, &opponent, wild, args.battles, args.threads, seed);
+        print_statistics(&stats, &player, &opponent);
+        println!("シード値: {}", seed);
+        return Ok(());
+    }
+
+    let mut battle = Battle::new(player, opponent, wild, rng);
+    let mut replay = Replay::new(&battle, seed, data_version);
+    run_simulation(&mut battle, &mut replay);
+    println!("シード値: {}", seed);
+    save_replay(args, &replay)?;
+
+    Ok(())
+}
+
+/// `--replay-file` を指定した場合にリプレイを保存
+///
+/// # 引数
+/// * `args`   - コマンドライン引数
+/// * `replay` - 記録したリプレイ
+fn save_replay(args: &Args, replay: &Replay) -> Result<(), RunError> {
+    if let Some(path) = &args.replay_file {
+        write_replay(path, replay)?;
+        println!("リプレイを {} に保存しました", path);
+    }
+
+    Ok(())
+}
+
+/// 記録したバトルを再生
+///
+/// 現在のゲームデータが記録したときと異なる場合は警告する（手持ちはリプレイに含まれるため再生は続ける）
+///
+/// # 引数
+/// * `args` - コマンドライン引数
+fn replay(args: &Args) -> Result<(), RunError> {
+    let Some(path) = &args.replay_file else {
+        return Err(ArgsError::MissingValue("--replay-file".to_string()).into());
+    };
+    let replay = read_replay(path)?;
+
+    if let (Some(recorded), Ok((_, _, _, _, Some(current)))) = (replay.data_version, load(args)) {
+        if recorded != current {
+            eprintln!("リプレイを記録したときとゲームデータが異なります（記録時: {:08x}、現在: {:08x}）", recorded, current);
         }
+    }
+
+    play_replay(&replay, args.step);
+    println!("シード値: {}", replay.seed);
+
+    Ok(())
+}
+
+/// ゲームデータのトレーナーで新しいセーブデータを作成
+///
+/// `--trainer` を指定しなかった場合は画面で選択する
+/// 進行中のセーブデータを失わないよう、既にあるファイルは上書きしない
+///
+/// # 引数
+/// * `args` - コマンドライン引数
+fn save(args: &Args) -> Result<(), RunError> {
+    let Some(path) = &args.save_file else {
+        return Err(ArgsError::MissingValue("--save-file".to_string()).into());
+    };
+    if Path::new(path).exists() {
+        return Err(RunError::SaveExists(path.clone()));
+    }
+
+    let (_, _, trainers, _, _) = load(args)?;
+    let player_idx = match &args.trainer {
+        Some(id) => find_trainer(&trainers, id)?,
+        None => select_trainer(&trainers),
+    };
+
+    let save = SaveData::new(trainers[player_idx].clone());
+    write_save(path, &save)?;
+    println!("{} のセーブデータを {} に作成しました", save.trainer.name, path);
+
+    Ok(())
+}
+
+/// セーブデータの内容を表示
+///
+/// # 引数
+/// * `args` - コマンドライン引数
+fn load_save(args: &Args) -> Result<(), RunError> {
+    let Some(path) = &args.save_file else {
+        return Err(ArgsError::MissingValue("--save-file".to_string()).into());
+    };
+
+    let (species, skills, _, _, _) = load(args)?;
+    print_save(&read_save(path, &species, &skills)?);
+
+    Ok(())
+}
+
+/// セーブデータのバッグの道具を手持ちのポケモンに使う
+///
+/// 道具で進化するポケモンに使った場合は進化し、道具を 1 つ消費する（進化をキャンセルした場合は消費しない）
+///
+/// # 引数
+/// * `args` - コマンドライン引数
+fn use_item(args: &Args) -> Result<(), RunError> {
+    let Some(path) = &args.save_file else {
+        return Err(ArgsError::MissingValue("--save-file".to_string()).into());
+    };
+    let Some(item) = &args.item else {
+        return Err(ArgsError::MissingValue("--item".to_string()).into());
+    };
+
+    let (species, skills, _, items, _) = load(args)?;
+    let mut save = read_save(path, &species, &skills)?;
+    if save.bag.get(item).copied().unwrap_or(0) == 0 {
+        return Err(RunError::NoItem(item.clone()));
+    }
+
+    let name = items.get(item).map_or(item.as_str(), |definition| definition.name.as_str());
+    if items.get(item).is_some_and(|definition| matches!(definition.kind, ItemKind::Ball(_))) {
+        println!("{}は　やせいの　ポケモンとの　バトルで　なげて　つかう　どうぐです", name);
+        return Ok(());
+    }
+    let Some(idx) = select_item_target(&save.trainer, name) else {
+        return Ok(());
+    };
+    let pokemon = &mut save.trainer.pokemons[idx];
+    let Some(into) = pokemon.evolution_target(EvolutionTrigger::Item(item)).and_then(|id| species.get(id)) else {
+        println!("つかっても　こうかが　ないよ");
+        return Ok(());
     };
 
-    // ポケモンデータを変換
-    let mut pokemon_data: HashMap<&'static str, Pokemon> = HashMap::new();
-    for (id, pokemon) in pokemon_json.into_iter() {
-        // 図鑑番号
-        let id_static = Box::leak(id.into_boxed_str());
+    if evolve_pokemon(pokemon, into.clone()) {
+        save.take_item(item);
+        write_save(path, &save)?;
+        println!("{} にレポートを書き込みました", path);
+    }
+
+    Ok(())
+}
+
+/// JSON ファイルをバイナリファイルに変換
+///
+/// 不正なデータを書き出さないよう、変換できることを確認してから書き込む
+///
+/// # 引数
+/// * `args` - コマンドライン引数
+fn convert(args: &Args) -> Result<(), RunError> {
+    let data = read_game_data(&args.data_dir)?;
+    data.clone().into_battle_data()?;
 
-        // Pokemon を HashMap に追加
-        pokemon_data.insert(id_static, pokemon.into_pokemon(id_static, &skill_json));
+    write_bin(&args.bin_path, &data)?;
+    println!("{} を作成しました", args.bin_path);
+
+    Ok(())
+}
+
+/// JSON ファイルを検証
+///
+/// 問題が見つかった場合はすべて表示し、終了コード 1 で終了する
+///
+/// # 引数
+/// * `args` - コマンドライン引数
+fn validate(args: &Args) -> Result<(), RunError> {
+    let problems = validate_game_data(&args.data_dir);
+    if problems.is_empty() {
+        println!("{}: 問題は見つかりませんでした", args.data_dir);
+        return Ok(());
     }
 
-    // トレーナーデータを変換
-    let mut trainers: Vec<Trainer> = trainer_json
-        .into_iter()
-        .map(|(_, trainer_json)| trainer_json.into_trainer(&pokemon_data))
-        .collect();
+    for problem in &problems {
+        eprintln!("{}", problem);
+    }
+    eprintln!("{} 件の問題が見つかりました", problems.len());
+    std::process::exit(1);
+}
 
-    let mut selected_trainer = select_trainer(&mut trainers);
+/// シード値を取得
+///
+/// `--seed`、環境変数 BATTLE_SEED の順に参照し、どちらもなければ現在時刻から生成する
+///
+/// # 引数
+/// * `args` - コマンドライン引数
+///
+/// # 戻り値
+/// * `u64` - シード値
+fn seed(args: &Args) -> Result<u64, ArgsError> {
+    if let Some(seed) = args.seed {
+        return Ok(seed);
+    }
 
-    // トレーナーが選んだポケモンを選択
-    select_pokemon(&mut selected_trainer);
+    match env::var("BATTLE_SEED") {
+        Ok(seed) => seed
+            .trim()
+            .parse::<u64>()
+            .map_err(|_| ArgsError::InvalidValue { option: "BATTLE_SEED".to_string(), value: seed }),
+        Err(_) => Ok(XorShift128::entropy_seed()),
+    }
+}
 
-    // バトルするポケモンを取得
-    let mut enemy_pokemon = select_random_enemy_pokemon(pokemon_data);
-    let mut self_pokemon = selected_trainer.active_pokemon.clone().unwrap();
+/// コマンドを実行
+///
+/// # 引数
+/// * `args` - コマンドライン引数
+fn run(args: &Args) -> Result<(), RunError> {
+    set_text_speed(args.text_speed);
 
-    // バトル開始
-    start_battle(&mut self_pokemon, &mut enemy_pokemon);    
+    match args.command {
+        Command::Play => play(args, seed(args)?),
+        Command::Simulate => simulate(args, seed(args)?),
+        Command::Replay => replay(args),
+        Command::Save => save(args),
+        Command::Load => load_save(args),
+        Command::UseItem => use_item(args),
+        Command::Validate => validate(args),
+        Command::Convert => convert(args),
+    }
 }
 
 fn main() {
     dotenv().ok();
-    let run_mode: &str = &env::var("RUN_MODE").expect("RUN_MODE が設定されていません。");
-
-    match run_mode {
-        // ハードコードしたデータで実行
-        "hard_code" => test(),
-        // JSON からデータを取得して実行
-        "json" => init(),
-        // バイナリファイルからデータを取得して実行
-        "bin" => todo!(),
-        _ => unreachable!()
-    }
-}
\ No newline at end of file
+
+    // コマンドを指定しなかった場合は RUN_MODE に従う
+    let run_mode = env::var("RUN_MODE").ok();
+    let args = match parse_args(env::args().skip(1), run_mode.as_deref()) {
+        Ok(args) => args,
+        Err(ArgsError::Help) => {
+            println!("{}", USAGE);
+            return;
+        }
+        Err(err) => {
+            eprintln!("{}\n\n{}", err, USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    match run(&args) {
+        Ok(()) => {}
+        Err(RunError::Args(err)) => {
+            eprintln!("{}", err);
+            std::process::exit(2);
+        }
+        Err(err) => {
+            eprintln!("{}", err);
+            std::process::exit(1);
+        }
+    }
+}
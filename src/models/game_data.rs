@@ -0,0 +1,15 @@
+use std::collections::HashMap;
+
+use super::{item::Item, pokemon::{Skill, SpeciesJson}, trainer::TrainerJson};
+
+/// ゲームデータ一式を表す構造体
+///
+/// ポケモンの種族、技、トレーナー、道具をそれぞれのキーで保持する
+/// JSON とバイナリのどちらから読み込んでも同じ値になる
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct GameData {
+    pub species: HashMap<String, SpeciesJson>,
+    pub skills: HashMap<String, Skill>,
+    pub trainers: HashMap<String, TrainerJson>,
+    pub items: HashMap<String, Item>,
+}
@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+
+/// 道具を表す構造体
+///
+/// 道具名、道具の種類で構成
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct Item {
+    pub name: String,
+    pub kind: ItemKind,
+}
+
+/// 道具の種類を表す列挙型
+///
+/// やせいのポケモンを捕まえるボール、ポケモンを進化させる道具で構成
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+pub enum ItemKind {
+    Ball(Ball),
+    Evolution,
+}
+
+/// ボールの性能を表す構造体
+///
+/// ボールの補正（10 倍した値、10 で 1 倍）、必ず捕まえられるかどうかで構成
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+pub struct Ball {
+    pub modifier: u8,
+    #[serde(default)]
+    pub guaranteed: bool,
+}
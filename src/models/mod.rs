@@ -1,2 +1,6 @@
+pub mod game_data;
+pub mod item;
 pub mod pokemon;
+pub mod replay;
+pub mod save;
 pub mod trainer;
\ No newline at end of file
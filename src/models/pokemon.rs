@@ -1,33 +1,142 @@
+use std::sync::Arc;
+
 use serde::{Deserialize, Serialize};
 
 /// ポケモンを表す構造体
 ///
-/// 各ポケモンは名前、レベル、ステータス、使用できる技で構成
+/// 1 匹ずつの個体を表し、各ポケモンは ID、種族、ニックネーム、レベル、経験値、なつき度、個体値、努力値、性格、ステータス、状態異常、一時的な状態、使用できる技で構成
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
 pub struct Pokemon {
-    pub id: &'static str,
-    pub name: String,
+    /// 個体を区別する ID（0 は未登録のやせいのポケモン）
+    pub uid: u64,
+    pub species: Arc<Species>,
+    pub nickname: Option<String>,
     pub level: u8,
-    pub element: Vec<ElementType>,
+    /// 累計の経験値
+    #[serde(default)]
+    pub exp: u32,
+    #[serde(default = "Pokemon::base_friendship")]
+    pub friendship: u8,
+    pub ivs: StatusPoints,
+    pub evs: StatusPoints,
+    pub nature: Nature,
     pub status: Status,
-    pub skills: Vec<Skill>,
+    pub ailment: Option<StatusAilment>,
+    /// ねむりの残りターン数、もうどくの経過ターン数
+    pub ailment_turns: u8,
+    pub volatile: VolatileStatus,
+    pub skills: Vec<LearnedSkill>,
 }
 
-/// ポケモンを表す構造体
+impl Pokemon {
+    /// 捕まえたときや受け取ったときのなつき度
+    pub fn base_friendship() -> u8 {
+        70
+    }
+
+    /// 表示する名前（ニックネームがなければ種族名）
+    pub fn name(&self) -> &str {
+        self.nickname.as_deref().unwrap_or(&self.species.name)
+    }
+}
+
+/// ポケモンの種族を表す構造体
 ///
-/// 各ポケモンは名前、レベル、ステータス、使用できる技で構成
+/// 図鑑番号、種族名、タイプ、基礎経験値、経験値タイプ、種族値、覚えられる技、進化先、やせいで出現するレベル、捕まえやすさで構成
+/// 同じ種族のポケモンで共有し、進化すると進化先の種族に置き換わる
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
-pub struct PokemonJson {
+pub struct Species {
+    pub id: String,
+    pub name: String,
+    pub element: Vec<ElementType>,
+    /// 倒したときにもらえる経験値の基準
+    pub base_exp: u16,
+    pub growth_rate: GrowthRate,
+    pub base_status: StatusPoints,
+    pub learnset: Learnset,
+    pub evolutions: Vec<Evolution>,
+    /// やせいで出現するときのレベル（None の場合はやせいでは出現しない）
+    #[serde(default)]
+    pub wild_level: Option<u8>,
+    /// 捕まえやすさ（1 〜 255、大きいほど捕まえやすい）
+    #[serde(default = "Species::default_catch_rate")]
+    pub catch_rate: u8,
+}
+
+impl Species {
+    /// 捕まえやすさを指定しない場合の値
+    pub fn default_catch_rate() -> u8 {
+        45
+    }
+}
+
+/// 進化先と進化の条件を表す構造体
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct Evolution {
+    /// 進化先の図鑑番号
+    pub into: String,
+    pub condition: EvolutionCondition,
+}
+
+/// 進化の条件を表す列挙型
+///
+/// レベル、どうぐ（キー）、なつき度、通信交換で構成
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub enum EvolutionCondition {
+    Level(u8),
+    Item(String),
+    Friendship(u8),
+    Trade,
+}
+
+/// ポケモンの種族を表すJSON用構造体
+///
+/// 図鑑番号、種族名、タイプ、基礎経験値、経験値タイプ、種族値、覚えられる技、進化先、やせいで出現するレベル、捕まえやすさで構成
+/// 経験値タイプ、覚えられる技、進化先、やせいで出現するレベル、捕まえやすさは省略可能
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct SpeciesJson {
     pub id: String,
     pub name: String,
-    pub level: u8,
     pub element: Vec<String>,
-    pub status: Status,
-    pub skills: Vec<String>,
+    pub base_exp: u16,
+    #[serde(default)]
+    pub growth_rate: GrowthRate,
+    pub base_status: StatusPoints,
+    #[serde(default)]
+    pub learnset: LearnsetJson,
+    #[serde(default)]
+    pub evolutions: Vec<Evolution>,
+    #[serde(default)]
+    pub wild_level: Option<u8>,
+    #[serde(default = "Species::default_catch_rate")]
+    pub catch_rate: u8,
+}
+
+/// ポケモンの個体を表すJSON用構造体
+///
+/// 種族（図鑑番号）、ニックネーム、レベル、個体値、努力値、性格、現在のHP、使用できる技で構成
+/// 種族とレベル以外は省略可能（技を省略した場合はレベルまでに覚える最後の 4 つ）
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct PokemonJson {
+    pub species: String,
+    #[serde(default)]
+    pub nickname: Option<String>,
+    pub level: u8,
+    #[serde(default = "StatusPoints::max_ivs")]
+    pub ivs: StatusPoints,
+    #[serde(default)]
+    pub evs: StatusPoints,
+    #[serde(default)]
+    pub nature: Nature,
+    #[serde(default)]
+    pub current_hp: Option<u16>,
+    #[serde(default)]
+    pub skills: Option<Vec<String>>,
 }
 
 /// ポケモンのステータスを表す構造体
 ///
+/// 種族値、個体値、努力値から計算した実数値
 /// HP、こうげき、ぼうぎょ、とくこう、とくぼう、すばやさで構成
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
 pub struct Status {
@@ -37,50 +146,270 @@ pub struct Status {
     pub sp_atk: BufToStatus,
     pub sp_def: BufToStatus,
     pub spd: BufToStatus,
-    pub current_hp: u8,
+    pub current_hp: u16,
 }
 
 /// 各ステータスのバフ/デバフを表す構造体
 ///
-/// 基本値、一時的な変更値で構成
+/// 実数値、一時的な変更値で構成
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
 pub struct BufToStatus {
-    pub value: u8,
+    pub value: u16,
     pub buff: i8,
 }
 
+/// 6つのステータスそれぞれの値を表す構造体
+///
+/// 種族値、個体値、努力値で使用
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default, PartialEq)]
+pub struct StatusPoints {
+    pub hp: u8,
+    pub atk: u8,
+    pub def: u8,
+    pub sp_atk: u8,
+    pub sp_def: u8,
+    pub spd: u8,
+}
+
+impl StatusPoints {
+    /// 個体値がすべて最大のステータス
+    pub fn max_ivs() -> Self {
+        StatusPoints { hp: 31, atk: 31, def: 31, sp_atk: 31, sp_def: 31, spd: 31 }
+    }
+}
+
+/// レベルアップに必要な経験値の増え方を表す列挙型
+///
+/// レベル 100 までに必要な経験値は 60万、80万、100万、105万9860、125万、164万の順に多い
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GrowthRate {
+    Erratic,
+    Fast,
+    #[default]
+    MediumFast,
+    MediumSlow,
+    Slow,
+    Fluctuating,
+}
+
+/// ポケモンの性格を表す列挙型
+///
+/// 性格に応じてステータスのいずれかが 1.1 倍、いずれかが 0.9 倍になる
+#[allow(dead_code)]
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Nature {
+    #[default]
+    Hardy,
+    Lonely,
+    Brave,
+    Adamant,
+    Naughty,
+    Bold,
+    Docile,
+    Relaxed,
+    Impish,
+    Lax,
+    Timid,
+    Hasty,
+    Serious,
+    Jolly,
+    Naive,
+    Modest,
+    Mild,
+    Quiet,
+    Bashful,
+    Rash,
+    Calm,
+    Gentle,
+    Sassy,
+    Careful,
+    Quirky,
+}
+
 /// ポケモンが使用できる技を表す構造体
 ///
-/// 技名、威力、命中率、技の追加効果、技の種類で構成
+/// 技のキー、技名、威力、命中率、PP、優先度、急所ランク、溜めが必要か、壁を壊すか、技の効果、技の追加効果、技の種類で構成
+/// 溜めが必要な技は 1 ターン目に力を溜め、2 ターン目に攻撃する
+/// 壁を壊す技はダメージを与える前に相手の陣営のリフレクターとひかりのかべを解除する
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
 pub struct Skill {
+    /// skill_data.json のキー（技データには書かず、バトルで使用するデータに変換するときに設定する）
+    #[serde(default)]
+    pub id: String,
     pub name: String,
     pub element: ElementType,
     pub base_atk: u8,
     pub accuracy: u8,
+    pub pp: u8,
     pub priority: i8,
+    #[serde(default)]
+    pub critical_rank: u8,
+    #[serde(default)]
+    pub charge: bool,
+    #[serde(default)]
+    pub breaks_screens: bool,
     pub skill_effect: Option<SkillEffect>,
+    #[serde(default)]
+    pub secondary_effects: Vec<SecondaryEffect>,
     pub class: SkillType,
 }
 
+/// ポケモンが覚えられる技を表す構造体
+///
+/// レベルアップで覚える技（レベルの低い順）、わざマシンで覚えられる技、タマゴわざで構成
+/// 技の定義は同じ種類のポケモンで共有する
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+pub struct Learnset {
+    pub level_up: Vec<LevelUpSkill>,
+    pub tm: Vec<Arc<Skill>>,
+    pub egg: Vec<Arc<Skill>>,
+}
+
+/// レベルアップで覚える技を表す構造体
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct LevelUpSkill {
+    pub level: u8,
+    pub skill: Arc<Skill>,
+}
+
+/// ポケモンが覚えられる技を表すJSON用構造体
+///
+/// 技は skill_data.json のキーで指定する
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct LearnsetJson {
+    pub level_up: Vec<LevelUpSkillJson>,
+    pub tm: Vec<String>,
+    pub egg: Vec<String>,
+}
+
+/// レベルアップで覚える技を表すJSON用構造体
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct LevelUpSkillJson {
+    pub level: u8,
+    pub skill: String,
+}
+
+/// ポケモンが覚えている技を表す構造体
+///
+/// 技の定義、残りPP、最大PPで構成
+/// 技の定義は変化しないため共有し、バトルの状態を複製するときにコピーしない
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct LearnedSkill {
+    pub skill: Arc<Skill>,
+    pub current_pp: u8,
+    pub max_pp: u8,
+}
+
+impl LearnedSkill {
+    /// 技の定義から PP が満タンの状態で生成
+    pub fn new(skill: Skill) -> Self {
+        LearnedSkill::from_shared(Arc::new(skill))
+    }
+
+    /// 共有している技の定義から PP が満タンの状態で生成
+    pub fn from_shared(skill: Arc<Skill>) -> Self {
+        LearnedSkill {
+            current_pp: skill.pp,
+            max_pp: skill.pp,
+            skill,
+        }
+    }
+}
+
 /// 技の種類を表す列挙型
 ///
-/// 物理攻撃、特殊攻撃、ステータス変化で構成
+/// 物理攻撃、特殊攻撃、ステータス変化、一撃必殺、わるあがきで構成
 #[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
 pub enum SkillType {
     PhysicalAttack,
     SpecialAttack,
     ChangeStatus,
     OneHitKO,
+    Struggle,
+}
+
+impl Skill {
+    /// すべての技の PP がなくなったときに使用する「わるあがき」
+    ///
+    /// タイプ相性とタイプ一致の影響を受けず、使用者は最大HPの 1/4 の反動を受ける
+    pub fn struggle() -> Self {
+        Skill {
+            id: "struggle".to_string(),
+            name: "わるあがき".to_string(),
+            element: ElementType::Normal,
+            base_atk: 50,
+            accuracy: 100,
+            pp: 0,
+            priority: 0,
+            critical_rank: 0,
+            charge: false,
+            breaks_screens: false,
+            skill_effect: None,
+            secondary_effects: vec![],
+            class: SkillType::Struggle,
+        }
+    }
 }
 
 /// 技の効果を表す構造体
 ///
-/// ステータスへの影響の詳細、効果の対象で構成
+/// ステータスへの影響の詳細、付与する状態異常、急所ランクの上昇量、張る壁、効果の対象で構成
 #[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
 pub struct SkillEffect {
-    pub status_effect: StatusEffect,
+    #[serde(default)]
+    pub status_effect: Option<StatusEffect>,
+    #[serde(default)]
+    pub ailment: Option<StatusAilment>,
+    #[serde(default)]
+    pub critical_stage: u8,
+    #[serde(default)]
+    pub screen: Option<Screen>,
+    pub target: Target,
+}
+
+/// 攻撃技の追加効果を表す構造体
+///
+/// 発動確率、効果の対象、効果の種類で構成
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+pub struct SecondaryEffect {
+    pub chance: u8,
     pub target: Target,
+    pub effect: EffectKind,
+}
+
+/// 追加効果の種類を表す列挙型
+///
+/// ステータスのランク変化、状態異常、ひるみ、こんらんで構成
+#[allow(dead_code)]
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+pub enum EffectKind {
+    StatusChange(StatusEffect),
+    Ailment(StatusAilment),
+    Flinch,
+    Confusion,
+}
+
+/// 交代やターン経過で解除される一時的な状態を表す構造体
+///
+/// ひるみ、こんらんの残りターン数、きあいだめなどによる急所ランク、力を溜めている技のインデックスで構成
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default, PartialEq)]
+pub struct VolatileStatus {
+    pub flinch: bool,
+    pub confusion_turns: u8,
+    #[serde(default)]
+    pub critical_stage: u8,
+    #[serde(default)]
+    pub charging: Option<usize>,
+}
+
+/// 陣営の場に張る壁を表す列挙型
+///
+/// リフレクターは物理技、ひかりのかべは特殊技のダメージを半分にする
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+pub enum Screen {
+    Reflect,
+    LightScreen,
 }
 
 /// 技の効果が適用される対象を表す列挙型
@@ -118,11 +447,25 @@ pub enum StatusType {
     Field
 }
 
+/// ポケモンの状態異常を表す列挙型
+///
+/// やけど、どく、もうどく、まひ、ねむり、こおりで構成
+#[allow(dead_code)]
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatusAilment {
+    Burn,
+    Poison,
+    BadPoison,
+    Paralysis,
+    Sleep,
+    Freeze,
+}
+
 /// ポケモンのタイプを表す列挙型
 /// 
 /// 各タイプ
 #[allow(dead_code)]
-#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ElementType {
     Normal,
     Fire,
@@ -179,6 +522,16 @@ impl SkillType {
             SkillType::SpecialAttack => "特殊技",
             SkillType::ChangeStatus => "変化技",
             SkillType::OneHitKO => "一撃必殺",
+            SkillType::Struggle => "わるあがき",
+        }
+    }
+}
+
+impl Screen {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Screen::Reflect => "リフレクター",
+            Screen::LightScreen => "ひかりのかべ",
         }
     }
 }
@@ -196,4 +549,50 @@ impl StatusType {
             StatusType::Field => "フィールド技"
         }
     }
-}
\ No newline at end of file
+}
+#[allow(dead_code)]
+impl Nature {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Nature::Hardy => "がんばりや",
+            Nature::Lonely => "さみしがり",
+            Nature::Brave => "ゆうかん",
+            Nature::Adamant => "いじっぱり",
+            Nature::Naughty => "やんちゃ",
+            Nature::Bold => "ずぶとい",
+            Nature::Docile => "すなお",
+            Nature::Relaxed => "のんき",
+            Nature::Impish => "わんぱく",
+            Nature::Lax => "のうてんき",
+            Nature::Timid => "おくびょう",
+            Nature::Hasty => "せっかち",
+            Nature::Serious => "まじめ",
+            Nature::Jolly => "ようき",
+            Nature::Naive => "むじゃき",
+            Nature::Modest => "ひかえめ",
+            Nature::Mild => "おっとり",
+            Nature::Quiet => "れいせい",
+            Nature::Bashful => "てれや",
+            Nature::Rash => "うっかりや",
+            Nature::Calm => "おだやか",
+            Nature::Gentle => "おとなしい",
+            Nature::Sassy => "なまいき",
+            Nature::Careful => "しんちょう",
+            Nature::Quirky => "きまぐれ",
+        }
+    }
+}
+
+#[allow(dead_code)]
+impl StatusAilment {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StatusAilment::Burn => "やけど",
+            StatusAilment::Poison => "どく",
+            StatusAilment::BadPoison => "もうどく",
+            StatusAilment::Paralysis => "まひ",
+            StatusAilment::Sleep => "ねむり",
+            StatusAilment::Freeze => "こおり",
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+
+use crate::logic::rand::XorShift128;
+use super::trainer::{BattleAction, Trainer};
+
+/// リプレイファイルのフォーマットのバージョン（レイアウトを変更したら上げる）
+pub const REPLAY_VERSION: u16 = 1;
+
+/// バトルのリプレイを表す構造体
+///
+/// フォーマットのバージョン、ゲームデータのバージョン、シード値、バトル開始時の乱数生成器の状態、
+/// 開始時の両陣営のトレーナー、経験値をもらうかどうか、ターンごとの行動で構成
+/// 開始時の状態と行動を記録するため、バトルエンジンで同じバトルを再現できる
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Replay {
+    pub version: u16,
+    /// 記録したときのゲームデータのバージョン（ハードコードしたデータの場合は None）
+    pub data_version: Option<u32>,
+    pub seed: u64,
+    pub rng: XorShift128,
+    pub wild: bool,
+    pub player: Trainer,
+    pub opponent: Trainer,
+    #[serde(default)]
+    pub exp_gain: bool,
+    pub turns: Vec<ReplayTurn>,
+}
+
+/// 1 ターン分の記録
+///
+/// 両陣営の行動、ターン終了後にレベルアップで覚えようとした技の代わりに忘れた技のインデックス（覚えなかった場合は None）、
+/// ひんしのポケモンと入れ替えたポケモンの手持ちのインデックスで構成
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct ReplayTurn {
+    pub player: BattleAction,
+    pub opponent: BattleAction,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skill_choices: Vec<Option<usize>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub player_replacement: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub opponent_replacement: Option<usize>,
+}
@@ -0,0 +1,105 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    pokemon::{Nature, Pokemon, Status, StatusAilment, StatusPoints, VolatileStatus},
+    trainer::{AiLevel, SearchConfig, Trainer}
+};
+
+/// セーブファイルのフォーマットのバージョン（レイアウトを変更したら上げ、`file::save` に古いバージョンからの変換を追加する）
+pub const SAVE_VERSION: u16 = 1;
+
+/// 新しいセーブデータのバッグに入れておく道具（キー, 個数）
+pub const STARTER_ITEMS: [(&str, u32); 2] = [("monster_ball", 5), ("thunder_stone", 1)];
+
+/// セーブデータを表す構造体
+///
+/// フォーマットのバージョン、自分のトレーナー、ボックス、バッグ、進行状況で構成
+/// 手持ちのポケモンは現在のHP、PP、状態異常、レベル、経験値を含めてそのまま保存する
+#[derive(Debug, Clone)]
+pub struct SaveData {
+    pub version: u16,
+    pub trainer: Trainer,
+    /// 手持ちがいっぱいのときに捕まえたポケモンを預ける場所（預けた順）
+    pub storage: Vec<Pokemon>,
+    /// 道具のキーごとの所持数
+    pub bag: BTreeMap<String, u32>,
+    pub progress: Progress,
+}
+
+/// セーブデータを表すJSON用構造体
+///
+/// ポケモンの種族は図鑑番号、技はキーだけを記録し、読み込むときにゲームデータの種族と技に置き換える
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct SaveDataJson {
+    pub version: u16,
+    pub trainer: SaveTrainerJson,
+    #[serde(default)]
+    pub storage: Vec<SavePokemonJson>,
+    #[serde(default)]
+    pub bag: BTreeMap<String, u32>,
+    #[serde(default)]
+    pub progress: Progress,
+}
+
+/// セーブデータのトレーナーを表すJSON用構造体
+///
+/// トレーナーデータのキー、トレーナ名、所持ポケモン（手持ちの並び順）、現在場に出しているポケモンのインデックス、AI の強さ、探索 AI の設定で構成
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct SaveTrainerJson {
+    pub id: String,
+    pub name: String,
+    pub pokemons: Vec<SavePokemonJson>,
+    pub active_idx: usize,
+    pub ai: AiLevel,
+    pub search: SearchConfig,
+}
+
+/// セーブデータのポケモンを表すJSON用構造体
+///
+/// 種族を図鑑番号、覚えている技をキーで記録する以外は `Pokemon` と同じ
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct SavePokemonJson {
+    pub uid: u64,
+    /// 図鑑番号
+    pub species: String,
+    pub nickname: Option<String>,
+    pub level: u8,
+    #[serde(default)]
+    pub exp: u32,
+    #[serde(default = "Pokemon::base_friendship")]
+    pub friendship: u8,
+    pub ivs: StatusPoints,
+    pub evs: StatusPoints,
+    pub nature: Nature,
+    pub status: Status,
+    pub ailment: Option<StatusAilment>,
+    pub ailment_turns: u8,
+    pub volatile: VolatileStatus,
+    pub skills: Vec<SaveSkillJson>,
+}
+
+/// セーブデータのポケモンが覚えている技を表すJSON用構造体
+///
+/// 技のキー、残りPP、最大PPで構成
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct SaveSkillJson {
+    /// skill_data.json のキー
+    pub skill: String,
+    pub current_pp: u8,
+    pub max_pp: u8,
+}
+
+/// 進行状況を表す構造体
+///
+/// バトルの回数、勝った回数、負けた回数、捕まえた回数、倒したトレーナーのキー（倒した順）で構成
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct Progress {
+    pub battles: u32,
+    pub wins: u32,
+    pub losses: u32,
+    pub caught: u32,
+    pub defeated_trainers: Vec<String>,
+}
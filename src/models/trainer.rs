@@ -1,21 +1,75 @@
 use serde::{Deserialize, Serialize};
 
-use super::pokemon::Pokemon;
+use super::{item::Ball, pokemon::{Pokemon, PokemonJson}};
 
 /// トレーナーを表すJSON用構造体
-#[derive(Deserialize, Serialize, Clone, Debug)]
+///
+/// 手持ちのポケモンは 1 匹ずつ個体として記述し、先頭に出すポケモンは手持ちのインデックスで指定する
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
 pub struct TrainerJson {
     pub name: String,
-    pub pokemons: Option<Vec<String>>,
-    pub active_pokemon: Option<String>,
+    pub pokemons: Option<Vec<PokemonJson>>,
+    pub active_pokemon: Option<usize>,
+    #[serde(default)]
+    pub ai: AiLevel,
+    #[serde(default)]
+    pub search: SearchConfig,
+}
+
+/// コンピュータが操作するときの AI の強さを表す列挙型
+///
+/// 使える技からランダム、最大ダメージの技、ランク変化や交代も考慮した判断、数ターン先までの探索で構成
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AiLevel {
+    #[default]
+    Random,
+    Greedy,
+    Heuristic,
+    Expectiminimax,
+}
+
+/// 探索 AI の設定
+///
+/// 先読みするターン数、1 回の行動選択で調べる局面数の上限、制限時間（ミリ秒）で構成
+/// 上限に達した場合は、最後まで調べ終えた深さの結果を使用する
+/// 制限時間は人が相手をするバトルでのみ使用し、シミュレーションは局面数の上限だけで打ち切る
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(default)]
+pub struct SearchConfig {
+    pub depth: u8,
+    pub max_nodes: u32,
+    pub time_limit_ms: u32,
+}
+
+impl Default for SearchConfig {
+    fn default() -> Self {
+        SearchConfig {
+            depth: 2,
+            max_nodes: 100_000,
+            time_limit_ms: 1000,
+        }
+    }
 }
 
 /// トレーナーを表す構造体
 /// 
-/// トレーナ名、所持ポケモン、現在場に出しているポケモンで構成
-#[derive(Serialize, Clone, Debug)]
+/// トレーナーデータのキー、トレーナ名、所持ポケモン（手持ちの並び順）、現在場に出しているポケモンのインデックス、AI の強さ、探索 AI の設定で構成
+#[derive(Deserialize, Serialize, Clone, Debug)]
 pub struct Trainer {
-    pub name: &'static str,
+    pub id: String,
+    pub name: String,
     pub pokemons: Vec<Pokemon>,
-    pub active_pokemon: Option<Pokemon>,
+    pub active_idx: usize,
+    pub ai: AiLevel,
+    pub search: SearchConfig,
+}
+
+/// バトル中にトレーナーが選択する行動を表す列挙型
+///
+/// 技を使う（技のインデックス）、ポケモンを交代する（手持ちのインデックス）、ボールを投げる（ボールの性能）で構成
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+pub enum BattleAction {
+    Fight(usize),
+    Switch(usize),
+    Ball(Ball),
 }
\ No newline at end of file
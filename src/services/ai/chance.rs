@@ -0,0 +1,104 @@
+use crate::logic::rand::RandomSource;
+
+/// すべての値を個別に列挙する範囲の大きさの上限
+const MAX_EXACT_BRANCHES: u64 = 4;
+
+/// 範囲が大きい場合に分割する区間の数
+const RANGE_BUCKETS: u64 = 3;
+
+/// 乱数の結果を列挙する乱数の取得元
+///
+/// 指定した分岐の選び方（接頭辞）に従って値を返し、接頭辞より後は最初の分岐を選ぶ
+/// 選んだ分岐と分岐数を記録するため、まだ調べていない分岐を後から列挙できる
+/// 範囲の大きい整数（ダメージの乱数など）は区間に分け、区間の中央の値で代表する
+#[derive(Debug, Clone, Default)]
+pub struct ChanceSource {
+    prefix: Vec<usize>,
+    choices: Vec<usize>,
+    arities: Vec<usize>,
+    probability: f64,
+}
+
+impl ChanceSource {
+    /// 分岐の選び方を指定して作成
+    ///
+    /// # 引数
+    /// * `prefix` - 先頭から順に選ぶ分岐のインデックス
+    pub fn new(prefix: Vec<usize>) -> Self {
+        ChanceSource { prefix, choices: Vec::new(), arities: Vec::new(), probability: 1.0 }
+    }
+
+    /// 選んだ分岐の組み合わせが起こる確率
+    pub fn probability(&self) -> f64 {
+        self.probability
+    }
+
+    /// まだ調べていない分岐の選び方を取得
+    ///
+    /// 接頭辞より後で最初の分岐を選んだ位置について、残りの分岐を選ぶ接頭辞を返す
+    ///
+    /// # 戻り値
+    /// * `Vec<Vec<usize>>` - 兄弟の分岐の接頭辞
+    pub fn siblings(&self) -> Vec<Vec<usize>> {
+        let mut siblings = Vec::new();
+        for pos in self.prefix.len()..self.choices.len() {
+            for alternative in 1..self.arities[pos] {
+                let mut prefix = self.choices[..pos].to_vec();
+                prefix.push(alternative);
+                siblings.push(prefix);
+            }
+        }
+
+        siblings
+    }
+
+    /// 分岐を選択して確率を掛ける
+    ///
+    /// # 引数
+    /// * `weights` - 各分岐の確率
+    ///
+    /// # 戻り値
+    /// * `usize` - 選んだ分岐のインデックス
+    fn branch(&mut self, weights: &[f64]) -> usize {
+        let choice = self.prefix.get(self.choices.len()).copied().unwrap_or(0);
+        self.choices.push(choice);
+        self.arities.push(weights.len());
+        self.probability *= weights[choice];
+
+        choice
+    }
+}
+
+impl RandomSource for ChanceSource {
+    fn random_in_range(&mut self, min: u64, max: u64) -> u64 {
+        let size = max - min + 1;
+        if size <= MAX_EXACT_BRANCHES {
+            let weights = vec![1.0 / size as f64; size as usize];
+            return min + self.branch(&weights) as u64;
+        }
+
+        // 区間に分け、区間に含まれる値の数で重み付けする
+        let bounds: Vec<(u64, u64)> = (0..RANGE_BUCKETS)
+            .map(|bucket| (min + size * bucket / RANGE_BUCKETS, min + size * (bucket + 1) / RANGE_BUCKETS - 1))
+            .collect();
+        let weights: Vec<f64> = bounds.iter().map(|(low, high)| (high - low + 1) as f64 / size as f64).collect();
+        let (low, high) = bounds[self.branch(&weights)];
+
+        (low + high) / 2
+    }
+
+    fn random_bool(&mut self, probability: f64) -> bool {
+        if probability <= 0.0 {
+            return false;
+        }
+        if probability >= 1.0 {
+            return true;
+        }
+
+        self.branch(&[probability, 1.0 - probability]) == 0
+    }
+
+    fn random_ratio(&mut self, numerator: u64, denominator: u64) -> bool {
+        self.random_bool(numerator as f64 / denominator as f64)
+    }
+}
@@ -0,0 +1,162 @@
+use std::sync::Arc;
+
+use crate::{
+    models::pokemon::{Pokemon, Skill, SkillType},
+    services::battle::{engine::Screens, pokemon::{PokemonActions, MAX_DAMAGE_ROLL, MIN_DAMAGE_ROLL}}
+};
+
+/// ダメージの乱数の平均値（%）
+const AVERAGE_DAMAGE_ROLL: u8 = (MIN_DAMAGE_ROLL + MAX_DAMAGE_ROLL) / 2;
+
+/// ポケモンが使用できる技の一覧を取得
+///
+/// すべての技の PP がない場合はわるあがきのみを返す
+///
+/// # 引数
+/// * `pokemon` - ポケモン
+///
+/// # 戻り値
+/// * `Vec<(usize, Arc<Skill>)>` - (技リストのインデックス, 技)
+pub fn usable_skills(pokemon: &Pokemon) -> Vec<(usize, Arc<Skill>)> {
+    if !pokemon.has_usable_skill() {
+        return vec![(0, Arc::new(Skill::struggle()))];
+    }
+
+    pokemon
+        .skills
+        .iter()
+        .enumerate()
+        .filter(|(_, learned)| learned.current_pp > 0)
+        .map(|(idx, learned)| (idx, learned.skill.clone()))
+        .collect()
+}
+
+/// 技が当たったときのダメージを計算
+///
+/// 急所と乱数は考慮せず、平均的なダメージを返す
+/// ターゲットの場に技を弱める壁がある場合はバトルと同じく半分にする
+///
+/// # 引数
+/// * `attacker`       - 攻撃するポケモン
+/// * `skill`          - 使用する技
+/// * `target`         - 攻撃するターゲット
+/// * `target_screens` - ターゲットの場の壁
+///
+/// # 戻り値
+/// * `u16` - ダメージ（攻撃技でない場合や効果がない場合は 0）
+pub fn hit_damage(attacker: &Pokemon, skill: &Skill, target: &Pokemon, target_screens: &Screens) -> u16 {
+    match skill.class {
+        SkillType::ChangeStatus => 0,
+        SkillType::OneHitKO if target.type_effectiveness(skill.element) == 0.0 => 0,
+        SkillType::OneHitKO => target.status.current_hp,
+        SkillType::PhysicalAttack | SkillType::SpecialAttack if target.type_effectiveness(skill.element) == 0.0 => 0,
+        _ if target_screens.reduces(skill.class) => {
+            (attacker.compute_damage(skill, target, false, AVERAGE_DAMAGE_ROLL) / 2).max(1)
+        }
+        _ => attacker.compute_damage(skill, target, false, AVERAGE_DAMAGE_ROLL),
+    }
+}
+
+/// 命中率と急所を考慮したダメージの期待値を計算
+///
+/// ターゲットの残りHPを超えるダメージは残りHPとして扱う
+/// 溜めが必要な技は 2 ターンで 1 回の攻撃になるため、1 ターンあたりのダメージにする
+/// 急所に当たった場合は壁を無視する
+///
+/// # 引数
+/// * `attacker`       - 攻撃するポケモン
+/// * `skill`          - 使用する技
+/// * `target`         - 攻撃するターゲット
+/// * `target_screens` - ターゲットの場の壁
+///
+/// # 戻り値
+/// * `f64` - ダメージの期待値
+pub fn expected_damage(attacker: &Pokemon, skill: &Skill, target: &Pokemon, target_screens: &Screens) -> f64 {
+    let current_hp = target.status.current_hp as f64;
+    let hit = skill.accuracy.min(100) as f64 / 100.0;
+
+    let damage = match skill.class {
+        SkillType::PhysicalAttack | SkillType::SpecialAttack | SkillType::Struggle => {
+            let normal = (hit_damage(attacker, skill, target, target_screens) as f64).min(current_hp);
+            if normal == 0.0 {
+                return 0.0;
+            }
+            let critical = (attacker.compute_damage(skill, target, true, AVERAGE_DAMAGE_ROLL) as f64).min(current_hp);
+            let chance = attacker.critical_chance(skill);
+
+            normal * (1.0 - chance) + critical * chance
+        }
+        _ => hit_damage(attacker, skill, target, target_screens) as f64,
+    };
+    let turns = if skill.charge && attacker.volatile.charging.is_none() { 2.0 } else { 1.0 };
+
+    damage * hit / turns
+}
+
+/// 使用できる技の中で最も大きいダメージの期待値を取得
+///
+/// # 引数
+/// * `attacker`       - 攻撃するポケモン
+/// * `target`         - 攻撃するターゲット
+/// * `target_screens` - ターゲットの場の壁
+///
+/// # 戻り値
+/// * `f64` - ダメージの期待値
+pub fn best_expected_damage(attacker: &Pokemon, target: &Pokemon, target_screens: &Screens) -> f64 {
+    usable_skills(attacker)
+        .iter()
+        .map(|(_, skill)| expected_damage(attacker, skill, target, target_screens))
+        .fold(0.0, f64::max)
+}
+
+/// 対面の有利さを評価
+///
+/// 相手の残りHPに対して与えられるダメージの割合から、受けるダメージの割合を引いた値
+/// すばやさで上回る場合は先に攻撃できるため少し加点する
+///
+/// # 引数
+/// * `pokemon`       - 評価するポケモン
+/// * `enemy`         - 相手のポケモン
+/// * `screens`       - 評価するポケモンの場の壁
+/// * `enemy_screens` - 相手の場の壁
+///
+/// # 戻り値
+/// * `f64` - 評価値（-1.0 〜 1.1、大きいほど有利）
+pub fn matchup(pokemon: &Pokemon, enemy: &Pokemon, screens: &Screens, enemy_screens: &Screens) -> f64 {
+    let dealt = best_expected_damage(pokemon, enemy, enemy_screens) / enemy.status.current_hp.max(1) as f64;
+    let taken = best_expected_damage(enemy, pokemon, screens) / pokemon.status.current_hp.max(1) as f64;
+    let speed = if pokemon.effective_speed() > enemy.effective_speed() { 0.1 } else { 0.0 };
+
+    dealt.min(1.0) - taken.min(1.0) + speed
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{file::json::fixture::trainer, services::battle::{engine::SCREEN_TURNS, trainer::TrainerActions}};
+
+    /// 壁が張られている場合はバトルと同じく急所以外のダメージを半分として評価する
+    #[test]
+    fn screens_halve_the_expected_damage() {
+        let (player, opponent) = (trainer("wataru"), trainer("daigo"));
+        let (attacker, target) = (player.active_pokemon(), opponent.active_pokemon());
+        let screens = Screens { reflect: SCREEN_TURNS, light_screen: SCREEN_TURNS };
+
+        for learned in &attacker.skills {
+            let skill = &learned.skill;
+            let normal = hit_damage(attacker, skill, target, &Screens::default());
+            let reduced = hit_damage(attacker, skill, target, &screens);
+            match skill.class {
+                SkillType::PhysicalAttack | SkillType::SpecialAttack if normal > 0 => {
+                    assert_eq!(reduced, (normal / 2).max(1));
+                    assert!(
+                        expected_damage(attacker, skill, target, &screens)
+                            < expected_damage(attacker, skill, target, &Screens::default())
+                    );
+                }
+                _ => assert_eq!(reduced, normal),
+            }
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use crate::{
+    logic::rand::RandomSource,
+    models::trainer::BattleAction,
+    services::{
+        ai::{evaluate::{expected_damage, usable_skills}, random::RandomAi, strategy::AiStrategy},
+        battle::{engine::Battle, event::Side, trainer::TrainerActions}
+    }
+};
+
+/// タイプ相性とダメージ計算式から、ダメージの期待値が最も大きい技を選ぶ戦略
+///
+/// 交代は行わず、ダメージを与えられる技がない場合はランダムに選ぶ
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GreedyAi;
+
+impl AiStrategy for GreedyAi {
+    fn select_action(&self, battle: &Battle, side: Side, rng: &mut dyn RandomSource) -> BattleAction {
+        let attacker = battle.trainer(side).active_pokemon();
+        let target = battle.trainer(side.opposite()).active_pokemon();
+        let target_screens = battle.screens(side.opposite());
+
+        let mut best: Option<(usize, f64)> = None;
+        for (idx, skill) in usable_skills(attacker) {
+            let damage = expected_damage(attacker, &skill, target, target_screens);
+            // 同じ期待値の場合は技リストの先頭を優先
+            if damage > best.map_or(0.0, |(_, best_damage)| best_damage) {
+                best = Some((idx, damage));
+            }
+        }
+
+        match best {
+            Some((idx, _)) => BattleAction::Fight(idx),
+            None => RandomAi.select_action(battle, side, rng),
+        }
+    }
+}
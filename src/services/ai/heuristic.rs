@@ -0,0 +1,217 @@
+use crate::{
+    logic::rand::RandomSource,
+    models::{
+        pokemon::{Pokemon, Skill, SkillType, StatusAilment, StatusType, Target},
+        trainer::BattleAction
+    },
+    services::{
+        ai::{evaluate::{best_expected_damage, hit_damage, matchup, usable_skills}, strategy::AiStrategy},
+        battle::{ailment::AilmentActions, engine::{Battle, Screens}, event::Side, pokemon::PokemonActions, trainer::TrainerActions}
+    }
+};
+
+/// 交代する場合に必要な対面の評価値の改善幅
+const SWITCH_THRESHOLD: f64 = 0.6;
+
+/// 倒せる技の評価値（命中率を掛けて使用）
+const KO_SCORE: f64 = 2.0;
+
+/// ダメージ、ランク変化、状態異常、交代を評価して行動を選ぶ戦略
+///
+/// 倒せる技があれば優先し、倒されない状況ではランクを上げたり状態異常にしたりする
+/// 対面が不利な場合は有利なポケモンに交代する
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HeuristicAi;
+
+impl AiStrategy for HeuristicAi {
+    fn select_action(&self, battle: &Battle, side: Side, _rng: &mut dyn RandomSource) -> BattleAction {
+        let trainer = battle.trainer(side);
+        let attacker = trainer.active_pokemon();
+        let target = battle.trainer(side.opposite()).active_pokemon();
+        let (screens, target_screens) = (battle.screens(side), battle.screens(side.opposite()));
+
+        // 次の相手の攻撃で倒される可能性がある場合は積み技や状態異常を使わない
+        let threatened = best_expected_damage(target, attacker, screens) >= attacker.status.current_hp as f64;
+
+        let mut best = (BattleAction::Fight(0), f64::MIN);
+        for (idx, skill) in usable_skills(attacker) {
+            let score = score_skill(attacker, &skill, target, target_screens, threatened);
+            if score > best.1 {
+                best = (BattleAction::Fight(idx), score);
+            }
+        }
+
+        // 倒せる技がなく、対面が大きく改善する場合は交代
+        if best.1 < KO_SCORE * 0.5 {
+            let current = matchup(attacker, target, screens, target_screens);
+            let candidate = (0..trainer.pokemons.len())
+                .filter(|&idx| trainer.can_switch_to(idx))
+                .map(|idx| (idx, matchup(&trainer.pokemons[idx], target, screens, target_screens)))
+                .max_by(|a, b| a.1.total_cmp(&b.1));
+            if let Some((idx, score)) = candidate {
+                if score - current > SWITCH_THRESHOLD {
+                    return BattleAction::Switch(idx);
+                }
+            }
+        }
+
+        best.0
+    }
+
+    fn select_replacement(&self, battle: &Battle, side: Side, _rng: &mut dyn RandomSource) -> Option<usize> {
+        best_matchup_replacement(battle, side)
+    }
+}
+
+/// 相手の場のポケモンに最も有利な交代先を選択
+///
+/// # 引数
+/// * `battle` - バトルの状態
+/// * `side`   - 交代する陣営
+///
+/// # 戻り値
+/// * `Option<usize>` - 交代先の手持ちのインデックス、出せるポケモンがいなければ None
+pub fn best_matchup_replacement(battle: &Battle, side: Side) -> Option<usize> {
+    let trainer = battle.trainer(side);
+    let enemy = battle.trainer(side.opposite()).active_pokemon();
+    let (screens, enemy_screens) = (battle.screens(side), battle.screens(side.opposite()));
+
+    (0..trainer.pokemons.len())
+        .filter(|&idx| trainer.pokemons[idx].status.current_hp > 0)
+        .map(|idx| (idx, matchup(&trainer.pokemons[idx], enemy, screens, enemy_screens)))
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(idx, _)| idx)
+}
+
+/// 技を評価
+///
+/// # 引数
+/// * `attacker`       - 技を使用するポケモン
+/// * `skill`          - 使用する技
+/// * `target`         - 相手のポケモン
+/// * `target_screens` - 相手の場の壁
+/// * `threatened`     - 次の相手の攻撃で倒される可能性があるかどうか
+///
+/// # 戻り値
+/// * `f64` - 評価値（大きいほど良い）
+fn score_skill(attacker: &Pokemon, skill: &Skill, target: &Pokemon, target_screens: &Screens, threatened: bool) -> f64 {
+    let hit = skill.accuracy.min(100) as f64 / 100.0;
+    let current_hp = target.status.current_hp.max(1) as f64;
+
+    if skill.class != SkillType::ChangeStatus {
+        let damage = hit_damage(attacker, skill, target, target_screens) as f64;
+        // 溜めが必要な技はすぐには倒せないため、2 ターン分に割り引く
+        if skill.charge && attacker.volatile.charging.is_none() {
+            return damage.min(current_hp) / current_hp * hit / 2.0;
+        }
+        if damage >= current_hp {
+            // 倒せる場合は先制技を優先
+            return KO_SCORE * hit + skill.priority.max(0) as f64 * 0.1;
+        }
+        return damage / current_hp * hit;
+    }
+
+    if threatened {
+        return 0.0;
+    }
+    let Some(skill_effect) = skill.skill_effect else {
+        return 0.0;
+    };
+
+    let mut score = 0.0;
+    if let Some(status_effect) = skill_effect.status_effect {
+        score += match skill_effect.target {
+            Target::Self_ if status_effect.effect_value > 0 => {
+                let stages = remaining_stages(attacker, status_effect.target, status_effect.effect_value);
+                stages as f64 * 0.25 * stat_relevance(attacker, status_effect.target)
+            }
+            Target::Enemy if status_effect.effect_value < 0 => {
+                remaining_stages(target, status_effect.target, status_effect.effect_value) as f64 * 0.15
+            }
+            _ => 0.0,
+        };
+    }
+    if let Some(ailment) = skill_effect.ailment {
+        if skill_effect.target == Target::Enemy
+            && target.ailment.is_none()
+            && !target.is_immune_to(ailment)
+            && target.type_effectiveness(skill.element) > 0.0
+        {
+            score += ailment_value(ailment, attacker, target);
+        }
+    }
+    if skill_effect.critical_stage > 0 && skill_effect.target == Target::Self_ && attacker.volatile.critical_stage == 0 {
+        // 急所に当たりやすくなる分だけ以降の攻撃が強くなる
+        score += 0.3;
+    }
+
+    score * hit
+}
+
+/// ランク変化で実際に変化する段階数を計算
+///
+/// # 引数
+/// * `pokemon`      - ランクが変化するポケモン
+/// * `status_type`  - 変化するステータス
+/// * `effect_value` - ランクの変化量
+///
+/// # 戻り値
+/// * `i8` - 変化する段階数（絶対値）
+fn remaining_stages(pokemon: &Pokemon, status_type: StatusType, effect_value: i8) -> i8 {
+    let buff = match status_type {
+        StatusType::Atk => pokemon.status.atk.buff,
+        StatusType::Def => pokemon.status.def.buff,
+        StatusType::SpAtk => pokemon.status.sp_atk.buff,
+        StatusType::SpDef => pokemon.status.sp_def.buff,
+        StatusType::Spd => pokemon.status.spd.buff,
+        StatusType::Hp | StatusType::Field => return 0,
+    };
+
+    ((buff + effect_value).clamp(-6, 6) - buff).abs()
+}
+
+/// 上げるステータスがポケモンの戦い方に合っているかを評価
+///
+/// 使う技のないこうげき・とくこうの上昇は評価しない
+///
+/// # 引数
+/// * `pokemon`     - ランクを上げるポケモン
+/// * `status_type` - 上げるステータス
+///
+/// # 戻り値
+/// * `f64` - 重み（0.0 〜 1.0）
+fn stat_relevance(pokemon: &Pokemon, status_type: StatusType) -> f64 {
+    let has_class = |class| pokemon.skills.iter().any(|learned| learned.skill.class == class);
+
+    match status_type {
+        StatusType::Atk if has_class(SkillType::PhysicalAttack) => 1.0,
+        StatusType::SpAtk if has_class(SkillType::SpecialAttack) => 1.0,
+        StatusType::Spd => 0.8,
+        StatusType::Def | StatusType::SpDef => 0.5,
+        _ => 0.0,
+    }
+}
+
+/// 相手を状態異常にする価値を評価
+///
+/// # 引数
+/// * `ailment`  - 状態異常
+/// * `attacker` - 技を使用するポケモン
+/// * `target`   - 相手のポケモン
+///
+/// # 戻り値
+/// * `f64` - 評価値
+fn ailment_value(ailment: StatusAilment, attacker: &Pokemon, target: &Pokemon) -> f64 {
+    let physical = target.skills.iter().any(|learned| learned.skill.class == SkillType::PhysicalAttack);
+
+    match ailment {
+        StatusAilment::Sleep | StatusAilment::Freeze => 0.6,
+        // 相手の方が速い場合はまひで行動順を逆転できる
+        StatusAilment::Paralysis if target.effective_speed() > attacker.effective_speed() => 0.6,
+        StatusAilment::Paralysis => 0.4,
+        StatusAilment::Burn if physical => 0.5,
+        StatusAilment::Burn => 0.3,
+        StatusAilment::BadPoison => 0.45,
+        StatusAilment::Poison => 0.35,
+    }
+}
@@ -0,0 +1,7 @@
+pub mod strategy;
+pub mod evaluate;
+pub mod random;
+pub mod greedy;
+pub mod heuristic;
+pub mod chance;
+pub mod search;
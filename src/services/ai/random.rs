@@ -0,0 +1,23 @@
+use crate::{
+    logic::rand::RandomSource,
+    models::trainer::BattleAction,
+    services::{ai::strategy::AiStrategy, battle::{engine::Battle, event::Side}}
+};
+
+/// 使用できる技の中からランダムに選ぶ戦略
+///
+/// 交代は行わない
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RandomAi;
+
+impl AiStrategy for RandomAi {
+    fn select_action(&self, battle: &Battle, side: Side, rng: &mut dyn RandomSource) -> BattleAction {
+        let fights: Vec<BattleAction> = battle
+            .legal_actions(side)
+            .into_iter()
+            .filter(|action| matches!(action, BattleAction::Fight(_)))
+            .collect();
+
+        fights[rng.random_in_range(0, fights.len() as u64 - 1) as usize]
+    }
+}
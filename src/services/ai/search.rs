@@ -0,0 +1,274 @@
+use std::time::{Duration, Instant};
+
+use crate::{
+    logic::rand::RandomSource,
+    models::trainer::{BattleAction, SearchConfig, Trainer},
+    services::{
+        ai::{
+            chance::ChanceSource,
+            evaluate::matchup,
+            heuristic::{best_matchup_replacement, HeuristicAi},
+            strategy::AiStrategy
+        },
+        battle::{engine::{Battle, TurnActions}, event::Side, trainer::TrainerActions}
+    }
+};
+
+/// 勝敗が決まった局面の評価値
+const WIN_SCORE: f64 = 1000.0;
+
+/// 状態異常のポケモンの評価の減点
+const AILMENT_PENALTY: f64 = 0.2;
+
+/// ランク 1 段階あたりの評価の加点
+const BUFF_SCORE: f64 = 0.05;
+
+/// 場のポケモンの対面の評価の重み
+const MATCHUP_SCORE: f64 = 0.5;
+
+/// バトルの状態を複製して数ターン先まで読む戦略（期待値付きミニマックス）
+///
+/// 両陣営の選択できる行動の組み合わせについて、命中・急所・ダメージの乱数などの結果を確率付きで列挙し、
+/// 相手は自分にとって最も悪い行動を選ぶものとして評価値の期待値が最大になる行動を選ぶ
+/// 1 ターン先から順に深く読み、局面数の上限に達した場合は最後まで読み終えた深さの結果を使用する
+/// 局面数だけで打ち切るため、同じ局面からは機械の速さに関係なく同じ行動を選ぶ
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SearchAi {
+    config: SearchConfig,
+    /// 制限時間でも探索を打ち切るかどうか
+    timed: bool,
+}
+
+impl SearchAi {
+    /// 探索の設定を指定して作成
+    ///
+    /// 制限時間は使用せず、局面数の上限だけで探索を打ち切る
+    ///
+    /// # 引数
+    /// * `config` - 探索 AI の設定
+    pub fn new(config: SearchConfig) -> Self {
+        SearchAi { config, timed: false }
+    }
+
+    /// 制限時間でも探索を打ち切る探索 AI を作成
+    ///
+    /// 人が相手をするバトルで待たせすぎないために使用する
+    /// 選ぶ行動が機械の速さや負荷で変わるため、シード値で再現したいバトルには使用しない
+    ///
+    /// # 引数
+    /// * `config` - 探索 AI の設定
+    pub fn timed(config: SearchConfig) -> Self {
+        SearchAi { config, timed: true }
+    }
+}
+
+impl AiStrategy for SearchAi {
+    fn select_action(&self, battle: &Battle, side: Side, rng: &mut dyn RandomSource) -> BattleAction {
+        let mut search = Search::new(side, &self.config, self.timed);
+
+        let mut best = None;
+        for depth in 1..=self.config.depth {
+            match search.root(battle, depth) {
+                Some(action) => best = Some(action),
+                None => break,
+            }
+        }
+
+        // 1 ターン先も読み切れなかった場合は評価関数だけで選ぶ
+        best.unwrap_or_else(|| HeuristicAi.select_action(battle, side, rng))
+    }
+
+    fn select_replacement(&self, battle: &Battle, side: Side, _rng: &mut dyn RandomSource) -> Option<usize> {
+        best_matchup_replacement(battle, side)
+    }
+}
+
+/// 1 回の行動選択の探索状態
+struct Search {
+    side: Side,
+    nodes: u32,
+    max_nodes: u32,
+    /// 制限時間を使用しない場合は None
+    deadline: Option<Instant>,
+}
+
+impl Search {
+    fn new(side: Side, config: &SearchConfig, timed: bool) -> Self {
+        Search {
+            side,
+            nodes: 0,
+            max_nodes: config.max_nodes,
+            deadline: timed.then(|| Instant::now() + Duration::from_millis(config.time_limit_ms as u64)),
+        }
+    }
+
+    /// 局面数か制限時間の上限に達したかを判定
+    fn exhausted(&self) -> bool {
+        self.nodes >= self.max_nodes || self.deadline.is_some_and(|deadline| Instant::now() >= deadline)
+    }
+
+    /// 最も評価値の高い行動を探索
+    ///
+    /// # 引数
+    /// * `battle` - 現在の局面
+    /// * `depth`  - 先読みするターン数
+    ///
+    /// # 戻り値
+    /// * `Option<BattleAction>` - 最善の行動、上限に達して読み切れなかった場合は None
+    fn root(&mut self, battle: &Battle, depth: u8) -> Option<BattleAction> {
+        let mut best = (None, f64::MIN);
+        for action in battle.legal_actions(self.side) {
+            let value = self.min_value(battle, action, depth, best.1)?;
+            if value > best.1 {
+                best = (Some(action), value);
+            }
+        }
+
+        best.0
+    }
+
+    /// 自分の行動を固定したとき、相手が最も悪い行動を選んだ場合の評価値
+    ///
+    /// # 引数
+    /// * `battle` - 現在の局面
+    /// * `action` - 自分の行動
+    /// * `depth`  - 残りの先読みするターン数
+    /// * `alpha`  - 既に見つけた自分の行動の評価値（これを下回ったら打ち切る）
+    fn min_value(&mut self, battle: &Battle, action: BattleAction, depth: u8, alpha: f64) -> Option<f64> {
+        let mut worst = f64::MAX;
+        for enemy_action in battle.legal_actions(self.side.opposite()) {
+            let actions = match self.side {
+                Side::Player => TurnActions { player: action, opponent: enemy_action },
+                Side::Opponent => TurnActions { player: enemy_action, opponent: action },
+            };
+            worst = worst.min(self.chance_value(battle, actions, depth)?);
+            if worst <= alpha {
+                break;
+            }
+        }
+
+        Some(worst)
+    }
+
+    /// 両陣営の行動を固定したとき、起こりうる結果の評価値の期待値
+    fn chance_value(&mut self, battle: &Battle, actions: TurnActions, depth: u8) -> Option<f64> {
+        let mut total = 0.0;
+        let mut prefixes = vec![Vec::new()];
+        while let Some(prefix) = prefixes.pop() {
+            if self.exhausted() {
+                return None;
+            }
+            self.nodes += 1;
+
+            let mut source = ChanceSource::new(prefix);
+            let mut next = battle.clone();
+            next.submit_actions_with(actions, &mut source);
+            prefixes.extend(source.siblings());
+
+            // ひんしになったポケモンは最も有利なポケモンと入れ替える
+            for side in [Side::Player, Side::Opponent] {
+                if next.needs_replacement(side) {
+                    if let Some(idx) = best_matchup_replacement(&next, side) {
+                        next.submit_replacement(side, idx);
+                    }
+                }
+            }
+
+            total += source.probability() * self.value(&next, depth - 1)?;
+        }
+
+        Some(total)
+    }
+
+    /// 局面の評価値
+    fn value(&mut self, battle: &Battle, depth: u8) -> Option<f64> {
+        if depth == 0 || battle.is_over() {
+            return Some(evaluate(battle, self.side));
+        }
+
+        let mut best = f64::MIN;
+        for action in battle.legal_actions(self.side) {
+            best = best.max(self.min_value(battle, action, depth, best)?);
+        }
+
+        Some(best)
+    }
+}
+
+/// 局面を評価
+///
+/// 戦えるポケモンの数と残りHPの割合、状態異常、場のポケモンのランク変化と対面の有利さから、相手との差を計算する
+///
+/// # 引数
+/// * `battle` - 評価する局面
+/// * `side`   - 評価する陣営
+///
+/// # 戻り値
+/// * `f64` - 評価値（大きいほど有利）
+pub fn evaluate(battle: &Battle, side: Side) -> f64 {
+    match battle.winner() {
+        Some(winner) if winner == side => return WIN_SCORE,
+        Some(_) => return -WIN_SCORE,
+        None => {}
+    }
+
+    let pokemon = battle.trainer(side).active_pokemon();
+    let enemy = battle.trainer(side.opposite()).active_pokemon();
+    party_value(battle.trainer(side)) - party_value(battle.trainer(side.opposite())) + MATCHUP_SCORE * matchup(pokemon, enemy, battle.screens(side), battle.screens(side.opposite()))
+}
+
+/// トレーナーの手持ちを評価
+fn party_value(trainer: &Trainer) -> f64 {
+    let mut value = 0.0;
+    for (idx, pokemon) in trainer.pokemons.iter().enumerate() {
+        if pokemon.status.current_hp == 0 {
+            continue;
+        }
+
+        value += 1.0 + pokemon.status.current_hp as f64 / pokemon.status.hp.value.max(1) as f64;
+        if pokemon.ailment.is_some() {
+            value -= AILMENT_PENALTY;
+        }
+        // ランク変化は交代で解除されるため場のポケモンのみ評価
+        if idx == trainer.active_idx {
+            let status = &pokemon.status;
+            let buffs = [status.atk.buff, status.def.buff, status.sp_atk.buff, status.sp_def.buff, status.spd.buff];
+            value += buffs.iter().map(|&buff| buff as f64).sum::<f64>() * BUFF_SCORE;
+        }
+    }
+
+    value
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{file::json::fixture::trainer, logic::rand::XorShift128};
+
+    /// リポジトリの JSON データからバトルを作成
+    fn battle(seed: u64) -> Battle {
+        Battle::new(trainer("wataru"), trainer("daigo"), false, XorShift128::from_seed(seed))
+    }
+
+    /// 制限時間を使用しない探索は、制限時間の設定に関係なく同じ行動を選ぶ
+    #[test]
+    fn untimed_search_ignores_time_limit() {
+        let battle = battle(9);
+        let config = SearchConfig { depth: 2, max_nodes: 20_000, time_limit_ms: 0 };
+        let generous = SearchConfig { time_limit_ms: u32::MAX, ..config };
+
+        let action = SearchAi::new(config).select_action(&battle, Side::Player, &mut battle.rng.clone());
+        assert_eq!(action, SearchAi::new(generous).select_action(&battle, Side::Player, &mut battle.rng.clone()));
+        assert_eq!(action, SearchAi::new(config).select_action(&battle, Side::Player, &mut battle.rng.clone()));
+    }
+
+    /// 制限時間を使用する探索だけが制限時間で打ち切られる
+    #[test]
+    fn only_timed_search_has_a_deadline() {
+        let config = SearchConfig { depth: 2, max_nodes: u32::MAX, time_limit_ms: 0 };
+
+        assert!(!Search::new(Side::Player, &config, false).exhausted());
+        assert!(Search::new(Side::Player, &config, true).exhausted());
+    }
+}
@@ -0,0 +1,110 @@
+use crate::{
+    logic::rand::RandomSource,
+    models::trainer::{AiLevel, BattleAction, Trainer},
+    services::{
+        ai::{greedy::GreedyAi, heuristic::HeuristicAi, random::RandomAi, search::SearchAi},
+        battle::{engine::Battle, event::Side, trainer::TrainerActions}
+    }
+};
+
+/// コンピュータが行動を決める戦略
+///
+/// バトルの状態を読み取るだけで変更せず、乱数はバトルの乱数とは別の AI 用の乱数生成器から受け取る
+pub trait AiStrategy {
+    /// ターンの行動を選択
+    ///
+    /// # 引数
+    /// * `battle` - バトルの状態
+    /// * `side`   - 行動する陣営
+    /// * `rng`    - AI 用の乱数の取得元
+    ///
+    /// # 戻り値
+    /// * `BattleAction` - `Battle::legal_actions` に含まれる行動
+    fn select_action(&self, battle: &Battle, side: Side, rng: &mut dyn RandomSource) -> BattleAction;
+
+    /// ひんしになったポケモンの交代先を選択
+    ///
+    /// 既定では手持ちの並び順で次のポケモンを選ぶ
+    ///
+    /// # 引数
+    /// * `battle` - バトルの状態
+    /// * `side`   - 交代する陣営
+    /// * `rng`    - AI 用の乱数の取得元
+    ///
+    /// # 戻り値
+    /// * `Option<usize>` - 交代先の手持ちのインデックス、出せるポケモンがいなければ None
+    fn select_replacement(&self, battle: &Battle, side: Side, _rng: &mut dyn RandomSource) -> Option<usize> {
+        battle.trainer(side).next_usable_pokemon()
+    }
+}
+
+/// トレーナーの AI の強さに対応する戦略を作成
+///
+/// 探索 AI は局面数の上限だけで探索を打ち切るため、同じシード値のバトルはどの機械でも同じ結果になる
+///
+/// # 引数
+/// * `trainer` - トレーナー
+///
+/// # 戻り値
+/// * `Box<dyn AiStrategy>` - 戦略
+pub fn strategy_for(trainer: &Trainer) -> Box<dyn AiStrategy> {
+    match trainer.ai {
+        AiLevel::Random => Box::new(RandomAi),
+        AiLevel::Greedy => Box::new(GreedyAi),
+        AiLevel::Heuristic => Box::new(HeuristicAi),
+        AiLevel::Expectiminimax => Box::new(SearchAi::new(trainer.search)),
+    }
+}
+
+/// 人が相手をするバトル用に、トレーナーの AI の強さに対応する戦略を作成
+///
+/// 探索 AI は局面数に加えて制限時間でも探索を打ち切り、思考で待たせすぎないようにする
+/// 制限時間に達すると機械の速さで選ぶ行動が変わるため、シミュレーションなどシード値で再現したいバトルには `strategy_for` を使用する
+///
+/// # 引数
+/// * `trainer` - トレーナー
+///
+/// # 戻り値
+/// * `Box<dyn AiStrategy>` - 戦略
+pub fn interactive_strategy_for(trainer: &Trainer) -> Box<dyn AiStrategy> {
+    match trainer.ai {
+        AiLevel::Expectiminimax => Box::new(SearchAi::timed(trainer.search)),
+        _ => strategy_for(trainer),
+    }
+}
+
+/// 戦略に AI 用の乱数生成器を渡してターンの行動を選択
+///
+/// バトルの乱数生成器は進めないため、記録した行動だけでリプレイを再現できる
+///
+/// # 引数
+/// * `battle`   - バトルの状態
+/// * `side`     - 行動する陣営
+/// * `strategy` - 戦略
+///
+/// # 戻り値
+/// * `BattleAction` - 選択した行動
+pub fn decide_action(battle: &mut Battle, side: Side, strategy: &dyn AiStrategy) -> BattleAction {
+    let mut rng = battle.ai_rng.clone();
+    let action = strategy.select_action(battle, side, &mut rng);
+    battle.ai_rng = rng;
+
+    action
+}
+
+/// 戦略に AI 用の乱数生成器を渡してひんしになったポケモンの交代先を選択
+///
+/// # 引数
+/// * `battle`   - バトルの状態
+/// * `side`     - 交代する陣営
+/// * `strategy` - 戦略
+///
+/// # 戻り値
+/// * `Option<usize>` - 交代先の手持ちのインデックス
+pub fn decide_replacement(battle: &mut Battle, side: Side, strategy: &dyn AiStrategy) -> Option<usize> {
+    let mut rng = battle.ai_rng.clone();
+    let idx = strategy.select_replacement(battle, side, &mut rng);
+    battle.ai_rng = rng;
+
+    idx
+}
@@ -0,0 +1,144 @@
+use crate::{
+    logic::rand::RandomSource,
+    models::pokemon::{ElementType, Pokemon, StatusAilment},
+    services::battle::pokemon::PokemonActions
+};
+
+#[allow(dead_code)]
+pub trait AilmentActions {
+    fn is_immune_to(&self, ailment: StatusAilment) -> bool;
+    fn inflict_ailment(&mut self, ailment: StatusAilment, rng: &mut dyn RandomSource) -> bool;
+    fn cure_ailment(&mut self) -> Option<StatusAilment>;
+    fn inflict_confusion(&mut self, rng: &mut dyn RandomSource) -> bool;
+    fn compute_confusion_damage(&self, rng: &mut dyn RandomSource) -> u16;
+    fn apply_residual_damage(&mut self) -> Option<u16>;
+}
+
+impl AilmentActions for Pokemon {
+    /// タイプによって状態異常を無効化するかを判定
+    ///
+    /// # 引数
+    /// * `ailment` - 状態異常
+    ///
+    /// # 戻り値
+    /// * `bool` - 無効化するかどうか
+    fn is_immune_to(&self, ailment: StatusAilment) -> bool {
+        let immune_types: &[ElementType] = match ailment {
+            StatusAilment::Burn => &[ElementType::Fire],
+            StatusAilment::Poison | StatusAilment::BadPoison => &[ElementType::Poison, ElementType::Steel],
+            StatusAilment::Paralysis => &[ElementType::Electric],
+            StatusAilment::Sleep => &[],
+            StatusAilment::Freeze => &[ElementType::Ice],
+        };
+
+        self.species.element.iter().any(|t| immune_types.contains(t))
+    }
+
+    /// 状態異常を付与
+    ///
+    /// すでに状態異常の場合、タイプで無効化される場合、ひんしの場合は付与しない
+    ///
+    /// # 引数
+    /// * `ailment` - 付与する状態異常
+    /// * `rng`     - 乱数生成器
+    ///
+    /// # 戻り値
+    /// * `bool` - 付与できたかどうか
+    fn inflict_ailment(&mut self, ailment: StatusAilment, rng: &mut dyn RandomSource) -> bool {
+        if self.ailment.is_some() || self.status.current_hp == 0 || self.is_immune_to(ailment) {
+            return false;
+        }
+
+        self.ailment = Some(ailment);
+        self.ailment_turns = match ailment {
+            // ねむりは 1 〜 3 ターン
+            StatusAilment::Sleep => rng.random_in_range(1, 3) as u8,
+            // もうどくは経過ターン数でダメージが増える
+            StatusAilment::BadPoison => 1,
+            _ => 0,
+        };
+
+        true
+    }
+
+    /// 状態異常を回復
+    ///
+    /// # 戻り値
+    /// * `Option<StatusAilment>` - 回復した状態異常
+    fn cure_ailment(&mut self) -> Option<StatusAilment> {
+        self.ailment_turns = 0;
+        self.ailment.take()
+    }
+
+    /// こんらん状態にする
+    ///
+    /// # 引数
+    /// * `rng` - 乱数生成器
+    ///
+    /// # 戻り値
+    /// * `bool` - こんらん状態にできたかどうか
+    fn inflict_confusion(&mut self, rng: &mut dyn RandomSource) -> bool {
+        if self.volatile.confusion_turns > 0 || self.status.current_hp == 0 {
+            return false;
+        }
+
+        // 行動時に 1 ずつ減らし、0 になったら解ける（1 〜 4 ターン）
+        self.volatile.confusion_turns = rng.random_in_range(2, 5) as u8;
+
+        true
+    }
+
+    /// こんらんで自分を攻撃したときのダメージを計算
+    ///
+    /// 威力 40 のタイプなし物理攻撃として扱い、急所・タイプ一致・タイプ相性は適用しない
+    ///
+    /// # 引数
+    /// * `rng` - 乱数生成器
+    ///
+    /// # 戻り値
+    /// * `u16` - 自分が受けるダメージ
+    fn compute_confusion_damage(&self, rng: &mut dyn RandomSource) -> u16 {
+        let mut dmg = (self.level as f32 * 2.0 / 5.0 + 2.0).floor();
+
+        let atk = self.compute_status_buff(&self.status.atk) as f32;
+        let def = self.compute_status_buff(&self.status.def) as f32;
+
+        dmg = (dmg * 40.0 * atk / def).floor();
+        dmg = (dmg / 50.0 + 2.0).floor();
+
+        // 乱数（85% 〜 100%）
+        let rand = rng.random_in_range(85, 100) as f32;
+        dmg = (dmg * rand / 100.0).floor();
+
+        dmg.max(1.0) as u16
+    }
+
+    /// ターン終了時の状態異常によるダメージを適用
+    ///
+    /// やけど: 最大HPの 1/16、どく: 最大HPの 1/8、もうどく: 最大HPの n/16（n は経過ターン数）
+    ///
+    /// # 戻り値
+    /// * `Option<u16>` - 受けたダメージ、ダメージを受けない場合は None
+    fn apply_residual_damage(&mut self) -> Option<u16> {
+        if self.status.current_hp == 0 {
+            return None;
+        }
+
+        let max_hp = self.status.hp.value;
+        let damage = match self.ailment {
+            Some(StatusAilment::Burn) => max_hp / 16,
+            Some(StatusAilment::Poison) => max_hp / 8,
+            Some(StatusAilment::BadPoison) => {
+                let damage = max_hp * self.ailment_turns.min(15) as u16 / 16;
+                self.ailment_turns = self.ailment_turns.saturating_add(1);
+                damage
+            }
+            _ => return None,
+        }
+        .max(1);
+
+        self.status.current_hp = self.status.current_hp.saturating_sub(damage);
+
+        Some(damage)
+    }
+}
@@ -0,0 +1,137 @@
+use crate::{
+    logic::rand::RandomSource,
+    models::{item::Ball, pokemon::{Pokemon, StatusAilment}}
+};
+
+/// ボールが揺れるかの判定の回数（すべて成功すると捕まえられる）
+pub const SHAKE_CHECKS: u8 = 4;
+
+/// 捕獲値の上限（上限に達すると判定なしで捕まえられる）
+const MAX_CATCH_VALUE: u64 = 255;
+
+pub trait CaptureActions {
+    fn catch_value(&self, ball: Ball) -> u64;
+    fn try_capture(&self, ball: Ball, rng: &mut dyn RandomSource) -> u8;
+}
+
+impl CaptureActions for Pokemon {
+    /// 捕獲値を計算
+    ///
+    /// (3 × 最大HP − 2 × 現在のHP) × 捕まえやすさ × ボール補正 ÷ (3 × 最大HP) × 状態異常補正
+    /// 状態異常補正はねむり・こおりが 2 倍、まひ・やけど・どくが 1.5 倍
+    ///
+    /// # 引数
+    /// * `ball` - 投げたボール
+    ///
+    /// # 戻り値
+    /// * `u64` - 捕獲値（1 〜 255）
+    fn catch_value(&self, ball: Ball) -> u64 {
+        if ball.guaranteed {
+            return MAX_CATCH_VALUE;
+        }
+
+        // ボール補正と状態異常補正は 10 倍した値で計算する
+        let ailment_modifier: u64 = match self.ailment {
+            Some(StatusAilment::Sleep | StatusAilment::Freeze) => 20,
+            Some(_) => 15,
+            None => 10,
+        };
+        let max_hp = self.status.hp.value.max(1) as u64;
+        let current_hp = self.status.current_hp.min(self.status.hp.value) as u64;
+        let value = (3 * max_hp - 2 * current_hp) * self.species.catch_rate as u64 * ball.modifier as u64 * ailment_modifier
+            / (3 * max_hp * 100);
+
+        value.clamp(1, MAX_CATCH_VALUE)
+    }
+
+    /// ボールが揺れるかを判定
+    ///
+    /// 揺れる確率は捕獲値から決まり、判定は最初に失敗した時点で終わる
+    ///
+    /// # 引数
+    /// * `ball` - 投げたボール
+    /// * `rng`  - 乱数の取得元
+    ///
+    /// # 戻り値
+    /// * `u8` - 成功した判定の回数（`SHAKE_CHECKS` の場合は捕まえた）
+    fn try_capture(&self, ball: Ball, rng: &mut dyn RandomSource) -> u8 {
+        let value = self.catch_value(ball);
+        if value >= MAX_CATCH_VALUE {
+            return SHAKE_CHECKS;
+        }
+
+        // 1 回の判定で揺れる確率は 65536 分の threshold
+        let threshold = (1_048_560.0 / (16_711_680.0 / value as f64).sqrt().sqrt()) as u64;
+        (0..SHAKE_CHECKS)
+            .take_while(|_| rng.random_in_range(0, 65_535) < threshold)
+            .count() as u8
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::{file::json::fixture::trainer, logic::rand::XorShift128};
+
+    /// モンスターボール
+    const MONSTER_BALL: Ball = Ball { modifier: 10, guaranteed: false };
+
+    /// 捕まえやすさ 45 のポケモンを作成
+    fn target() -> Pokemon {
+        let mut pokemon = trainer("wataru").pokemons[0].clone();
+        let mut species = (*pokemon.species).clone();
+        species.catch_rate = 45;
+        pokemon.species = Arc::new(species);
+        pokemon.ailment = None;
+        pokemon.status.current_hp = pokemon.status.hp.value;
+
+        pokemon
+    }
+
+    /// 1000 回投げて捕まえた回数
+    fn catches(pokemon: &Pokemon, ball: Ball) -> usize {
+        let mut rng = XorShift128::from_seed(1);
+        (0..1000).filter(|_| pokemon.try_capture(ball, &mut rng) == SHAKE_CHECKS).count()
+    }
+
+    /// 必ず捕まえられるボールは乱数によらず捕まえる
+    #[test]
+    fn guaranteed_ball_always_catches() {
+        let pokemon = target();
+        let ball = Ball { modifier: 10, guaranteed: true };
+
+        assert_eq!(pokemon.catch_value(ball), MAX_CATCH_VALUE);
+        assert_eq!(catches(&pokemon, ball), 1000);
+    }
+
+    /// HPが減るほど捕獲値と捕まえた回数が増える
+    #[test]
+    fn lower_hp_raises_catch_odds() {
+        let full = target();
+        let mut half = target();
+        half.status.current_hp = half.status.hp.value / 2;
+        let mut one = target();
+        one.status.current_hp = 1;
+
+        assert_eq!(full.catch_value(MONSTER_BALL), 15);
+        assert!(full.catch_value(MONSTER_BALL) < half.catch_value(MONSTER_BALL));
+        assert!(half.catch_value(MONSTER_BALL) < one.catch_value(MONSTER_BALL));
+        assert!(catches(&full, MONSTER_BALL) < catches(&one, MONSTER_BALL));
+    }
+
+    /// 状態異常のポケモンは捕まえやすい（ねむり・こおりは 2 倍、それ以外は 1.5 倍）
+    #[test]
+    fn status_conditions_raise_catch_odds() {
+        let healthy = target();
+        let mut paralyzed = target();
+        paralyzed.ailment = Some(StatusAilment::Paralysis);
+        let mut asleep = target();
+        asleep.ailment = Some(StatusAilment::Sleep);
+
+        assert_eq!(paralyzed.catch_value(MONSTER_BALL), healthy.catch_value(MONSTER_BALL) * 3 / 2);
+        assert_eq!(asleep.catch_value(MONSTER_BALL), healthy.catch_value(MONSTER_BALL) * 2);
+        assert!(catches(&healthy, MONSTER_BALL) < catches(&asleep, MONSTER_BALL));
+    }
+}
@@ -1,4 +1,93 @@
-use crate::models::pokemon::Pokemon;
+use crate::models::pokemon::{ElementType, Pokemon};
+
+/// タイプ相性の区分を表す列挙型
+///
+/// 倍率からメッセージを決定するために使用
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Effectiveness {
+    NoEffect,
+    NotVeryEffective,
+    Normal,
+    SuperEffective,
+}
+
+impl Effectiveness {
+    /// 倍率から相性の区分を取得
+    ///
+    /// # 引数
+    /// * `multiplier` - タイプ相性の倍率
+    ///
+    /// # 戻り値
+    /// * `Effectiveness` - 相性の区分
+    pub fn from_multiplier(multiplier: f32) -> Self {
+        if multiplier == 0.0 {
+            Effectiveness::NoEffect
+        } else if multiplier < 1.0 {
+            Effectiveness::NotVeryEffective
+        } else if multiplier > 1.0 {
+            Effectiveness::SuperEffective
+        } else {
+            Effectiveness::Normal
+        }
+    }
+
+    /// 相性に応じたメッセージを取得
+    ///
+    /// # 戻り値
+    /// * `Option<&'static str>` - 表示するメッセージ（等倍の場合は None）
+    pub fn message(&self) -> Option<&'static str> {
+        match self {
+            Effectiveness::NoEffect => Some("こうかが ないようだ"),
+            Effectiveness::NotVeryEffective => Some("こうかは いまひとつのようだ"),
+            Effectiveness::Normal => None,
+            Effectiveness::SuperEffective => Some("こうかは ばつぐんだ！"),
+        }
+    }
+}
+
+impl ElementType {
+    /// 攻撃側のタイプが防御側の単タイプに与える倍率
+    ///
+    /// # 引数
+    /// * `defender` - 防御側のタイプ
+    ///
+    /// # 戻り値
+    /// * `f32` - 倍率（0.0、0.5、1.0、2.0）
+    pub fn effectiveness_against(&self, defender: ElementType) -> f32 {
+        use ElementType::*;
+
+        let (super_effective, not_very_effective, no_effect): (&[ElementType], &[ElementType], &[ElementType]) = match self {
+            Normal => (&[], &[Rock, Steel], &[Ghost]),
+            Fire => (&[Grass, Ice, Bug, Steel], &[Fire, Water, Rock, Dragon], &[]),
+            Water => (&[Fire, Ground, Rock], &[Water, Grass, Dragon], &[]),
+            Electric => (&[Water, Flying], &[Electric, Grass, Dragon], &[Ground]),
+            Grass => (&[Water, Ground, Rock], &[Fire, Grass, Poison, Flying, Bug, Dragon, Steel], &[]),
+            Ice => (&[Grass, Ground, Flying, Dragon], &[Fire, Water, Ice, Steel], &[]),
+            Fighting => (&[Normal, Ice, Rock, Dark, Steel], &[Poison, Flying, Psychic, Bug, Fairy], &[Ghost]),
+            Poison => (&[Grass, Fairy], &[Poison, Ground, Rock, Ghost], &[Steel]),
+            Ground => (&[Fire, Electric, Poison, Rock, Steel], &[Grass, Bug], &[Flying]),
+            Flying => (&[Grass, Fighting, Bug], &[Electric, Rock, Steel], &[]),
+            Psychic => (&[Fighting, Poison], &[Psychic, Steel], &[Dark]),
+            Bug => (&[Grass, Psychic, Dark], &[Fire, Fighting, Poison, Flying, Ghost, Steel, Fairy], &[]),
+            Rock => (&[Fire, Ice, Flying, Bug], &[Fighting, Ground, Steel], &[]),
+            Ghost => (&[Psychic, Ghost], &[Dark], &[Normal]),
+            Dragon => (&[Dragon], &[Steel], &[Fairy]),
+            Dark => (&[Psychic, Ghost], &[Fighting, Dark, Fairy], &[]),
+            Steel => (&[Ice, Rock, Fairy], &[Fire, Water, Electric, Steel], &[]),
+            Fairy => (&[Fighting, Dragon, Dark], &[Fire, Poison, Steel], &[]),
+        };
+
+        if no_effect.contains(&defender) {
+            0.0
+        } else if super_effective.contains(&defender) {
+            2.0
+        } else if not_very_effective.contains(&defender) {
+            0.5
+        } else {
+            1.0
+        }
+    }
+}
 
 #[allow(dead_code)]
 impl Pokemon {
@@ -10,9 +99,98 @@ impl Pokemon {
     /// # 戻り値
     /// * `Vec<&'static str>` - ポケモンのタイプを格納したベクター
     pub fn convert_to_text(&self) -> Vec<&'static str> {
-        self.element
+        self.species.element
             .iter()
             .map(|t| t.as_str())
             .collect()
     }
-}
\ No newline at end of file
+
+    /// 技のタイプに対するタイプ相性の倍率を計算
+    ///
+    /// 複合タイプの場合はそれぞれの倍率を掛け合わせる
+    ///
+    /// # 引数
+    /// * `skill_element` - 技のタイプ
+    ///
+    /// # 戻り値
+    /// * `f32` - 倍率（0.0、0.25、0.5、1.0、2.0、4.0）
+    pub fn type_effectiveness(&self, skill_element: ElementType) -> f32 {
+        self.species.element
+            .iter()
+            .map(|&t| skill_element.effectiveness_against(t))
+            .product()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::file::json::fixture::trainer;
+
+    /// 指定したタイプのポケモンを作成
+    fn pokemon_of(element: &[ElementType]) -> Pokemon {
+        let mut pokemon = trainer("wataru").pokemons[0].clone();
+        let mut species = (*pokemon.species).clone();
+        species.element = element.to_vec();
+        pokemon.species = Arc::new(species);
+
+        pokemon
+    }
+
+    /// 単タイプに対する倍率
+    #[test]
+    fn single_type_multipliers() {
+        use ElementType::*;
+
+        assert_eq!(Water.effectiveness_against(Fire), 2.0);
+        assert_eq!(Fire.effectiveness_against(Water), 0.5);
+        assert_eq!(Normal.effectiveness_against(Normal), 1.0);
+        assert_eq!(Fairy.effectiveness_against(Dragon), 2.0);
+        assert_eq!(Steel.effectiveness_against(Fairy), 2.0);
+    }
+
+    /// 効果がないタイプの組み合わせ
+    #[test]
+    fn immunities() {
+        use ElementType::*;
+
+        for (attacker, defender) in [
+            (Normal, Ghost),
+            (Fighting, Ghost),
+            (Ghost, Normal),
+            (Electric, Ground),
+            (Ground, Flying),
+            (Psychic, Dark),
+            (Poison, Steel),
+            (Dragon, Fairy),
+        ] {
+            assert_eq!(attacker.effectiveness_against(defender), 0.0, "{:?} → {:?}", attacker, defender);
+        }
+    }
+
+    /// 複合タイプでは倍率を掛け合わせる
+    #[test]
+    fn dual_types_multiply() {
+        use ElementType::*;
+
+        // こおり → ドラゴン・ひこう（カイリュー）
+        assert_eq!(pokemon_of(&[Dragon, Flying]).type_effectiveness(Ice), 4.0);
+        // かくとう → どく・ひこう
+        assert_eq!(pokemon_of(&[Poison, Flying]).type_effectiveness(Fighting), 0.25);
+        // でんき → みず・じめん（じめんで無効）
+        assert_eq!(pokemon_of(&[Water, Ground]).type_effectiveness(Electric), 0.0);
+        // ほのお → くさ・みず（2.0 × 0.5）
+        assert_eq!(pokemon_of(&[Grass, Water]).type_effectiveness(Fire), 1.0);
+    }
+
+    /// 倍率から相性の区分を決める
+    #[test]
+    fn effectiveness_from_multiplier() {
+        assert_eq!(Effectiveness::from_multiplier(0.0), Effectiveness::NoEffect);
+        assert_eq!(Effectiveness::from_multiplier(0.25), Effectiveness::NotVeryEffective);
+        assert_eq!(Effectiveness::from_multiplier(1.0), Effectiveness::Normal);
+        assert_eq!(Effectiveness::from_multiplier(4.0), Effectiveness::SuperEffective);
+    }
+}
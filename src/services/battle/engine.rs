@@ -0,0 +1,1073 @@
+use std::{collections::BTreeSet, sync::Arc};
+
+use crate::{
+    logic::rand::{RandomSource, XorShift128},
+    models::{
+        item::Ball,
+        pokemon::{EffectKind, ElementType, Pokemon, Screen, Skill, SkillType, StatusAilment, Target},
+        trainer::{BattleAction, Trainer}
+    },
+    services::battle::{
+        ailment::AilmentActions,
+        capture::{CaptureActions, SHAKE_CHECKS},
+        element::Effectiveness,
+        event::{BattleEvent, CantMoveReason, Side},
+        experience::ExperienceActions,
+        learnset::LearnsetActions,
+        pokemon::{PokemonActions, MAX_DAMAGE_ROLL, MIN_DAMAGE_ROLL},
+        trainer::TrainerActions
+    }
+};
+
+/// 壁が張られているターン数
+pub const SCREEN_TURNS: u8 = 5;
+
+/// 陣営の場に張られている壁の残りターン数
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Screens {
+    pub reflect: u8,
+    pub light_screen: u8,
+}
+
+impl Screens {
+    /// 壁の残りターン数を可変参照で取得
+    ///
+    /// # 引数
+    /// * `screen` - 壁の種類
+    pub fn turns_mut(&mut self, screen: Screen) -> &mut u8 {
+        match screen {
+            Screen::Reflect => &mut self.reflect,
+            Screen::LightScreen => &mut self.light_screen,
+        }
+    }
+
+    /// 技のダメージを半分にする壁が張られているかを判定
+    ///
+    /// # 引数
+    /// * `class` - 技の種類
+    pub fn reduces(&self, class: SkillType) -> bool {
+        match class {
+            SkillType::PhysicalAttack | SkillType::Struggle => self.reflect > 0,
+            SkillType::SpecialAttack => self.light_screen > 0,
+            _ => false,
+        }
+    }
+}
+
+/// 1ターンに両陣営が選択した行動
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TurnActions {
+    pub player: BattleAction,
+    pub opponent: BattleAction,
+}
+
+/// レベルアップで覚えようとしている技
+///
+/// 技を 4 つ覚えているため、どの技を忘れるかの選択を待っている
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendingSkill {
+    /// 自分の手持ちのインデックス
+    pub idx: usize,
+    pub skill: Arc<Skill>,
+}
+
+/// バトルの状態を表す構造体
+///
+/// 表示や入力には依存せず、行動を受け取って状態を進め、発生したイベントを返す
+/// 乱数はバトルが所有する乱数生成器から取得するため、同じシード値と行動で同じ結果になる
+/// コンピュータの AI は別の乱数生成器を使用するため、AI の選択でダメージの乱数などがずれることはない
+#[derive(Debug, Clone)]
+pub struct Battle {
+    pub player: Trainer,
+    pub opponent: Trainer,
+    pub wild: bool,
+    /// やせいのポケモンを捕まえたかどうか（捕まえるとバトルが終了する）
+    pub caught: bool,
+    /// 自分の陣営に張られている壁
+    pub player_screens: Screens,
+    /// 相手の陣営に張られている壁
+    pub opponent_screens: Screens,
+    pub turn: u32,
+    pub rng: XorShift128,
+    /// コンピュータの AI が使用する乱数生成器（バトル開始時の `rng` から作成）
+    pub ai_rng: XorShift128,
+    /// 自分の陣営が相手のポケモンを倒したときに経験値をもらうかどうか（既定では無効）
+    pub exp_gain: bool,
+    /// 忘れる技の選択を待っている技（`resolve_pending_skill` で先頭から処理する）
+    pub pending_skills: Vec<PendingSkill>,
+    /// 相手の手持ちのインデックスごとに、そのポケモンと場で向き合った自分の手持ちのインデックス
+    participants: Vec<BTreeSet<usize>>,
+}
+
+impl Battle {
+    /// バトルを作成
+    ///
+    /// # 引数
+    /// * `player`   - 自分のトレーナー
+    /// * `opponent` - 相手のトレーナー（やせいのポケモンの場合は 1 匹だけ所持）
+    /// * `wild`     - やせいのポケモンとのバトルかどうか
+    /// * `rng`      - バトルで使用する乱数生成器
+    pub fn new(player: Trainer, opponent: Trainer, wild: bool, rng: XorShift128) -> Self {
+        let participants = vec![BTreeSet::new(); opponent.pokemons.len()];
+        let mut battle = Battle {
+            player,
+            opponent,
+            wild,
+            caught: false,
+            player_screens: Screens::default(),
+            opponent_screens: Screens::default(),
+            turn: 0,
+            ai_rng: rng.fork(),
+            rng,
+            exp_gain: false,
+            pending_skills: Vec::new(),
+            participants,
+        };
+        battle.record_participants();
+
+        battle
+    }
+
+    /// 陣営のトレーナーを取得
+    pub fn trainer(&self, side: Side) -> &Trainer {
+        match side {
+            Side::Player => &self.player,
+            Side::Opponent => &self.opponent,
+        }
+    }
+
+    /// 陣営のトレーナーを可変参照で取得
+    fn trainer_mut(&mut self, side: Side) -> &mut Trainer {
+        match side {
+            Side::Player => &mut self.player,
+            Side::Opponent => &mut self.opponent,
+        }
+    }
+
+    /// 陣営に張られている壁を取得
+    pub fn screens(&self, side: Side) -> &Screens {
+        match side {
+            Side::Player => &self.player_screens,
+            Side::Opponent => &self.opponent_screens,
+        }
+    }
+
+    /// 陣営の場に出しているポケモンと壁、相手のポケモンと壁を可変参照で取得
+    ///
+    /// # 戻り値
+    /// * `((&mut Pokemon, &mut Screens), (&mut Pokemon, &mut Screens))` - (指定した陣営, 相手の陣営)
+    fn actives_mut(&mut self, side: Side) -> ((&mut Pokemon, &mut Screens), (&mut Pokemon, &mut Screens)) {
+        let player = (self.player.active_pokemon_mut(), &mut self.player_screens);
+        let opponent = (self.opponent.active_pokemon_mut(), &mut self.opponent_screens);
+        match side {
+            Side::Player => (player, opponent),
+            Side::Opponent => (opponent, player),
+        }
+    }
+
+    /// 力を溜めているポケモンの行動を溜めている技に置き換える
+    ///
+    /// # 引数
+    /// * `side`   - 行動する陣営
+    /// * `action` - 選択された行動
+    ///
+    /// # 戻り値
+    /// * `BattleAction` - 実際に行う行動
+    fn forced_action(&self, side: Side, action: BattleAction) -> BattleAction {
+        match self.trainer(side).active_pokemon().volatile.charging {
+            Some(skill_idx) => BattleAction::Fight(skill_idx),
+            None => action,
+        }
+    }
+
+    /// バトルが終了したかを判定
+    ///
+    /// どちらかの手持ちが全員ひんしになるか、やせいのポケモンを捕まえると終了する
+    pub fn is_over(&self) -> bool {
+        self.caught || self.winner().is_some()
+    }
+
+    /// 勝った陣営を取得
+    ///
+    /// # 戻り値
+    /// * `Option<Side>` - 勝った陣営、決着していなければ None
+    pub fn winner(&self) -> Option<Side> {
+        if self.opponent.is_defeated() {
+            Some(Side::Player)
+        } else if self.player.is_defeated() {
+            Some(Side::Opponent)
+        } else {
+            None
+        }
+    }
+
+    /// ひんしのポケモンを入れ替える必要があるかを判定
+    pub fn needs_replacement(&self, side: Side) -> bool {
+        let trainer = self.trainer(side);
+        !trainer.is_defeated() && trainer.active_pokemon().status.current_hp == 0
+    }
+
+    /// 陣営が選択できる行動の一覧を取得
+    ///
+    /// すべての技の PP がない場合はわるあがきとして `Fight(0)` のみを技の行動に含める
+    /// 力を溜めている場合は溜めている技しか選択できない
+    ///
+    /// # 戻り値
+    /// * `Vec<BattleAction>` - 選択できる行動
+    pub fn legal_actions(&self, side: Side) -> Vec<BattleAction> {
+        let trainer = self.trainer(side);
+        let active = trainer.active_pokemon();
+        if let Some(skill_idx) = active.volatile.charging {
+            return vec![BattleAction::Fight(skill_idx)];
+        }
+
+        let mut actions: Vec<BattleAction> = if active.has_usable_skill() {
+            active
+                .skills
+                .iter()
+                .enumerate()
+                .filter(|(_, learned)| learned.current_pp > 0)
+                .map(|(idx, _)| BattleAction::Fight(idx))
+                .collect()
+        } else {
+            vec![BattleAction::Fight(0)]
+        };
+
+        actions.extend(
+            (0..trainer.pokemons.len())
+                .filter(|&idx| trainer.can_switch_to(idx))
+                .map(BattleAction::Switch),
+        );
+
+        actions
+    }
+
+    /// 1ターン分の行動を処理
+    ///
+    /// 交代とボールは技よりも先に行い、技は優先度とすばやさの順に処理する
+    /// 力を溜めているポケモンは選択した行動に関係なく溜めている技を使用する
+    /// ボールでやせいのポケモンを捕まえた場合は、その時点でバトルを終了する
+    /// 後攻のポケモンは倒れていなければ行動し、最後にターン終了時の処理を行う
+    ///
+    /// # 引数
+    /// * `actions` - 両陣営が選択した行動
+    ///
+    /// # 戻り値
+    /// * `Vec<BattleEvent>` - このターンに発生したイベント
+    pub fn submit_actions(&mut self, actions: TurnActions) -> Vec<BattleEvent> {
+        let mut rng = self.rng.clone();
+        let events = self.submit_actions_with(actions, &mut rng);
+        self.rng = rng;
+
+        events
+    }
+
+    /// 指定した乱数の取得元で 1ターン分の行動を処理
+    ///
+    /// バトルの乱数生成器は使用しないため、探索 AI が起こりうる結果を列挙するときに使用する
+    ///
+    /// # 引数
+    /// * `actions` - 両陣営が選択した行動
+    /// * `rng`     - 乱数の取得元
+    ///
+    /// # 戻り値
+    /// * `Vec<BattleEvent>` - このターンに発生したイベント
+    pub fn submit_actions_with(&mut self, actions: TurnActions, rng: &mut dyn RandomSource) -> Vec<BattleEvent> {
+        let mut events = Vec::new();
+        if self.is_over() {
+            return events;
+        }
+        self.turn += 1;
+
+        let actions = TurnActions {
+            player: self.forced_action(Side::Player, actions.player),
+            opponent: self.forced_action(Side::Opponent, actions.opponent),
+        };
+
+        // 交代
+        for (side, action) in [(Side::Player, actions.player), (Side::Opponent, actions.opponent)] {
+            if let BattleAction::Switch(idx) = action {
+                self.switch(side, idx, &mut events);
+            }
+        }
+
+        // ボール
+        for (side, action) in [(Side::Player, actions.player), (Side::Opponent, actions.opponent)] {
+            if let BattleAction::Ball(ball) = action {
+                self.throw_ball(side, ball, rng, &mut events);
+            }
+        }
+        if self.caught {
+            return events;
+        }
+
+        // 技の優先度とすばやさから行動順を決定
+        let player_first = match (actions.player, actions.opponent) {
+            (BattleAction::Fight(skill_idx), BattleAction::Fight(enemy_skill_idx)) => {
+                self.player.active_pokemon().moves_first(skill_idx, self.opponent.active_pokemon(), enemy_skill_idx, rng)
+            }
+            _ => true,
+        };
+        let order = if player_first {
+            [(Side::Player, actions.player), (Side::Opponent, actions.opponent)]
+        } else {
+            [(Side::Opponent, actions.opponent), (Side::Player, actions.player)]
+        };
+
+        for (side, action) in order {
+            if self.player.active_pokemon().status.current_hp == 0
+                || self.opponent.active_pokemon().status.current_hp == 0
+            {
+                break;
+            }
+            if let BattleAction::Fight(skill_idx) = action {
+                let alive = self.actives_alive();
+                self.use_skill(side, skill_idx, rng, &mut events);
+                self.push_fainted(alive, &mut events);
+            }
+        }
+
+        // ターン終了時の処理
+        self.end_turn(&mut events);
+
+        if let Some(winner) = self.winner() {
+            events.push(BattleEvent::BattleEnded { winner });
+        }
+
+        events
+    }
+
+    /// ひんしになったポケモンを入れ替える
+    ///
+    /// # 引数
+    /// * `side` - 入れ替える陣営
+    /// * `idx`  - 交代先の手持ちのインデックス
+    ///
+    /// # 戻り値
+    /// * `Vec<BattleEvent>` - 発生したイベント
+    pub fn submit_replacement(&mut self, side: Side, idx: usize) -> Vec<BattleEvent> {
+        let mut events = Vec::new();
+        if !self.needs_replacement(side) || !self.trainer(side).can_switch_to(idx) {
+            events.push(BattleEvent::InvalidAction { side });
+            return events;
+        }
+
+        let trainer = self.trainer_mut(side);
+        trainer.switch_pokemon(idx);
+        let pokemon = trainer.active_pokemon();
+        events.push(BattleEvent::SwitchedIn {
+            side,
+            pokemon: pokemon.name().to_string(),
+            idx,
+            current_hp: pokemon.status.current_hp,
+        });
+        self.record_participants();
+
+        events
+    }
+
+    /// 覚えようとしている技を 1 つ処理
+    ///
+    /// `pending_skills` の先頭の技について、指定した技を忘れて覚えるか、覚えずにあきらめる
+    ///
+    /// # 引数
+    /// * `forget` - 忘れる技のインデックス、覚えない場合は None
+    ///
+    /// # 戻り値
+    /// * `Vec<BattleEvent>` - 発生したイベント
+    pub fn resolve_pending_skill(&mut self, forget: Option<usize>) -> Vec<BattleEvent> {
+        let mut events = Vec::new();
+        let Some(pending) = self.pending_skills.first().cloned() else {
+            return events;
+        };
+        let pokemon = &mut self.player.pokemons[pending.idx];
+        if forget.is_some_and(|slot| slot >= pokemon.skills.len()) {
+            events.push(BattleEvent::InvalidAction { side: Side::Player });
+            return events;
+        }
+        self.pending_skills.remove(0);
+
+        let (name, idx, skill) = (pokemon.name().to_string(), pending.idx, pending.skill.name.clone());
+        match forget.and_then(|slot| pokemon.replace_skill(slot, pending.skill)) {
+            Some(forgotten) => {
+                events.push(BattleEvent::SkillForgotten { side: Side::Player, pokemon: name.clone(), idx, skill: forgotten.name.clone() });
+                events.push(BattleEvent::SkillLearned { side: Side::Player, pokemon: name, idx, skill });
+            }
+            None => events.push(BattleEvent::SkillNotLearned { side: Side::Player, pokemon: name, idx, skill }),
+        }
+
+        events
+    }
+
+    /// ポケモンを交代
+    ///
+    /// # 引数
+    /// * `side`   - 交代する陣営
+    /// * `idx`    - 交代先の手持ちのインデックス
+    /// * `events` - イベントの出力先
+    fn switch(&mut self, side: Side, idx: usize, events: &mut Vec<BattleEvent>) {
+        let trainer = self.trainer_mut(side);
+        if !trainer.can_switch_to(idx) {
+            events.push(BattleEvent::InvalidAction { side });
+            return;
+        }
+
+        events.push(BattleEvent::SwitchedOut { side, pokemon: trainer.active_pokemon().name().to_string() });
+        trainer.switch_pokemon(idx);
+        let pokemon = trainer.active_pokemon();
+        events.push(BattleEvent::SwitchedIn {
+            side,
+            pokemon: pokemon.name().to_string(),
+            idx,
+            current_hp: pokemon.status.current_hp,
+        });
+        self.record_participants();
+    }
+
+    /// やせいのポケモンにボールを投げる
+    ///
+    /// ボールを投げられるのはやせいのポケモンとのバトルの自分の陣営のみ
+    ///
+    /// # 引数
+    /// * `side`   - ボールを投げる陣営
+    /// * `ball`   - 投げるボール
+    /// * `rng`    - 乱数の取得元
+    /// * `events` - イベントの出力先
+    fn throw_ball(&mut self, side: Side, ball: Ball, rng: &mut dyn RandomSource, events: &mut Vec<BattleEvent>) {
+        if side != Side::Player || !self.wild {
+            events.push(BattleEvent::InvalidAction { side });
+            return;
+        }
+
+        let target = self.opponent.active_pokemon();
+        let checks = target.try_capture(ball, rng);
+        self.caught = checks == SHAKE_CHECKS;
+        events.push(BattleEvent::BallThrown {
+            side: Side::Opponent,
+            pokemon: target.name().to_string(),
+            shakes: checks.min(SHAKE_CHECKS - 1),
+            caught: self.caught,
+        });
+    }
+
+    /// 技を使用
+    ///
+    /// # 引数
+    /// * `side`      - 技を使用する陣営
+    /// * `skill_idx` - 技リストのインデックス
+    /// * `rng`       - 乱数の取得元
+    /// * `events`    - イベントの出力先
+    fn use_skill(&mut self, side: Side, skill_idx: usize, rng: &mut dyn RandomSource, events: &mut Vec<BattleEvent>) {
+        let target_side = side.opposite();
+        let ((attacker, attacker_screens), (target, target_screens)) = self.actives_mut(side);
+
+        // 力を溜めていた場合は PP を消費せずに攻撃する（行動できなければ溜めは解除される）
+        let charged = attacker.volatile.charging.take() == Some(skill_idx);
+
+        // すべての技の PP がない場合はわるあがき
+        let struggle = !charged && !attacker.has_usable_skill();
+        let skill = if struggle {
+            Arc::new(Skill::struggle())
+        } else {
+            match attacker.skills.get(skill_idx) {
+                Some(learned) if learned.current_pp == 0 && !charged => {
+                    events.push(BattleEvent::NoPp { side, pokemon: attacker.name().to_string() });
+                    return;
+                }
+                Some(learned) => learned.skill.clone(),
+                None => {
+                    events.push(BattleEvent::InvalidAction { side });
+                    return;
+                }
+            }
+        };
+
+        // 状態異常で行動できるかを判定
+        if !Self::can_move(side, attacker, rng, events) {
+            return;
+        }
+
+        // PP を消費
+        if struggle {
+            events.push(BattleEvent::Struggle { side, pokemon: attacker.name().to_string() });
+        } else if !charged {
+            attacker.skills[skill_idx].current_pp -= 1;
+        }
+
+        // 溜めが必要な技は 1 ターン目に力を溜める
+        if skill.charge && !charged {
+            attacker.volatile.charging = Some(skill_idx);
+            events.push(BattleEvent::Charging { side, pokemon: attacker.name().to_string(), skill: skill.name.clone() });
+            return;
+        }
+
+        events.push(BattleEvent::SkillUsed { side, pokemon: attacker.name().to_string(), skill: skill.name.clone() });
+
+        // 命中確率を計算
+        if !attacker.is_hit(skill.accuracy, rng) {
+            events.push(BattleEvent::Missed { side: target_side, pokemon: target.name().to_string() });
+            return;
+        }
+
+        // タイプ相性を判定（わるあがき、相手を状態異常にしない変化技には適用しない）
+        let effectiveness = if skill.class == SkillType::Struggle {
+            Effectiveness::Normal
+        } else {
+            Effectiveness::from_multiplier(target.type_effectiveness(skill.element))
+        };
+        let immunity_applies = match skill.class {
+            SkillType::ChangeStatus => skill
+                .skill_effect
+                .is_some_and(|skill_effect| skill_effect.ailment.is_some() && skill_effect.target == Target::Enemy),
+            _ => true,
+        };
+        if immunity_applies && effectiveness == Effectiveness::NoEffect {
+            events.push(BattleEvent::Effectiveness { side: target_side, pokemon: target.name().to_string(), effectiveness });
+            return;
+        }
+
+        // 壁を壊す技はダメージの前に相手の陣営の壁を解除
+        if skill.breaks_screens {
+            for screen in [Screen::Reflect, Screen::LightScreen] {
+                let turns = target_screens.turns_mut(screen);
+                if *turns > 0 {
+                    *turns = 0;
+                    events.push(BattleEvent::ScreenBroken { side: target_side, screen });
+                }
+            }
+        }
+
+        // 技の種類を判定
+        match skill.class {
+            SkillType::PhysicalAttack | SkillType::SpecialAttack | SkillType::Struggle => {
+                let critical = attacker.is_critical(&skill, rng);
+                let roll = rng.random_in_range(MIN_DAMAGE_ROLL as u64, MAX_DAMAGE_ROLL as u64) as u8;
+                let mut damage = attacker.compute_damage(&skill, target, critical, roll);
+                // 急所に当たった場合は壁を無視する
+                if !critical && target_screens.reduces(skill.class) {
+                    damage = (damage / 2).max(1);
+                }
+                target.status.current_hp = target.status.current_hp.saturating_sub(damage);
+                events.push(BattleEvent::Damage {
+                    side: target_side,
+                    pokemon: target.name().to_string(),
+                    damage,
+                    current_hp: target.status.current_hp,
+                });
+                if critical {
+                    events.push(BattleEvent::Critical { side: target_side });
+                }
+                if effectiveness != Effectiveness::Normal {
+                    events.push(BattleEvent::Effectiveness { side: target_side, pokemon: target.name().to_string(), effectiveness });
+                }
+
+                // ほのおタイプの技を受けるとこおりがとける
+                if skill.element == ElementType::Fire
+                    && target.ailment == Some(StatusAilment::Freeze)
+                    && target.status.current_hp > 0
+                {
+                    if let Some(ailment) = target.cure_ailment() {
+                        events.push(BattleEvent::AilmentCured { side: target_side, pokemon: target.name().to_string(), ailment });
+                    }
+                }
+
+                // 追加効果
+                Self::apply_secondary_effects(side, &skill, attacker, target, rng, events);
+
+                // わるあがきの反動
+                if skill.class == SkillType::Struggle {
+                    let recoil = (attacker.status.hp.value / 4).max(1);
+                    attacker.status.current_hp = attacker.status.current_hp.saturating_sub(recoil);
+                    events.push(BattleEvent::Recoil {
+                        side,
+                        pokemon: attacker.name().to_string(),
+                        damage: recoil,
+                        current_hp: attacker.status.current_hp,
+                    });
+                }
+            }
+            SkillType::ChangeStatus => {
+                let Some(skill_effect) = skill.skill_effect else {
+                    events.push(BattleEvent::Failed { side });
+                    return;
+                };
+
+                // 効果の対象を決定
+                let (receiver_side, receiver, receiver_screens): (Side, &mut Pokemon, &mut Screens) = match skill_effect.target {
+                    Target::Self_ => (side, attacker, attacker_screens),
+                    Target::Enemy => (target_side, target, target_screens),
+                    Target::Ally => {
+                        // TODO ダブルバトルは未実装
+                        events.push(BattleEvent::Failed { side });
+                        return;
+                    }
+                };
+
+                if let Some(status_effect) = skill_effect.status_effect {
+                    match receiver.apply_status_change(status_effect.target, status_effect.effect_value) {
+                        Some(value) if value != 0 => events.push(BattleEvent::StatChanged {
+                            side: receiver_side,
+                            pokemon: receiver.name().to_string(),
+                            status: status_effect.target,
+                            value,
+                        }),
+                        _ => events.push(BattleEvent::Failed { side }),
+                    }
+                }
+                if let Some(ailment) = skill_effect.ailment {
+                    if receiver.inflict_ailment(ailment, rng) {
+                        events.push(BattleEvent::AilmentInflicted { side: receiver_side, pokemon: receiver.name().to_string(), ailment });
+                    } else {
+                        events.push(BattleEvent::Failed { side });
+                    }
+                }
+                if let Some(screen) = skill_effect.screen {
+                    // すでに同じ壁が張られている場合は失敗
+                    let turns = receiver_screens.turns_mut(screen);
+                    if *turns == 0 {
+                        *turns = SCREEN_TURNS;
+                        events.push(BattleEvent::ScreenSet { side: receiver_side, screen });
+                    } else {
+                        events.push(BattleEvent::Failed { side });
+                    }
+                }
+                if skill_effect.critical_stage > 0 {
+                    // すでに急所ランクが上がっている場合は失敗
+                    if receiver.volatile.critical_stage == 0 {
+                        receiver.volatile.critical_stage = skill_effect.critical_stage;
+                        events.push(BattleEvent::CriticalStageRaised { side: receiver_side, pokemon: receiver.name().to_string() });
+                    } else {
+                        events.push(BattleEvent::Failed { side });
+                    }
+                }
+            }
+            SkillType::OneHitKO => {
+                target.status.current_hp = 0;
+                events.push(BattleEvent::OneHitKO { side: target_side, pokemon: target.name().to_string() });
+            }
+        }
+    }
+
+    /// 状態異常、ひるみ、こんらんによって行動できるかを判定
+    ///
+    /// ねむりのターン経過、こおりの解凍判定、こんらんの自傷もここで行う
+    ///
+    /// # 引数
+    /// * `side`    - 行動する陣営
+    /// * `pokemon` - 行動するポケモン
+    /// * `rng`     - 乱数の取得元
+    /// * `events`  - イベントの出力先
+    ///
+    /// # 戻り値
+    /// * `bool` - 行動できるかどうか
+    fn can_move(side: Side, pokemon: &mut Pokemon, rng: &mut dyn RandomSource, events: &mut Vec<BattleEvent>) -> bool {
+        let cant_move = |pokemon: &Pokemon, reason| BattleEvent::CantMove { side, pokemon: pokemon.name().to_string(), reason };
+
+        match pokemon.ailment {
+            Some(StatusAilment::Sleep) => {
+                if pokemon.ailment_turns == 0 {
+                    if let Some(ailment) = pokemon.cure_ailment() {
+                        events.push(BattleEvent::AilmentCured { side, pokemon: pokemon.name().to_string(), ailment });
+                    }
+                } else {
+                    pokemon.ailment_turns -= 1;
+                    events.push(cant_move(pokemon, CantMoveReason::Sleep));
+                    return false;
+                }
+            }
+            Some(StatusAilment::Freeze) => {
+                // 20% の確率でこおりがとける
+                if rng.random_bool(0.2) {
+                    if let Some(ailment) = pokemon.cure_ailment() {
+                        events.push(BattleEvent::AilmentCured { side, pokemon: pokemon.name().to_string(), ailment });
+                    }
+                } else {
+                    events.push(cant_move(pokemon, CantMoveReason::Freeze));
+                    return false;
+                }
+            }
+            _ => {}
+        }
+
+        if pokemon.volatile.flinch {
+            events.push(cant_move(pokemon, CantMoveReason::Flinch));
+            return false;
+        }
+
+        if pokemon.volatile.confusion_turns > 0 {
+            pokemon.volatile.confusion_turns -= 1;
+            if pokemon.volatile.confusion_turns == 0 {
+                events.push(BattleEvent::ConfusionEnded { side, pokemon: pokemon.name().to_string() });
+            } else {
+                events.push(BattleEvent::IsConfused { side, pokemon: pokemon.name().to_string() });
+
+                // 1/3 の確率で自分を攻撃
+                if rng.random_bool(1.0 / 3.0) {
+                    let damage = pokemon.compute_confusion_damage(rng);
+                    pokemon.status.current_hp = pokemon.status.current_hp.saturating_sub(damage);
+                    events.push(BattleEvent::HurtItself {
+                        side,
+                        pokemon: pokemon.name().to_string(),
+                        damage,
+                        current_hp: pokemon.status.current_hp,
+                    });
+                    return false;
+                }
+            }
+        }
+
+        // 25% の確率でしびれて動けない
+        if pokemon.ailment == Some(StatusAilment::Paralysis) && rng.random_bool(0.25) {
+            events.push(cant_move(pokemon, CantMoveReason::Paralysis));
+            return false;
+        }
+
+        true
+    }
+
+    /// 攻撃技の追加効果を確率で適用
+    ///
+    /// 相手が対象の効果は、相手がひんしの場合は適用しない
+    ///
+    /// # 引数
+    /// * `side`     - 技を使用した陣営
+    /// * `skill`    - 使用した技
+    /// * `attacker` - 技を使用したポケモン
+    /// * `target`   - 攻撃したターゲット
+    /// * `rng`      - 乱数の取得元
+    /// * `events`   - イベントの出力先
+    fn apply_secondary_effects(
+        side: Side,
+        skill: &Skill,
+        attacker: &mut Pokemon,
+        target: &mut Pokemon,
+        rng: &mut dyn RandomSource,
+        events: &mut Vec<BattleEvent>,
+    ) {
+        for secondary_effect in &skill.secondary_effects {
+            if !rng.random_bool(secondary_effect.chance as f64 / 100.0) {
+                continue;
+            }
+            let (receiver_side, receiver): (Side, &mut Pokemon) = match secondary_effect.target {
+                Target::Self_ => (side, &mut *attacker),
+                Target::Enemy if target.status.current_hp > 0 => (side.opposite(), &mut *target),
+                _ => continue,
+            };
+
+            match secondary_effect.effect {
+                EffectKind::StatusChange(status_effect) => {
+                    if let Some(value) = receiver.apply_status_change(status_effect.target, status_effect.effect_value) {
+                        if value != 0 {
+                            events.push(BattleEvent::StatChanged {
+                                side: receiver_side,
+                                pokemon: receiver.name().to_string(),
+                                status: status_effect.target,
+                                value,
+                            });
+                        }
+                    }
+                }
+                EffectKind::Ailment(ailment) => {
+                    if receiver.inflict_ailment(ailment, rng) {
+                        events.push(BattleEvent::AilmentInflicted { side: receiver_side, pokemon: receiver.name().to_string(), ailment });
+                    }
+                }
+                EffectKind::Flinch => {
+                    receiver.volatile.flinch = true;
+                }
+                EffectKind::Confusion => {
+                    if receiver.inflict_confusion(rng) {
+                        events.push(BattleEvent::Confused { side: receiver_side, pokemon: receiver.name().to_string() });
+                    }
+                }
+            }
+        }
+    }
+
+    /// ターン終了時の処理
+    ///
+    /// 状態異常によるダメージを適用し、ひるみを解除して、壁の残りターン数を減らす
+    ///
+    /// # 引数
+    /// * `events` - イベントの出力先
+    fn end_turn(&mut self, events: &mut Vec<BattleEvent>) {
+        let alive = self.actives_alive();
+
+        for side in [Side::Player, Side::Opponent] {
+            let pokemon = self.trainer_mut(side).active_pokemon_mut();
+            let ailment = pokemon.ailment;
+            if let (Some(ailment), Some(damage)) = (ailment, pokemon.apply_residual_damage()) {
+                events.push(BattleEvent::ResidualDamage {
+                    side,
+                    pokemon: pokemon.name().to_string(),
+                    ailment,
+                    damage,
+                    current_hp: pokemon.status.current_hp,
+                });
+            }
+            pokemon.volatile.flinch = false;
+        }
+
+        for (side, screens) in [(Side::Player, &mut self.player_screens), (Side::Opponent, &mut self.opponent_screens)] {
+            for screen in [Screen::Reflect, Screen::LightScreen] {
+                let turns = screens.turns_mut(screen);
+                if *turns > 0 {
+                    *turns -= 1;
+                    if *turns == 0 {
+                        events.push(BattleEvent::ScreenEnded { side, screen });
+                    }
+                }
+            }
+        }
+
+        self.push_fainted(alive, events);
+    }
+
+    /// 場に出している (自分, 相手) のポケモンが戦える状態かを取得
+    fn actives_alive(&self) -> (bool, bool) {
+        (
+            self.player.active_pokemon().status.current_hp > 0,
+            self.opponent.active_pokemon().status.current_hp > 0,
+        )
+    }
+
+    /// 新たにひんしになったポケモンのイベントを追加
+    ///
+    /// 相手のポケモンが倒れた場合は、経験値をもらう設定なら戦ったポケモンに経験値を配る
+    ///
+    /// # 引数
+    /// * `alive`  - 処理前に (自分, 相手) がそれぞれ戦える状態だったか
+    /// * `events` - イベントの出力先
+    fn push_fainted(&mut self, alive: (bool, bool), events: &mut Vec<BattleEvent>) {
+        let (player_alive, opponent_alive) = self.actives_alive();
+        if alive.1 && !opponent_alive {
+            events.push(BattleEvent::Fainted { side: Side::Opponent, pokemon: self.opponent.active_pokemon().name().to_string() });
+            if self.exp_gain {
+                self.award_exp(events);
+            }
+        }
+        if alive.0 && !player_alive {
+            events.push(BattleEvent::Fainted { side: Side::Player, pokemon: self.player.active_pokemon().name().to_string() });
+        }
+    }
+
+    /// 場で向き合っている自分と相手のポケモンを記録
+    ///
+    /// どちらかがひんしの場合は記録しない
+    fn record_participants(&mut self) {
+        if self.actives_alive() != (true, true) {
+            return;
+        }
+        if let Some(participants) = self.participants.get_mut(self.opponent.active_idx) {
+            participants.insert(self.player.active_idx);
+        }
+    }
+
+    /// 倒れた相手のポケモンと戦った自分のポケモンに経験値を配る
+    ///
+    /// 経験値は戦ったポケモンのうちひんしでないポケモンで等分する
+    ///
+    /// # 引数
+    /// * `events` - イベントの出力先
+    fn award_exp(&mut self, events: &mut Vec<BattleEvent>) {
+        let participants: Vec<usize> = self
+            .participants
+            .get(self.opponent.active_idx)
+            .into_iter()
+            .flatten()
+            .copied()
+            .filter(|&idx| self.player.pokemons[idx].status.current_hp > 0)
+            .collect();
+        let exp = self.opponent.active_pokemon().exp_yield(self.wild, participants.len() as u32);
+
+        for idx in participants {
+            let pokemon = &mut self.player.pokemons[idx];
+            events.push(BattleEvent::ExpGained { side: Side::Player, pokemon: pokemon.name().to_string(), idx, exp });
+            pokemon.gain_exp(exp);
+            while pokemon.can_level_up() {
+                pokemon.level_up();
+                events.push(BattleEvent::LevelUp {
+                    side: Side::Player,
+                    pokemon: pokemon.name().to_string(),
+                    idx,
+                    level: pokemon.level,
+                    max_hp: pokemon.status.hp.value,
+                    current_hp: pokemon.status.current_hp,
+                });
+
+                // 新しいレベルで覚える技は空いている枠に覚え、空きがなければ選択を待つ
+                for skill in pokemon.skills_learned_at(pokemon.level) {
+                    if pokemon.learn_skill(skill.clone()) {
+                        events.push(BattleEvent::SkillLearned {
+                            side: Side::Player,
+                            pokemon: pokemon.name().to_string(),
+                            idx,
+                            skill: skill.name.clone(),
+                        });
+                    } else {
+                        self.pending_skills.push(PendingSkill { idx, skill });
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file::json::fixture::trainer;
+
+    /// 両陣営が常に最初の選択肢を選ぶバトルを最後まで進め、発生したイベントを返す
+    fn play_out(seed: u64) -> Vec<BattleEvent> {
+        let player = trainer("wataru");
+        let opponent = trainer("daigo");
+        let mut battle = Battle::new(player, opponent, false, XorShift128::from_seed(seed));
+
+        let mut events = Vec::new();
+        while !battle.is_over() && battle.turn < 500 {
+            let actions = TurnActions {
+                player: battle.legal_actions(Side::Player)[0],
+                opponent: battle.legal_actions(Side::Opponent)[0],
+            };
+            events.extend(battle.submit_actions(actions));
+            for side in [Side::Player, Side::Opponent] {
+                if battle.needs_replacement(side) {
+                    let idx = (0..battle.trainer(side).pokemons.len())
+                        .find(|&idx| battle.trainer(side).can_switch_to(idx))
+                        .expect("交代先がありません");
+                    events.extend(battle.submit_replacement(side, idx));
+                }
+            }
+        }
+
+        events
+    }
+
+    /// 指定した技を覚えているポケモンを場に出し、その技のインデックスを返す
+    fn send_out_with(trainer: &mut Trainer, skill: &str) -> usize {
+        let (idx, skill_idx) = trainer
+            .pokemons
+            .iter()
+            .enumerate()
+            .find_map(|(idx, pokemon)| Some((idx, pokemon.skills.iter().position(|learned| learned.skill.name == skill)?)))
+            .expect("技を覚えているポケモンがいません");
+        trainer.active_idx = idx;
+
+        skill_idx
+    }
+
+    /// 同じシード値と行動からは同じイベント列が得られる
+    #[test]
+    fn same_seed_gives_same_events() {
+        let first = play_out(9);
+        assert!(matches!(first.last(), Some(BattleEvent::BattleEnded { .. })));
+        assert_eq!(first, play_out(9));
+    }
+
+    /// シード値が異なればダメージの乱数などが変わる
+    #[test]
+    fn different_seed_gives_different_events() {
+        assert_ne!(play_out(9), play_out(10));
+    }
+
+    /// 探索用に複製したバトルを進めても元のバトルの乱数は変わらない
+    #[test]
+    fn cloned_battle_does_not_share_rng() {
+        let player = trainer("wataru");
+        let opponent = trainer("daigo");
+        let mut battle = Battle::new(player, opponent, false, XorShift128::from_seed(3));
+        let actions = TurnActions { player: BattleAction::Fight(0), opponent: BattleAction::Fight(0) };
+
+        let mut clone = battle.clone();
+        let expected = clone.submit_actions(actions);
+        clone.submit_actions(actions);
+
+        assert_eq!(battle.submit_actions(actions), expected);
+    }
+
+    /// 状態異常にする変化技もタイプ相性で無効になる
+    #[test]
+    fn thunder_wave_does_not_affect_ground_types() {
+        let mut player = trainer("wataru");
+        let opponent = trainer("acerola");
+        let skill_idx = send_out_with(&mut player, "でんじは");
+        let mut battle = Battle::new(player, opponent, false, XorShift128::from_seed(1));
+
+        let (mut rng, mut events) = (battle.rng.clone(), Vec::new());
+        battle.use_skill(Side::Player, skill_idx, &mut rng, &mut events);
+
+        assert!(events.iter().any(|event| matches!(
+            event,
+            BattleEvent::Effectiveness { effectiveness: Effectiveness::NoEffect, .. }
+        )));
+        assert_eq!(battle.opponent.active_pokemon().ailment, None);
+    }
+
+    /// 溜めが必要な技は 1 ターン目に PP を消費して力を溜め、2 ターン目は溜めた技しか選べない
+    #[test]
+    fn charge_move_attacks_on_the_second_turn() {
+        let mut player = trainer("wataru");
+        let opponent = trainer("daigo");
+        let skill_idx = send_out_with(&mut player, "ゴッドバード");
+        let mut battle = Battle::new(player, opponent, false, XorShift128::from_seed(1));
+        let pp = battle.player.active_pokemon().skills[skill_idx].current_pp;
+
+        let (mut rng, mut events) = (battle.rng.clone(), Vec::new());
+        battle.use_skill(Side::Player, skill_idx, &mut rng, &mut events);
+        assert!(matches!(events.as_slice(), [BattleEvent::Charging { .. }]));
+        assert_eq!(battle.player.active_pokemon().skills[skill_idx].current_pp, pp - 1);
+        assert_eq!(battle.legal_actions(Side::Player), vec![BattleAction::Fight(skill_idx)]);
+
+        events.clear();
+        battle.use_skill(Side::Player, skill_idx, &mut rng, &mut events);
+        assert!(matches!(events.first(), Some(BattleEvent::SkillUsed { .. })));
+        assert_eq!(battle.player.active_pokemon().skills[skill_idx].current_pp, pp - 1);
+        assert_eq!(battle.player.active_pokemon().volatile.charging, None);
+    }
+
+    /// リフレクターは急所に当たらなかった物理技のダメージを半分にする
+    #[test]
+    fn reflect_halves_physical_damage() {
+        let mut player = trainer("wataru");
+        let opponent = trainer("daigo");
+        let skill_idx = send_out_with(&mut player, "ドラゴンクロー");
+        let battle = Battle::new(player, opponent, false, XorShift128::from_seed(1));
+
+        for seed in 0..20 {
+            let damage = |reflect: u8| {
+                let mut battle = battle.clone();
+                battle.opponent_screens.reflect = reflect;
+                let (mut rng, mut events) = (XorShift128::from_seed(seed), Vec::new());
+                battle.use_skill(Side::Player, skill_idx, &mut rng, &mut events);
+                let critical = events.iter().any(|event| matches!(event, BattleEvent::Critical { .. }));
+                let damage = events.iter().find_map(|event| match event {
+                    BattleEvent::Damage { damage, .. } => Some(*damage),
+                    _ => None,
+                });
+                (damage, critical)
+            };
+
+            match (damage(0), damage(SCREEN_TURNS)) {
+                ((Some(normal), false), (Some(reduced), false)) => assert_eq!(reduced, (normal / 2).max(1)),
+                ((normal, _), (reduced, true)) => assert_eq!(normal, reduced),
+                (normal, reduced) => assert_eq!(normal.0, reduced.0),
+            }
+        }
+    }
+
+    /// きあいだめで上がった急所ランクは技の急所ランクに加算される
+    #[test]
+    fn focus_energy_raises_critical_chance() {
+        let mut player = trainer("wataru");
+        let skill_idx = send_out_with(&mut player, "ドラゴンクロー");
+        let pokemon = player.active_pokemon_mut();
+        let skill = pokemon.skills[skill_idx].skill.clone();
+
+        assert_eq!(pokemon.critical_chance(&skill), 1.0 / 24.0);
+        pokemon.volatile.critical_stage = 2;
+        assert_eq!(pokemon.critical_chance(&skill), 1.0 / 2.0);
+        pokemon.volatile.critical_stage = 3;
+        assert_eq!(pokemon.critical_chance(&skill), 1.0);
+    }
+}
@@ -0,0 +1,108 @@
+use crate::{
+    models::pokemon::{Screen, StatusAilment, StatusType},
+    services::battle::element::Effectiveness
+};
+
+/// バトルの陣営を表す列挙型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Player,
+    Opponent,
+}
+
+impl Side {
+    /// 相手側の陣営を取得
+    pub fn opposite(&self) -> Side {
+        match self {
+            Side::Player => Side::Opponent,
+            Side::Opponent => Side::Player,
+        }
+    }
+}
+
+/// 行動できなかった理由を表す列挙型
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CantMoveReason {
+    Sleep,
+    Freeze,
+    Flinch,
+    Paralysis,
+}
+
+/// バトル中に発生した出来事を表す列挙型
+///
+/// エンジンは状態の変化をイベントとして返し、表示はフロントエンド側で行う
+/// `side` はイベントの対象となったポケモンの陣営、`pokemon` はそのポケモンの名前
+#[derive(Debug, Clone, PartialEq)]
+pub enum BattleEvent {
+    /// 技を使用した
+    SkillUsed { side: Side, pokemon: String, skill: String },
+    /// すべての技の PP がなく、わるあがきを使用した
+    Struggle { side: Side, pokemon: String },
+    /// 選択した技の PP がなかった
+    NoPp { side: Side, pokemon: String },
+    /// 存在しない技や交代先が指定された
+    InvalidAction { side: Side },
+    /// 溜めが必要な技で力を溜めた
+    Charging { side: Side, pokemon: String, skill: String },
+    /// 技が外れた
+    Missed { side: Side, pokemon: String },
+    /// タイプ相性（等倍の場合は発生しない）
+    Effectiveness { side: Side, pokemon: String, effectiveness: Effectiveness },
+    /// 急所に当たった
+    Critical { side: Side },
+    /// 技によるダメージを受けた
+    Damage { side: Side, pokemon: String, damage: u16, current_hp: u16 },
+    /// 一撃必殺の技を受けた
+    OneHitKO { side: Side, pokemon: String },
+    /// ステータスのランクが変化した
+    StatChanged { side: Side, pokemon: String, status: StatusType, value: i8 },
+    /// 陣営に壁が張られた
+    ScreenSet { side: Side, screen: Screen },
+    /// 陣営の壁が壊された
+    ScreenBroken { side: Side, screen: Screen },
+    /// 陣営の壁の効果がなくなった
+    ScreenEnded { side: Side, screen: Screen },
+    /// きあいだめなどで急所ランクが上がった
+    CriticalStageRaised { side: Side, pokemon: String },
+    /// 状態異常になった
+    AilmentInflicted { side: Side, pokemon: String, ailment: StatusAilment },
+    /// 状態異常が治った
+    AilmentCured { side: Side, pokemon: String, ailment: StatusAilment },
+    /// 変化技の効果がなかった
+    Failed { side: Side },
+    /// こんらん状態になった
+    Confused { side: Side, pokemon: String },
+    /// こんらんしている
+    IsConfused { side: Side, pokemon: String },
+    /// こんらんが解けた
+    ConfusionEnded { side: Side, pokemon: String },
+    /// こんらんで自分を攻撃した
+    HurtItself { side: Side, pokemon: String, damage: u16, current_hp: u16 },
+    /// 状態異常やひるみで行動できなかった
+    CantMove { side: Side, pokemon: String, reason: CantMoveReason },
+    /// ターン終了時に状態異常のダメージを受けた
+    ResidualDamage { side: Side, pokemon: String, ailment: StatusAilment, damage: u16, current_hp: u16 },
+    /// わるあがきの反動を受けた
+    Recoil { side: Side, pokemon: String, damage: u16, current_hp: u16 },
+    /// ポケモンを引っ込めた
+    SwitchedOut { side: Side, pokemon: String },
+    /// ポケモンを場に出した（`idx` は手持ちのインデックス）
+    SwitchedIn { side: Side, pokemon: String, idx: usize, current_hp: u16 },
+    /// ひんしになった
+    Fainted { side: Side, pokemon: String },
+    /// 相手のポケモンを倒して経験値をもらった（`idx` は手持ちのインデックス）
+    ExpGained { side: Side, pokemon: String, idx: usize, exp: u32 },
+    /// レベルが上がった（`max_hp`、`current_hp` はレベルアップ後の値）
+    LevelUp { side: Side, pokemon: String, idx: usize, level: u8, max_hp: u16, current_hp: u16 },
+    /// 技を覚えた
+    SkillLearned { side: Side, pokemon: String, idx: usize, skill: String },
+    /// 新しい技を覚えるために技を忘れた
+    SkillForgotten { side: Side, pokemon: String, idx: usize, skill: String },
+    /// 新しい技を覚えずにあきらめた
+    SkillNotLearned { side: Side, pokemon: String, idx: usize, skill: String },
+    /// やせいのポケモンにボールを投げた（`shakes` はボールが揺れた回数）
+    BallThrown { side: Side, pokemon: String, shakes: u8, caught: bool },
+    /// バトルが終了した
+    BattleEnded { winner: Side },
+}
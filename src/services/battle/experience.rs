@@ -0,0 +1,195 @@
+use crate::models::pokemon::{GrowthRate, Pokemon, Status};
+
+/// レベルの上限
+pub const MAX_LEVEL: u8 = 100;
+
+impl GrowthRate {
+    /// レベルに到達するのに必要な累計の経験値
+    ///
+    /// # 引数
+    /// * `level` - レベル（1 〜 100）
+    ///
+    /// # 戻り値
+    /// * `u32` - 必要な累計の経験値（レベル 1 は 0）
+    pub fn exp_for_level(&self, level: u8) -> u32 {
+        if level <= 1 {
+            return 0;
+        }
+
+        let n = level.min(MAX_LEVEL) as i64;
+        let cube = n * n * n;
+        let exp = match self {
+            GrowthRate::Erratic => match n {
+                ..50 => cube * (100 - n) / 50,
+                50..68 => cube * (150 - n) / 100,
+                68..98 => cube * ((1911 - 10 * n) / 3) / 500,
+                _ => cube * (160 - n) / 100,
+            },
+            GrowthRate::Fast => cube * 4 / 5,
+            GrowthRate::MediumFast => cube,
+            GrowthRate::MediumSlow => cube * 6 / 5 - 15 * n * n + 100 * n - 140,
+            GrowthRate::Slow => cube * 5 / 4,
+            GrowthRate::Fluctuating => match n {
+                ..15 => cube * ((n + 1) / 3 + 24) / 50,
+                15..36 => cube * (n + 14) / 50,
+                _ => cube * (n / 2 + 32) / 50,
+            },
+        };
+
+        exp.max(0) as u32
+    }
+}
+
+pub trait ExperienceActions {
+    fn exp_yield(&self, wild: bool, participants: u32) -> u32;
+    fn gain_exp(&mut self, exp: u32);
+    fn can_level_up(&self) -> bool;
+    fn level_up(&mut self);
+}
+
+impl ExperienceActions for Pokemon {
+    /// 倒されたときに、戦ったポケモン 1 匹がもらえる経験値を計算
+    ///
+    /// 基礎経験値とレベルに比例し、トレーナーのポケモンは 1.5 倍、戦ったポケモンの数で等分する
+    ///
+    /// # 引数
+    /// * `wild`         - やせいのポケモンかどうか
+    /// * `participants` - 戦ったポケモンの数
+    ///
+    /// # 戻り値
+    /// * `u32` - もらえる経験値（最低 1）
+    fn exp_yield(&self, wild: bool, participants: u32) -> u32 {
+        let mut exp = self.species.base_exp as u32 * self.level as u32 / 7;
+        if !wild {
+            exp = exp * 3 / 2;
+        }
+
+        (exp / participants.max(1)).max(1)
+    }
+
+    /// 経験値を加える
+    ///
+    /// 経験値がレベルに対して足りない場合（経験値を記録していないデータ）は、そのレベルの最低値から加算する
+    /// レベルアップは `can_level_up` と `level_up` で 1 レベルずつ行う
+    ///
+    /// # 引数
+    /// * `exp` - もらった経験値
+    fn gain_exp(&mut self, exp: u32) {
+        let max_exp = self.species.growth_rate.exp_for_level(MAX_LEVEL);
+        self.exp = self.exp.max(self.species.growth_rate.exp_for_level(self.level)).saturating_add(exp).min(max_exp);
+    }
+
+    /// 次のレベルに必要な経験値に達しているかを判定
+    fn can_level_up(&self) -> bool {
+        self.level < MAX_LEVEL && self.exp >= self.species.growth_rate.exp_for_level(self.level + 1)
+    }
+
+    /// レベルを 1 上げてステータスを再計算
+    ///
+    /// ランク変化はそのまま、現在のHPは最大HPの増加分だけ回復する
+    /// なつき度は低いほど大きく上がる
+    fn level_up(&mut self) {
+        let old = self.status.clone();
+        self.level = (self.level + 1).min(MAX_LEVEL);
+
+        let mut status = Status::from_base_status(&self.species.base_status, &self.ivs, &self.evs, self.nature, self.level);
+        status.atk.buff = old.atk.buff;
+        status.def.buff = old.def.buff;
+        status.sp_atk.buff = old.sp_atk.buff;
+        status.sp_def.buff = old.sp_def.buff;
+        status.spd.buff = old.spd.buff;
+        status.current_hp = (old.current_hp + status.hp.value.saturating_sub(old.hp.value)).min(status.hp.value);
+        self.status = status;
+
+        let friendship = match self.friendship {
+            ..100 => 5,
+            100..200 => 3,
+            _ => 2,
+        };
+        self.friendship = self.friendship.saturating_add(friendship);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::file::json::fixture::trainer;
+
+    /// 指定したレベル、経験値タイプ、基礎経験値のポケモンを作成
+    fn pokemon_at(level: u8, growth_rate: GrowthRate, base_exp: u16) -> Pokemon {
+        let mut pokemon = trainer("wataru").pokemons[0].clone();
+        let mut species = (*pokemon.species).clone();
+        species.growth_rate = growth_rate;
+        species.base_exp = base_exp;
+        pokemon.species = Arc::new(species);
+        pokemon.level = level;
+        pokemon.exp = growth_rate.exp_for_level(level);
+        pokemon.status = Status::from_base_status(&pokemon.species.base_status, &pokemon.ivs, &pokemon.evs, pokemon.nature, level);
+
+        pokemon
+    }
+
+    /// 経験値タイプごとのレベル 1、50、100 に必要な累計の経験値
+    #[test]
+    fn growth_curves_match_reference_values() {
+        for (growth_rate, level_50, level_100) in [
+            (GrowthRate::Erratic, 125_000, 600_000),
+            (GrowthRate::Fast, 100_000, 800_000),
+            (GrowthRate::MediumFast, 125_000, 1_000_000),
+            (GrowthRate::MediumSlow, 117_360, 1_059_860),
+            (GrowthRate::Slow, 156_250, 1_250_000),
+            (GrowthRate::Fluctuating, 142_500, 1_640_000),
+        ] {
+            assert_eq!(growth_rate.exp_for_level(1), 0, "{:?}", growth_rate);
+            assert_eq!(growth_rate.exp_for_level(50), level_50, "{:?}", growth_rate);
+            assert_eq!(growth_rate.exp_for_level(100), level_100, "{:?}", growth_rate);
+        }
+    }
+
+    /// トレーナーのポケモンは 1.5 倍、戦ったポケモンの数で等分する
+    #[test]
+    fn exp_yield_scales_with_level_and_trainer() {
+        let defeated = pokemon_at(10, GrowthRate::MediumFast, 100);
+
+        assert_eq!(defeated.exp_yield(true, 1), 142);
+        assert_eq!(defeated.exp_yield(false, 1), 213);
+        assert_eq!(defeated.exp_yield(false, 2), 106);
+        assert_eq!(pokemon_at(1, GrowthRate::MediumFast, 1).exp_yield(true, 6), 1);
+    }
+
+    /// 1 回の経験値で複数のレベルが上がり、ステータスはそのレベルの実数値になる
+    #[test]
+    fn one_award_can_raise_several_levels() {
+        let mut pokemon = pokemon_at(5, GrowthRate::MediumFast, 100);
+        let old_hp = pokemon.status.hp.value;
+
+        pokemon.gain_exp(GrowthRate::MediumFast.exp_for_level(10) - pokemon.exp);
+        while pokemon.can_level_up() {
+            pokemon.level_up();
+        }
+
+        let expected = Status::from_base_status(&pokemon.species.base_status, &pokemon.ivs, &pokemon.evs, pokemon.nature, 10);
+        assert_eq!(pokemon.level, 10);
+        assert_eq!(pokemon.status.hp.value, expected.hp.value);
+        assert_eq!(pokemon.status.atk.value, expected.atk.value);
+        assert_eq!(pokemon.status.current_hp, expected.hp.value);
+        assert!(pokemon.status.hp.value > old_hp);
+    }
+
+    /// 経験値はレベル 100 に必要な値で止まり、それ以上レベルは上がらない
+    #[test]
+    fn exp_is_capped_at_max_level() {
+        let mut pokemon = pokemon_at(99, GrowthRate::Fast, 100);
+
+        pokemon.gain_exp(u32::MAX);
+        while pokemon.can_level_up() {
+            pokemon.level_up();
+        }
+
+        assert_eq!(pokemon.level, MAX_LEVEL);
+        assert_eq!(pokemon.exp, GrowthRate::Fast.exp_for_level(MAX_LEVEL));
+        assert!(!pokemon.can_level_up());
+    }
+}
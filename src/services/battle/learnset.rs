@@ -0,0 +1,69 @@
+use std::sync::Arc;
+
+use crate::models::pokemon::{LearnedSkill, Pokemon, Skill};
+
+/// 1 匹が覚えられる技の上限
+pub const MAX_SKILLS: usize = 4;
+
+pub trait LearnsetActions {
+    fn skills_learned_at(&self, level: u8) -> Vec<Arc<Skill>>;
+    fn knows_skill(&self, skill: &Skill) -> bool;
+    fn learn_skill(&mut self, skill: Arc<Skill>) -> bool;
+    fn replace_skill(&mut self, slot: usize, skill: Arc<Skill>) -> Option<Arc<Skill>>;
+}
+
+impl LearnsetActions for Pokemon {
+    /// レベルアップで覚える技を取得
+    ///
+    /// # 引数
+    /// * `level` - 上がったレベル
+    ///
+    /// # 戻り値
+    /// * `Vec<Arc<Skill>>` - そのレベルで覚える技（まだ覚えていない技のみ）
+    fn skills_learned_at(&self, level: u8) -> Vec<Arc<Skill>> {
+        self.species.learnset
+            .level_up
+            .iter()
+            .filter(|entry| entry.level == level && !self.knows_skill(&entry.skill))
+            .map(|entry| entry.skill.clone())
+            .collect()
+    }
+
+    /// 技を覚えているかを判定
+    fn knows_skill(&self, skill: &Skill) -> bool {
+        self.skills.iter().any(|learned| learned.skill.name == skill.name)
+    }
+
+    /// 空いている枠に技を覚える
+    ///
+    /// # 引数
+    /// * `skill` - 覚える技
+    ///
+    /// # 戻り値
+    /// * `bool` - 覚えたかどうか（すでに 4 つ覚えている場合は false）
+    fn learn_skill(&mut self, skill: Arc<Skill>) -> bool {
+        if self.skills.len() >= MAX_SKILLS {
+            return false;
+        }
+
+        self.skills.push(LearnedSkill::from_shared(skill));
+        true
+    }
+
+    /// 覚えている技を忘れさせて新しい技を覚える
+    ///
+    /// 新しい技は PP が満タンの状態で同じ位置に覚える
+    ///
+    /// # 引数
+    /// * `slot`  - 忘れる技のインデックス
+    /// * `skill` - 覚える技
+    ///
+    /// # 戻り値
+    /// * `Option<Arc<Skill>>` - 忘れた技、インデックスが範囲外の場合は None
+    fn replace_skill(&mut self, slot: usize, skill: Arc<Skill>) -> Option<Arc<Skill>> {
+        let learned = self.skills.get_mut(slot)?;
+        let forgotten = std::mem::replace(learned, LearnedSkill::from_shared(skill));
+
+        Some(forgotten.skill)
+    }
+}
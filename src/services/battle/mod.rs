@@ -1,3 +1,10 @@
 pub mod pokemon;
 pub mod trainer;
-pub mod element;
\ No newline at end of file
+pub mod element;
+pub mod status;
+pub mod ailment;
+pub mod experience;
+pub mod learnset;
+pub mod capture;
+pub mod event;
+pub mod engine;
\ No newline at end of file
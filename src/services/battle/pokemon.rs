@@ -1,124 +1,53 @@
-use std::{cmp::Ordering, thread, time::Duration};
+use std::{cmp::Ordering, fmt};
 
 use crate::{
-    cli::print::print_letter_with_delay,
-    logic::rand::XorShift128,
-    models::pokemon::{BufToStatus, Pokemon, Skill, SkillType, StatusType, Target}
+    logic::rand::RandomSource,
+    models::pokemon::{BufToStatus, Pokemon, Skill, SkillType, StatusAilment, StatusType, VolatileStatus}
 };
 
 #[allow(dead_code)]
 pub trait PokemonActions {
-    fn attack(&mut self, skill_idx: usize, target: &mut Pokemon);
-    fn compute_physical_damage(&self, skill: &Skill, attacker: &Pokemon, target: &Pokemon) -> u8;
-    fn compute_special_damage(&self, skill: &Skill, attacker: &Pokemon, target: &Pokemon) -> u8;
-    fn compute_status_buff(&self, status: &BufToStatus) -> u8;
-    fn is_hit(&self, accuracy: u8) -> bool;
-    fn apply_status_change(
-        &self,
-        target: &mut Pokemon,
-        status_type: StatusType,
-        effect_value: i8,
-        status_change_more: &str,
-        status_change: &str
-    );
-    fn get_battle_order(self_pokemon: Pokemon, enemy_pokemon: Pokemon) -> Vec<Pokemon>;
+    fn compute_damage(&self, skill: &Skill, target: &Pokemon, critical: bool, roll: u8) -> u16;
+    fn compute_physical_damage(&self, skill: &Skill, attacker: &Pokemon, target: &Pokemon, critical: bool, roll: u8) -> u16;
+    fn compute_special_damage(&self, skill: &Skill, attacker: &Pokemon, target: &Pokemon, critical: bool, roll: u8) -> u16;
+    fn apply_damage_modifiers(&self, dmg: f32, skill: &Skill, target: &Pokemon, critical: bool, roll: u8) -> u16;
+    fn compute_status_buff(&self, status: &BufToStatus) -> u16;
+    fn is_hit(&self, accuracy: u8, rng: &mut dyn RandomSource) -> bool;
+    fn is_critical(&self, skill: &Skill, rng: &mut dyn RandomSource) -> bool;
+    fn critical_chance(&self, skill: &Skill) -> f64;
+    fn apply_status_change(&mut self, status_type: StatusType, effect_value: i8) -> Option<i8>;
+    fn effective_speed(&self) -> u16;
+    fn moves_first(&self, skill_idx: usize, enemy: &Pokemon, enemy_skill_idx: usize, rng: &mut dyn RandomSource) -> bool;
+    fn has_usable_skill(&self) -> bool;
+    fn reset_battle_state(&mut self);
+    fn heal(&mut self);
 }
 
-impl PokemonActions for Pokemon {
-    /// 攻撃
-    /// 
-    /// # 引数
-    /// * `skill_idx` - 技リストのインデックス
-    /// * `target`    - 攻撃するターゲット
-    fn attack(&mut self, skill_idx: usize, target: &mut Pokemon) {
-        let skill = match self.skills.get(skill_idx) {
-            Some(skill) => skill.clone(),
-            None => {
-                eprintln!("Error: Invalid skill index {}", skill_idx);
-                return;
-            }
-        };
-
-        // テキスト演出
-        print_letter_with_delay(&format!("{}の\n{}！", self.name, skill.name));
-        thread::sleep(Duration::from_millis(1000));
+/// ダメージの乱数の最小値（%）
+pub const MIN_DAMAGE_ROLL: u8 = 85;
 
-        // 命中確率を計算
-        if !self.is_hit(skill.accuracy) {
-            print_letter_with_delay(&format!("{}には\n当たらなかった！", target.name));
-            thread::sleep(Duration::from_millis(1000));
-            return;
-        }
+/// ダメージの乱数の最大値（%）
+pub const MAX_DAMAGE_ROLL: u8 = 100;
 
-        // 技の種類を判定
-        match skill.class {
-            SkillType::PhysicalAttack => {
-                let damage = self.compute_physical_damage(&skill, self, target);
-                target.status.current_hp = target.status.current_hp.saturating_sub(damage);
-            }
-            SkillType::SpecialAttack => {
-                let damage = self.compute_special_damage(&skill, self, target);
-                target.status.current_hp = target.status.current_hp.saturating_sub(damage);
-            }
-            SkillType::ChangeStatus => {
-                if let Some(skill_effect) = skill.skill_effect {
-                    match skill_effect.target {
-                        Target::Self_ => {
-                            let status_change = if skill_effect.status_effect.effect_value > 0 {
-                                "あがった"
-                            } else {
-                                "さがった"
-                            };
-                            let status_change_more =
-                                if skill_effect.status_effect.effect_value.abs() == 2 {
-                                    "ぐーんと"
-                                } else {
-                                    ""
-                                };
-                            self.apply_status_change(
-                                target,
-                                skill_effect.status_effect.target,
-                                skill_effect.status_effect.effect_value,
-                                status_change_more,
-                                status_change
-                            )
-                        }
-                        Target::Enemy => {
-                            let status_change = if skill_effect.status_effect.effect_value > 0 {
-                                "あがった"
-                            } else {
-                                "さがった"
-                            };
-    
-                            let status_change_more =
-                                if skill_effect.status_effect.effect_value.abs() == 2 {
-                                    "ぐーんと"
-                                } else {
-                                    ""
-                                };
-                            self.apply_status_change(
-                                target,
-                                skill_effect.status_effect.target,
-                                skill_effect.status_effect.effect_value,
-                                status_change_more,
-                                status_change
-                            )
-                        }
-                        Target::Ally => {
-                            // TODO
-                        }
-                    }
-                } else {
-                    eprintln!("Error: Skill effect is missing for skill: {}", skill.name);
-                    return;
-                }
-            }
-            SkillType::OneHitKO => {
-                print_letter_with_delay("一撃必殺！");
-                target.status.current_hp = 0;
-            }
+impl PokemonActions for Pokemon {
+    /// 技の種類に応じてダメージを計算
+    ///
+    /// 特殊攻撃は特攻と特防、それ以外は攻撃と防御で計算する
+    ///
+    /// # 引数
+    /// * `skill`    - 使用する技
+    /// * `target`   - 攻撃するターゲット
+    /// * `critical` - 急所に当たったかどうか
+    /// * `roll`     - ダメージの乱数（85 〜 100）
+    ///
+    /// # 戻り値
+    /// * `u16` - 与えるダメージ
+    fn compute_damage(&self, skill: &Skill, target: &Pokemon, critical: bool, roll: u8) -> u16 {
+        if skill.class == SkillType::SpecialAttack {
+            self.compute_special_damage(skill, self, target, critical, roll)
+        } else {
+            self.compute_physical_damage(skill, self, target, critical, roll)
         }
-        thread::sleep(Duration::from_millis(1000));
     }
 
     /// 物理攻撃を計算
@@ -127,24 +56,30 @@ impl PokemonActions for Pokemon {
     /// * `skill`    - 使用する技
     /// * `attacker` - 攻撃するポケモン
     /// * `target`   - 攻撃するターゲット
+    /// * `critical` - 急所に当たったかどうか
+    /// * `roll`     - ダメージの乱数（85 〜 100）
     /// 
     /// # 戻り値
     /// * `dmg`      - 与えるダメージ
-    fn compute_physical_damage(&self, skill: &Skill, attacker: &Pokemon, target: &Pokemon) -> u8 {
+    fn compute_physical_damage(&self, skill: &Skill, attacker: &Pokemon, target: &Pokemon, critical: bool, roll: u8) -> u16 {
         let mut dmg = attacker.level as f32 * 2.0 / 5.0 + 2.0;
         dmg = dmg.floor();
 
-        let atk = self.compute_status_buff(&attacker.status.atk) as f32;
-        let def = self.compute_status_buff(&target.status.def) as f32;
+        // 急所の場合は攻撃側のランク低下と防御側のランク上昇を無視
+        let (atk, def) = if critical {
+            (attacker.status.atk.without_debuff(), target.status.def.without_buff())
+        } else {
+            (attacker.status.atk.clone(), target.status.def.clone())
+        };
+        let atk = self.compute_status_buff(&atk) as f32;
+        let def = self.compute_status_buff(&def) as f32;
 
         dmg = dmg * skill.base_atk as f32 * atk / def;
         dmg = dmg.floor();
         dmg = dmg / 50.0 + 2.0;
         dmg = dmg.floor();
 
-        // let rand = (1 / xor_shift_rand(42)) as f32;
-
-        dmg as u8
+        self.apply_damage_modifiers(dmg, skill, target, critical, roll)
     }
 
     /// 特殊攻撃を計算
@@ -153,161 +88,387 @@ impl PokemonActions for Pokemon {
     /// * `skill`    - 使用する技
     /// * `attacker` - 攻撃するポケモン
     /// * `target`   - 攻撃するターゲット
+    /// * `critical` - 急所に当たったかどうか
+    /// * `roll`     - ダメージの乱数（85 〜 100）
     /// 
     /// # 戻り値
     /// * `dmg`      - 与えるダメージ
-    fn compute_special_damage(&self, skill: &Skill, attacker: &Pokemon, target: &Pokemon) -> u8 {
+    fn compute_special_damage(&self, skill: &Skill, attacker: &Pokemon, target: &Pokemon, critical: bool, roll: u8) -> u16 {
         let mut dmg = attacker.level as f32 * 2.0 / 5.0 + 2.0;
         dmg = dmg.floor();
 
-        let sp_atk = self.compute_status_buff(&attacker.status.sp_atk) as f32;
-        let sp_def = self.compute_status_buff(&target.status.sp_def) as f32;
+        // 急所の場合は攻撃側のランク低下と防御側のランク上昇を無視
+        let (sp_atk, sp_def) = if critical {
+            (attacker.status.sp_atk.without_debuff(), target.status.sp_def.without_buff())
+        } else {
+            (attacker.status.sp_atk.clone(), target.status.sp_def.clone())
+        };
+        let sp_atk = self.compute_status_buff(&sp_atk) as f32;
+        let sp_def = self.compute_status_buff(&sp_def) as f32;
 
         dmg = dmg * skill.base_atk as f32 * sp_atk / sp_def;
         dmg = dmg.floor();
         dmg = dmg / 50.0 + 2.0;
         dmg = dmg.floor();
 
-        // let rand = (1 / xor_shift_rand(42)) as f32;
+        self.apply_damage_modifiers(dmg, skill, target, critical, roll)
+    }
+
+    /// ダメージ補正を適用
+    ///
+    /// 急所、乱数、タイプ一致、タイプ相性、やけどの順に補正をかける
+    ///
+    /// # 引数
+    /// * `dmg`      - 補正前のダメージ
+    /// * `skill`    - 使用する技
+    /// * `target`   - 攻撃するターゲット
+    /// * `critical` - 急所に当たったかどうか
+    /// * `roll`     - ダメージの乱数（85 〜 100）
+    ///
+    /// # 戻り値
+    /// * `dmg`      - 補正後のダメージ
+    fn apply_damage_modifiers(&self, mut dmg: f32, skill: &Skill, target: &Pokemon, critical: bool, roll: u8) -> u16 {
+        // 急所
+        if critical {
+            dmg = (dmg * 1.5).floor();
+        }
+
+        // 乱数（85% 〜 100%）
+        dmg = (dmg * roll as f32 / 100.0).floor();
+
+        // わるあがきはタイプ一致、タイプ相性の影響を受けない
+        if skill.class != SkillType::Struggle {
+            // タイプ一致
+            if self.species.element.contains(&skill.element) {
+                dmg = (dmg * 1.5).floor();
+            }
+
+            // タイプ相性
+            dmg = (dmg * target.type_effectiveness(skill.element)).floor();
+        }
+
+        // やけど状態の物理技は半減
+        if skill.class == SkillType::PhysicalAttack && self.ailment == Some(StatusAilment::Burn) {
+            dmg = (dmg * 0.5).floor();
+        }
 
-        dmg as u8
+        dmg.max(1.0) as u16
     }
 
     /// ステータスバフを計算
     /// 
     /// # 引数
-    /// * `status` - 使用する技
+    /// * `status` - 対象のステータス
     /// 
     /// # 戻り値
-    /// * `(status.value as f64 * rate)` - バフの実数値
-    fn compute_status_buff(&self, status: &BufToStatus) -> u8 {
-        if status.buff < -6 {
-            return status.value * 2 / 8;
-        }
-
-        if status.buff > 6 {
-            return status.value * 8 / 2;
-        }
+    /// * `u16` - ランク補正を適用した実数値
+    fn compute_status_buff(&self, status: &BufToStatus) -> u16 {
+        // ランクは -6 〜 +6 の範囲で扱う
+        let buff = status.buff.clamp(-6, 6) as i32;
 
-        let rate = match status.buff {
-            -6 => 2.0 / 8.0,
-            -5 => 2.0 / 7.0,
-            -4 => 2.0 / 6.0,
-            -3 => 2.0 / 5.0,
-            -2 => 2.0 / 4.0,
-            -1 => 2.0 / 3.0,
-            0 => 2.0 / 2.0,
-            1 => 3.0 / 2.0,
-            2 => 4.0 / 2.0,
-            3 => 5.0 / 2.0,
-            4 => 6.0 / 2.0,
-            5 => 7.0 / 2.0,
-            6 => 8.0 / 2.0,
-            _ => unreachable!(),
-        };
+        // +n: (2 + n) / 2、-n: 2 / (2 + n)
+        let numerator = (2 + buff).max(2) as u32;
+        let denominator = (2 - buff).max(2) as u32;
 
-        (status.value as f64 * rate) as u8
+        (status.value as u32 * numerator / denominator) as u16
     }
 
     /// 命中確率を計算
     /// 
     /// # 引数
     /// * `accuracy` - 命中率
+    /// * `rng`      - 乱数生成器
     /// 
     /// # 戻り値
-    /// * `rand_num < accuracy as u64` - 技が当たったかどうか
-    fn is_hit(&self, accuracy: u8) -> bool {
-        let rand_num = XorShift128::random_in_range(0, 100);
-        rand_num < accuracy as u64
+    /// * `bool` - 技が当たったかどうか
+    fn is_hit(&self, accuracy: u8, rng: &mut dyn RandomSource) -> bool {
+        rng.random_ratio(accuracy as u64, 100)
     }
 
-    fn apply_status_change(
-        &self,
-        target: &mut Pokemon,
-        status_type: StatusType,
-        effect_value: i8,
-        status_change_more: &str,
-        status_change: &str
-    ) {
-        // ステータス変更
-        match status_type {
+    /// 急所に当たったかを判定
+    ///
+    /// 技の急所ランクとポケモン自身の急所ランクの合計が 0: 1/24、1: 1/8、2: 1/2、3以上: 確定
+    ///
+    /// # 引数
+    /// * `skill` - 使用する技
+    /// * `rng`   - 乱数生成器
+    ///
+    /// # 戻り値
+    /// * `bool` - 急所に当たったかどうか
+    fn is_critical(&self, skill: &Skill, rng: &mut dyn RandomSource) -> bool {
+        match critical_denominator(skill.critical_rank.saturating_add(self.volatile.critical_stage)) {
+            Some(denominator) => rng.random_ratio(1, denominator),
+            None => true,
+        }
+    }
+
+    /// 急所に当たる確率を計算
+    ///
+    /// # 引数
+    /// * `skill` - 使用する技
+    ///
+    /// # 戻り値
+    /// * `f64` - 急所に当たる確率（0.0 〜 1.0）
+    fn critical_chance(&self, skill: &Skill) -> f64 {
+        match critical_denominator(skill.critical_rank.saturating_add(self.volatile.critical_stage)) {
+            Some(denominator) => 1.0 / denominator as f64,
+            None => 1.0,
+        }
+    }
+
+    /// ステータスのランクを変更
+    ///
+    /// ランクは -6 〜 +6 の範囲に収める
+    ///
+    /// # 引数
+    /// * `status_type`  - 変更するステータス
+    /// * `effect_value` - ランクの変化量
+    ///
+    /// # 戻り値
+    /// * `Option<i8>` - 実際に変化したランク、ランクを持たないステータスの場合は None
+    fn apply_status_change(&mut self, status_type: StatusType, effect_value: i8) -> Option<i8> {
+        let status = match status_type {
             StatusType::Hp => {
                 unreachable!()
             }
-            StatusType::Atk => {
-                target.status.atk.buff += effect_value;
-            }
-            StatusType::Def => {
-                target.status.def.buff += effect_value;
-            }
-            StatusType::SpAtk => {
-                target.status.sp_atk.buff += effect_value;
-            }
-            StatusType::SpDef => {
-                target.status.sp_def.buff += effect_value;
-            }
-            StatusType::Spd => {
-                target.status.spd.buff += effect_value;
-            }
-            StatusType::Field => {
-                todo!()
-            }
+            StatusType::Atk => &mut self.status.atk,
+            StatusType::Def => &mut self.status.def,
+            StatusType::SpAtk => &mut self.status.sp_atk,
+            StatusType::SpDef => &mut self.status.sp_def,
+            StatusType::Spd => &mut self.status.spd,
+            // TODO 天候・フィールドは未実装
+            StatusType::Field => return None,
+        };
+
+        let before = status.buff;
+        status.buff = (status.buff + effect_value).clamp(-6, 6);
+
+        Some(status.buff - before)
+    }
+
+    /// ランク補正と状態異常を適用したすばやさを計算
+    ///
+    /// # 戻り値
+    /// * `u16` - 行動順の判定に使用するすばやさ
+    fn effective_speed(&self) -> u16 {
+        let spd = self.compute_status_buff(&self.status.spd);
+
+        // まひ状態のすばやさは半減
+        if self.ailment == Some(StatusAilment::Paralysis) {
+            spd / 2
+        } else {
+            spd
         }
+    }
 
-        // 変更メッセージの表示
-        print_letter_with_delay(&format!(
-            "{}の{}が{}{}",
-            target.name, 
-            status_type.to_string(),
-            status_change_more, 
-            status_change
-        ));
+    /// PP が残っている技があるかを判定
+    ///
+    /// # 戻り値
+    /// * `bool` - 使用できる技があるかどうか
+    fn has_usable_skill(&self) -> bool {
+        self.skills.iter().any(|s| s.current_pp > 0)
     }
 
-    /// ポケモンの行動順を設定
-    /// 
+    /// 交代で引っ込めたときにランク変化と一時的な状態を解除
+    ///
+    /// もうどくの経過ターン数も初期化する
+    fn reset_battle_state(&mut self) {
+        for status in [
+            &mut self.status.atk,
+            &mut self.status.def,
+            &mut self.status.sp_atk,
+            &mut self.status.sp_def,
+            &mut self.status.spd,
+        ] {
+            status.buff = 0;
+        }
+        self.volatile = VolatileStatus::default();
+
+        if self.ailment == Some(StatusAilment::BadPoison) {
+            self.ailment_turns = 1;
+        }
+    }
+
+    /// HP、PP、状態異常を全回復
+    ///
+    /// ひんしのポケモンも回復する
+    fn heal(&mut self) {
+        self.reset_battle_state();
+        self.status.current_hp = self.status.hp.value;
+        self.ailment = None;
+        self.ailment_turns = 0;
+        for skill in &mut self.skills {
+            skill.current_pp = skill.max_pp;
+        }
+    }
+
+    /// ポケモンの行動順を判定
+    ///
+    /// 選択した技の優先度、すばやさの順に比較し、同速の場合はランダムに決定
+    ///
     /// # 引数
-    /// * `self_pokemon`  - 自分のポケモン
-    /// * `enemy_pokemon` - 敵のポケモン
-    /// 
+    /// * `skill_idx`       - 自分が選択した技のインデックス
+    /// * `enemy`           - 敵のポケモン
+    /// * `enemy_skill_idx` - 敵が選択した技のインデックス
+    /// * `rng`             - 乱数生成器
+    ///
     /// # 戻り値
-    /// * `pokemons` - 自分のポケモンと敵のポケモンのリスト
-    fn get_battle_order(self_pokemon: Pokemon, enemy_pokemon: Pokemon) -> Vec<Pokemon> {
-        let mut pokemons = vec![self_pokemon, enemy_pokemon];
-    
-        pokemons.sort_by(|a, b| {
-            // すばやさでソート、同じなら技の優先度で比較
-            let spd_comparison = b.status.spd.value.cmp(&a.status.spd.value);
-            if spd_comparison == Ordering::Equal {
-                // // すばやさが同じなら技の優先度を比較
-                // b.skills.iter().map(|s| s.priority).max().cmp(
-                //     &a.skills.iter().map(|s| s.priority).max(),
-                // )
-
-                let max_priority_a = a.skills.iter().map(|s| s.priority).max().unwrap_or(0);
-                let max_priority_b = b.skills.iter().map(|s| s.priority).max().unwrap_or(0);
-                
-                max_priority_b.cmp(&max_priority_a)
-            } else {
-                spd_comparison
-            }
-        });
-    
-        pokemons
+    /// * `bool` - 自分が先に行動するかどうか
+    fn moves_first(&self, skill_idx: usize, enemy: &Pokemon, enemy_skill_idx: usize, rng: &mut dyn RandomSource) -> bool {
+        // わるあがきの優先度は 0
+        let priority_of = |pokemon: &Pokemon, idx: usize| match pokemon.has_usable_skill() {
+            true => pokemon.skills.get(idx).map_or(0, |s| s.skill.priority),
+            false => 0,
+        };
+        let priority = priority_of(self, skill_idx);
+        let enemy_priority = priority_of(enemy, enemy_skill_idx);
+
+        match priority
+            .cmp(&enemy_priority)
+            .then_with(|| self.effective_speed().cmp(&enemy.effective_speed()))
+        {
+            Ordering::Greater => true,
+            Ordering::Less => false,
+            // 同速の場合はランダム
+            Ordering::Equal => rng.random_bool(0.5),
+        }
+    }
+}
+
+/// 急所ランクから急所に当たる確率の分母を取得
+///
+/// # 引数
+/// * `critical_rank` - 急所ランク
+///
+/// # 戻り値
+/// * `Option<u64>` - 確率の分母、確定で急所に当たる場合は None
+fn critical_denominator(critical_rank: u8) -> Option<u64> {
+    match critical_rank {
+        0 => Some(24),
+        1 => Some(8),
+        2 => Some(2),
+        _ => None,
+    }
+}
+
+impl BufToStatus {
+    /// ランク低下を無視したステータスを取得
+    pub fn without_debuff(&self) -> BufToStatus {
+        BufToStatus { value: self.value, buff: self.buff.max(0) }
+    }
+
+    /// ランク上昇を無視したステータスを取得
+    pub fn without_buff(&self) -> BufToStatus {
+        BufToStatus { value: self.value, buff: self.buff.min(0) }
     }
 }
 
 /// StatusTypeの列挙子に応じてステータス名を返す
-impl ToString for StatusType {
-    fn to_string(&self) -> String {
-        match self {
-            StatusType::Hp => "HP".to_string(),
-            StatusType::Atk => "こうげき".to_string(),
-            StatusType::Def => "ぼうぎょ".to_string(),
-            StatusType::SpAtk => "とくこう".to_string(),
-            StatusType::SpDef => "とくぼう".to_string(),
-            StatusType::Spd => "すばやさ".to_string(),
-            StatusType::Field => "フィールド技".to_string(),
-        }
+impl fmt::Display for StatusType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::{file::json::fixture::trainer, logic::rand::XorShift128, models::pokemon::ElementType};
+
+    /// 指定したタイプのポケモンを作成
+    fn pokemon_of(element: &[ElementType]) -> Pokemon {
+        let mut pokemon = trainer("wataru").pokemons[0].clone();
+        let mut species = (*pokemon.species).clone();
+        species.element = element.to_vec();
+        pokemon.species = Arc::new(species);
+        pokemon.ailment = None;
+        pokemon.volatile = VolatileStatus::default();
+
+        pokemon
+    }
+
+    /// 指定したタイプと分類の技を作成
+    fn skill_of(element: ElementType, class: SkillType) -> Skill {
+        let mut skill = (*trainer("wataru").pokemons[0].skills[0].skill).clone();
+        skill.element = element;
+        skill.class = class;
+        skill.critical_rank = 0;
+
+        skill
+    }
+
+    /// 補正なしの場合はそのままのダメージ
+    #[test]
+    fn modifiers_keep_neutral_damage() {
+        let attacker = pokemon_of(&[ElementType::Normal]);
+        let target = pokemon_of(&[ElementType::Normal]);
+        let skill = skill_of(ElementType::Fire, SkillType::PhysicalAttack);
+
+        assert_eq!(attacker.apply_damage_modifiers(100.0, &skill, &target, false, MAX_DAMAGE_ROLL), 100);
+    }
+
+    /// 急所 → 乱数 → タイプ一致 → タイプ相性 → やけどの順に切り捨てながら掛ける
+    #[test]
+    fn modifiers_apply_in_order() {
+        let mut attacker = pokemon_of(&[ElementType::Fire]);
+        attacker.ailment = Some(StatusAilment::Burn);
+        let target = pokemon_of(&[ElementType::Grass]);
+        let skill = skill_of(ElementType::Fire, SkillType::PhysicalAttack);
+
+        // 100 → 150 → 127 → 190 → 380 → 190（乱数をタイプ一致の後に掛けると 191 になる）
+        assert_eq!(attacker.apply_damage_modifiers(100.0, &skill, &target, true, MIN_DAMAGE_ROLL), 190);
+
+        // やけどは特殊技を半減しない
+        let special = skill_of(ElementType::Fire, SkillType::SpecialAttack);
+        assert_eq!(attacker.apply_damage_modifiers(100.0, &special, &target, true, MIN_DAMAGE_ROLL), 380);
+    }
+
+    /// 補正で 0 になってもダメージは最低 1
+    #[test]
+    fn modifiers_leave_at_least_one_damage() {
+        let attacker = pokemon_of(&[ElementType::Normal]);
+        let target = pokemon_of(&[ElementType::Poison, ElementType::Flying]);
+        let skill = skill_of(ElementType::Fighting, SkillType::PhysicalAttack);
+
+        // 2 → 1 → 0（いまひとつ × 2）→ 1
+        assert_eq!(attacker.apply_damage_modifiers(2.0, &skill, &target, false, MIN_DAMAGE_ROLL), 1);
+    }
+
+    /// わるあがきはタイプ一致とタイプ相性の影響を受けない
+    #[test]
+    fn struggle_ignores_type_modifiers() {
+        let attacker = pokemon_of(&[ElementType::Normal]);
+        let target = pokemon_of(&[ElementType::Rock]);
+        let skill = skill_of(ElementType::Normal, SkillType::Struggle);
+
+        assert_eq!(attacker.apply_damage_modifiers(100.0, &skill, &target, false, MAX_DAMAGE_ROLL), 100);
+    }
+
+    /// 急所ランク 0 〜 3 の確率（1/24、1/8、1/2、確定）
+    #[test]
+    fn critical_stages() {
+        assert_eq!(critical_denominator(0), Some(24));
+        assert_eq!(critical_denominator(1), Some(8));
+        assert_eq!(critical_denominator(2), Some(2));
+        assert_eq!(critical_denominator(3), None);
+        assert_eq!(critical_denominator(u8::MAX), None);
+    }
+
+    /// 技の急所ランクと一時的な急所ランクを足し合わせる
+    #[test]
+    fn critical_rank_adds_volatile_stage() {
+        let mut attacker = pokemon_of(&[ElementType::Normal]);
+        let mut skill = skill_of(ElementType::Normal, SkillType::PhysicalAttack);
+        skill.critical_rank = 1;
+
+        assert_eq!(attacker.critical_chance(&skill), 1.0 / 8.0);
+        attacker.volatile.critical_stage = 2;
+        assert_eq!(attacker.critical_chance(&skill), 1.0);
+
+        // 確定の場合は乱数を使わずに急所に当たる
+        let mut rng = XorShift128::from_seed(0);
+        assert!((0..10).all(|_| attacker.is_critical(&skill, &mut rng)));
     }
 }
@@ -0,0 +1,131 @@
+use crate::models::pokemon::{BufToStatus, Nature, Status, StatusPoints, StatusType};
+
+impl Nature {
+    /// 性格によって上昇するステータスと下降するステータスを取得
+    ///
+    /// # 戻り値
+    /// * `Option<(StatusType, StatusType)>` - (上昇するステータス, 下降するステータス)、無補正の性格は None
+    pub fn modified_status(&self) -> Option<(StatusType, StatusType)> {
+        use StatusType::*;
+
+        match self {
+            Nature::Lonely => Some((Atk, Def)),
+            Nature::Brave => Some((Atk, Spd)),
+            Nature::Adamant => Some((Atk, SpAtk)),
+            Nature::Naughty => Some((Atk, SpDef)),
+            Nature::Bold => Some((Def, Atk)),
+            Nature::Relaxed => Some((Def, Spd)),
+            Nature::Impish => Some((Def, SpAtk)),
+            Nature::Lax => Some((Def, SpDef)),
+            Nature::Timid => Some((Spd, Atk)),
+            Nature::Hasty => Some((Spd, Def)),
+            Nature::Jolly => Some((Spd, SpAtk)),
+            Nature::Naive => Some((Spd, SpDef)),
+            Nature::Modest => Some((SpAtk, Atk)),
+            Nature::Mild => Some((SpAtk, Def)),
+            Nature::Quiet => Some((SpAtk, Spd)),
+            Nature::Rash => Some((SpAtk, SpDef)),
+            Nature::Calm => Some((SpDef, Atk)),
+            Nature::Gentle => Some((SpDef, Def)),
+            Nature::Sassy => Some((SpDef, Spd)),
+            Nature::Careful => Some((SpDef, SpAtk)),
+            Nature::Hardy | Nature::Docile | Nature::Serious | Nature::Bashful | Nature::Quirky => None,
+        }
+    }
+
+    /// 性格によるステータス補正の倍率を取得
+    ///
+    /// # 引数
+    /// * `status_type` - 対象のステータス
+    ///
+    /// # 戻り値
+    /// * `u32` - 百分率で表した倍率（110、100、90）
+    pub fn modifier(&self, status_type: StatusType) -> u32 {
+        match self.modified_status() {
+            Some((up, _)) if up == status_type => 110,
+            Some((_, down)) if down == status_type => 90,
+            _ => 100,
+        }
+    }
+}
+
+impl Status {
+    /// 種族値、個体値、努力値、性格、レベルからステータスの実数値を計算
+    ///
+    /// # 引数
+    /// * `base`   - 種族値
+    /// * `ivs`    - 個体値
+    /// * `evs`    - 努力値
+    /// * `nature` - 性格
+    /// * `level`  - レベル
+    ///
+    /// # 戻り値
+    /// * `Status` - 計算したステータス（HPは満タン）
+    pub fn from_base_status(base: &StatusPoints, ivs: &StatusPoints, evs: &StatusPoints, nature: Nature, level: u8) -> Status {
+        let level = level as u32;
+        let core = |b: u8, i: u8, e: u8| (2 * b as u32 + i as u32 + e as u32 / 4) * level / 100;
+
+        let hp = (core(base.hp, ivs.hp, evs.hp) + level + 10) as u16;
+        let other = |b: u8, i: u8, e: u8, status_type: StatusType| {
+            let value = (core(b, i, e) + 5) * nature.modifier(status_type) / 100;
+            BufToStatus { value: value as u16, buff: 0 }
+        };
+
+        Status {
+            hp: BufToStatus { value: hp, buff: 0 },
+            atk: other(base.atk, ivs.atk, evs.atk, StatusType::Atk),
+            def: other(base.def, ivs.def, evs.def, StatusType::Def),
+            sp_atk: other(base.sp_atk, ivs.sp_atk, evs.sp_atk, StatusType::SpAtk),
+            sp_def: other(base.sp_def, ivs.sp_def, evs.sp_def, StatusType::SpDef),
+            spd: other(base.spd, ivs.spd, evs.spd, StatusType::Spd),
+            current_hp: hp,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// ガブリアス（レベル 78、いじっぱり）の実数値が本家の計算例と一致する
+    #[test]
+    fn stats_match_reference_values() {
+        let base = StatusPoints { hp: 108, atk: 130, def: 95, sp_atk: 80, sp_def: 85, spd: 102 };
+        let ivs = StatusPoints { hp: 24, atk: 12, def: 30, sp_atk: 16, sp_def: 23, spd: 5 };
+        let evs = StatusPoints { hp: 74, atk: 190, def: 91, sp_atk: 48, sp_def: 84, spd: 23 };
+
+        let status = Status::from_base_status(&base, &ivs, &evs, Nature::Adamant, 78);
+
+        assert_eq!(status.hp.value, 289);
+        assert_eq!(status.atk.value, 278);
+        assert_eq!(status.def.value, 193);
+        assert_eq!(status.sp_atk.value, 135);
+        assert_eq!(status.sp_def.value, 171);
+        assert_eq!(status.spd.value, 171);
+        assert_eq!(status.current_hp, 289);
+    }
+
+    /// 無補正の性格、個体値最大、努力値なしのレベル 100 とレベル 1
+    #[test]
+    fn stats_at_level_bounds() {
+        let base = StatusPoints { hp: 100, atk: 100, def: 100, sp_atk: 100, sp_def: 100, spd: 100 };
+        let evs = StatusPoints::default();
+
+        let status = Status::from_base_status(&base, &StatusPoints::max_ivs(), &evs, Nature::Hardy, 100);
+        assert_eq!(status.hp.value, 341);
+        assert_eq!(status.atk.value, 236);
+
+        let status = Status::from_base_status(&base, &StatusPoints::max_ivs(), &evs, Nature::Hardy, 1);
+        assert_eq!(status.hp.value, 13);
+        assert_eq!(status.atk.value, 7);
+    }
+
+    /// 性格で上がるステータスは 1.1 倍、下がるステータスは 0.9 倍
+    #[test]
+    fn nature_modifies_stats() {
+        assert_eq!(Nature::Modest.modifier(StatusType::SpAtk), 110);
+        assert_eq!(Nature::Modest.modifier(StatusType::Atk), 90);
+        assert_eq!(Nature::Modest.modifier(StatusType::Spd), 100);
+        assert_eq!(Nature::Hardy.modifier(StatusType::Atk), 100);
+    }
+}
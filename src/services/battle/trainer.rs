@@ -1,25 +1,172 @@
-use crate::models::{pokemon::Pokemon, trainer::Trainer};
+use crate::{
+    models::{pokemon::Pokemon, trainer::{AiLevel, SearchConfig, Trainer}},
+    services::battle::pokemon::PokemonActions
+};
+
+/// 手持ちの上限
+pub const MAX_PARTY: usize = 6;
+
+/// 捕まえたポケモンを加えた場所
+///
+/// 手持ち、ボックスで構成
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaughtPlace {
+    Party,
+    Storage,
+}
 
 #[allow(dead_code)]
 pub trait TrainerActions {
-    fn new(name: &'static str, pokemons: Vec<Pokemon>) -> Self;
-    fn set_active_pokemon(&mut self, pokemon: Pokemon);
+    fn new(id: &str, name: &str, pokemons: Vec<Pokemon>) -> Self;
+    fn set_active_pokemon(&mut self, idx: usize);
+    fn active_pokemon(&self) -> &Pokemon;
+    fn active_pokemon_mut(&mut self) -> &mut Pokemon;
+    fn can_switch_to(&self, idx: usize) -> bool;
+    fn switch_pokemon(&mut self, idx: usize);
+    fn next_usable_pokemon(&self) -> Option<usize>;
+    fn is_defeated(&self) -> bool;
+    fn heal_party(&mut self);
+    fn add_caught(&mut self, pokemon: Pokemon, storage: &mut Vec<Pokemon>) -> CaughtPlace;
 }
 
 impl TrainerActions for Trainer {
-    fn new(name: &'static str, pokemons: Vec<Pokemon>) -> Self {
+    fn new(id: &str, name: &str, pokemons: Vec<Pokemon>) -> Self {
         Trainer {
-            name,
+            id: id.to_string(),
+            name: name.to_string(),
             pokemons,
-            active_pokemon: None,
+            active_idx: 0,
+            ai: AiLevel::default(),
+            search: SearchConfig::default(),
         }
     }
 
     /// 現在使用しているポケモンを設定
     /// 
     /// # 引数
-    /// * `pokemon` - ポケモン
-    fn set_active_pokemon(&mut self, pokemon: Pokemon) {
-        self.active_pokemon = Some(pokemon);
+    /// * `idx` - 手持ちのインデックス
+    fn set_active_pokemon(&mut self, idx: usize) {
+        self.active_idx = idx;
+    }
+
+    /// 場に出しているポケモンを取得
+    fn active_pokemon(&self) -> &Pokemon {
+        &self.pokemons[self.active_idx]
+    }
+
+    /// 場に出しているポケモンを可変参照で取得
+    fn active_pokemon_mut(&mut self) -> &mut Pokemon {
+        &mut self.pokemons[self.active_idx]
+    }
+
+    /// 交代先として選べるかを判定
+    ///
+    /// # 引数
+    /// * `idx` - 手持ちのインデックス
+    ///
+    /// # 戻り値
+    /// * `bool` - 場に出ておらず、ひんしでなければ true
+    fn can_switch_to(&self, idx: usize) -> bool {
+        idx != self.active_idx
+            && self.pokemons.get(idx).is_some_and(|p| p.status.current_hp > 0)
     }
-}
\ No newline at end of file
+
+    /// ポケモンを交代
+    ///
+    /// 引っ込めたポケモンのランク変化と一時的な状態は解除される
+    ///
+    /// # 引数
+    /// * `idx` - 交代先の手持ちのインデックス
+    fn switch_pokemon(&mut self, idx: usize) {
+        self.active_pokemon_mut().reset_battle_state();
+        self.active_idx = idx;
+    }
+
+    /// 次に出せるポケモンを手持ちの並び順で取得
+    ///
+    /// # 戻り値
+    /// * `Option<usize>` - 戦えるポケモンのインデックス、いなければ None
+    fn next_usable_pokemon(&self) -> Option<usize> {
+        self.pokemons.iter().position(|p| p.status.current_hp > 0)
+    }
+
+    /// 手持ちのポケモンが全員ひんしかを判定
+    fn is_defeated(&self) -> bool {
+        self.pokemons.iter().all(|p| p.status.current_hp == 0)
+    }
+
+    /// 手持ちのポケモンを全員全回復
+    fn heal_party(&mut self) {
+        for pokemon in &mut self.pokemons {
+            pokemon.heal();
+        }
+    }
+
+    /// 捕まえたポケモンを手持ちに加える
+    ///
+    /// 手持ちがいっぱいの場合はボックスに預ける
+    /// 個体の ID は手持ちとボックスのポケモンのうち最も大きい ID の次を割り当てる
+    ///
+    /// # 引数
+    /// * `pokemon` - 捕まえたポケモン
+    /// * `storage` - セーブデータのボックス
+    ///
+    /// # 戻り値
+    /// * `CaughtPlace` - 加えた場所
+    fn add_caught(&mut self, mut pokemon: Pokemon, storage: &mut Vec<Pokemon>) -> CaughtPlace {
+        pokemon.reset_battle_state();
+        pokemon.uid = self.pokemons
+            .iter()
+            .chain(storage.iter())
+            .map(|owned| owned.uid)
+            .max()
+            .unwrap_or(0) + 1;
+
+        if self.pokemons.len() < MAX_PARTY {
+            self.pokemons.push(pokemon);
+            CaughtPlace::Party
+        } else {
+            storage.push(pokemon);
+            CaughtPlace::Storage
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file::json::fixture::trainer;
+
+    /// 手持ちに空きがあれば手持ちに加え、ボックスのポケモンとも重ならない個体の ID を割り当てる
+    #[test]
+    fn caught_pokemon_joins_the_party_when_there_is_room() {
+        let mut trainer = trainer("satoshi");
+        let party = trainer.pokemons.len();
+        let caught = trainer.pokemons[0].clone();
+        let mut stored = caught.clone();
+        stored.uid = 100;
+        let mut storage = vec![stored];
+
+        assert_eq!(trainer.add_caught(caught, &mut storage), CaughtPlace::Party);
+        assert_eq!(storage.len(), 1);
+        assert_eq!(trainer.pokemons.len(), party + 1);
+        assert_eq!(trainer.pokemons[party].uid, 101);
+    }
+
+    /// 手持ちがいっぱいの場合はボックスに預ける
+    #[test]
+    fn caught_pokemon_goes_to_storage_when_the_party_is_full() {
+        let mut trainer = trainer("satoshi");
+        let caught = trainer.pokemons[0].clone();
+        while trainer.pokemons.len() < MAX_PARTY {
+            trainer.pokemons.push(caught.clone());
+        }
+        let mut storage = Vec::new();
+
+        assert_eq!(trainer.add_caught(caught.clone(), &mut storage), CaughtPlace::Storage);
+        assert_eq!(trainer.add_caught(caught, &mut storage), CaughtPlace::Storage);
+        assert_eq!(storage.len(), 2);
+        assert_ne!(storage[0].uid, storage[1].uid);
+        assert_eq!(trainer.pokemons.len(), MAX_PARTY);
+    }
+}
@@ -0,0 +1,66 @@
+use std::sync::Arc;
+
+use crate::models::pokemon::{EvolutionCondition, Pokemon, Species, Status};
+
+/// 進化のきっかけを表す列挙型
+///
+/// レベルアップ、どうぐの使用（どうぐのキー）、通信交換で構成
+/// なつき度による進化はレベルアップをきっかけに判定する
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EvolutionTrigger<'a> {
+    LevelUp,
+    Item(&'a str),
+    Trade,
+}
+
+pub trait EvolutionActions {
+    fn evolution_target(&self, trigger: EvolutionTrigger) -> Option<&str>;
+    fn evolve(&mut self, species: Arc<Species>);
+}
+
+impl EvolutionActions for Pokemon {
+    /// きっかけに対して進化の条件を満たす進化先を取得
+    ///
+    /// 条件を満たす進化先が複数ある場合は、データに記述した順で最初の進化先を選ぶ
+    ///
+    /// # 引数
+    /// * `trigger` - 進化のきっかけ
+    ///
+    /// # 戻り値
+    /// * `Option<&str>` - 進化先の図鑑番号、進化しない場合は None
+    fn evolution_target(&self, trigger: EvolutionTrigger) -> Option<&str> {
+        self.species
+            .evolutions
+            .iter()
+            .find(|evolution| match (&evolution.condition, trigger) {
+                (EvolutionCondition::Level(level), EvolutionTrigger::LevelUp) => self.level >= *level,
+                (EvolutionCondition::Friendship(friendship), EvolutionTrigger::LevelUp) => self.friendship >= *friendship,
+                (EvolutionCondition::Item(item), EvolutionTrigger::Item(used)) => item == used,
+                (EvolutionCondition::Trade, EvolutionTrigger::Trade) => true,
+                _ => false,
+            })
+            .map(|evolution| evolution.into.as_str())
+    }
+
+    /// 進化先の種族に変えてステータスを再計算
+    ///
+    /// レベル、経験値、なつき度、覚えている技、状態異常はそのまま、現在のHPは最大HPに対する割合を保つ
+    /// ニックネームはそのまま、ニックネームがない場合の名前は進化先の種族名になる
+    ///
+    /// # 引数
+    /// * `species` - 進化先の種族
+    fn evolve(&mut self, species: Arc<Species>) {
+        let old = &self.status;
+        let mut status = Status::from_base_status(&species.base_status, &self.ivs, &self.evs, self.nature, self.level);
+
+        // ひんしでなければHPは 1 以上残す
+        let current_hp = old.current_hp as u32 * status.hp.value as u32 / old.hp.value.max(1) as u32;
+        status.current_hp = match old.current_hp {
+            0 => 0,
+            _ => (current_hp as u16).clamp(1, status.hp.value),
+        };
+
+        self.species = species;
+        self.status = status;
+    }
+}
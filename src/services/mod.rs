@@ -1 +1,7 @@
-pub mod battle;
\ No newline at end of file
+pub mod ai;
+pub mod battle;
+pub mod evolution;
+pub mod replay;
+pub mod save;
+pub mod simulation;
+pub mod validate;
\ No newline at end of file
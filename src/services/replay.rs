@@ -0,0 +1,53 @@
+use crate::{
+    models::replay::{Replay, ReplayTurn},
+    services::battle::{engine::TurnActions, event::Side}
+};
+
+/// リプレイに行動を記録するトレイト
+pub trait ReplayActions {
+    fn record_turn(&mut self, actions: TurnActions);
+    fn record_replacement(&mut self, side: Side, idx: usize);
+    fn record_skill_choice(&mut self, forget: Option<usize>);
+}
+
+impl ReplayActions for Replay {
+    /// ターンの両陣営の行動を記録
+    ///
+    /// # 引数
+    /// * `actions` - `Battle::submit_actions` に渡した行動
+    fn record_turn(&mut self, actions: TurnActions) {
+        self.turns.push(ReplayTurn {
+            player: actions.player,
+            opponent: actions.opponent,
+            skill_choices: Vec::new(),
+            player_replacement: None,
+            opponent_replacement: None,
+        });
+    }
+
+    /// ひんしのポケモンの交代先を直前のターンに記録
+    ///
+    /// # 引数
+    /// * `side` - 交代した陣営
+    /// * `idx`  - `Battle::submit_replacement` に渡した手持ちのインデックス
+    fn record_replacement(&mut self, side: Side, idx: usize) {
+        let Some(turn) = self.turns.last_mut() else {
+            return;
+        };
+
+        match side {
+            Side::Player => turn.player_replacement = Some(idx),
+            Side::Opponent => turn.opponent_replacement = Some(idx),
+        }
+    }
+
+    /// レベルアップで覚えようとした技の処理を直前のターンに記録
+    ///
+    /// # 引数
+    /// * `forget` - `Battle::resolve_pending_skill` に渡した忘れる技のインデックス
+    fn record_skill_choice(&mut self, forget: Option<usize>) {
+        if let Some(turn) = self.turns.last_mut() {
+            turn.skill_choices.push(forget);
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use crate::{
+    models::save::SaveData,
+    services::battle::{engine::Battle, event::Side, pokemon::PokemonActions, trainer::TrainerActions}
+};
+
+/// セーブデータにバトルの結果を反映するトレイト
+pub trait SaveActions {
+    fn record_battle(&mut self, battle: &Battle);
+    fn take_item(&mut self, item: &str) -> bool;
+}
+
+impl SaveActions for SaveData {
+    /// バトル後の手持ちと進行状況をセーブデータに反映
+    ///
+    /// 手持ちのHP、PP、状態異常はバトル終了時のまま引き継ぎ、ランク変化と一時的な状態だけを解除する
+    /// 負けた場合は手持ちを全回復する（目の前が真っ暗になり、ポケモンセンターに戻る）
+    /// 捕まえたポケモンは、反映する前に `TrainerActions::add_caught` で自分のトレーナーに加えておく
+    ///
+    /// # 引数
+    /// * `battle` - 終了したバトルの状態（自分の陣営がセーブデータのトレーナー）
+    fn record_battle(&mut self, battle: &Battle) {
+        let mut trainer = battle.player.clone();
+        for pokemon in &mut trainer.pokemons {
+            pokemon.reset_battle_state();
+        }
+
+        self.progress.battles += 1;
+        match battle.winner() {
+            Some(Side::Player) => {
+                self.progress.wins += 1;
+                let defeated = battle.opponent.id.to_string();
+                if !battle.wild && !self.progress.defeated_trainers.contains(&defeated) {
+                    self.progress.defeated_trainers.push(defeated);
+                }
+            }
+            Some(Side::Opponent) => {
+                self.progress.losses += 1;
+                trainer.heal_party();
+            }
+            None if battle.caught => self.progress.caught += 1,
+            None => {}
+        }
+
+        // 次のバトルは戦えるポケモンのうち手持ちの先頭から始める
+        trainer.active_idx = trainer.next_usable_pokemon().unwrap_or(0);
+        self.trainer = trainer;
+    }
+
+    /// バッグから道具を 1 つ取り出す
+    ///
+    /// # 引数
+    /// * `item` - 道具のキー
+    ///
+    /// # 戻り値
+    /// * `bool` - 取り出せたかどうか（持っていない場合は false）
+    fn take_item(&mut self, item: &str) -> bool {
+        let Some(count) = self.bag.get_mut(item).filter(|count| **count > 0) else {
+            return false;
+        };
+
+        *count -= 1;
+        if *count == 0 {
+            self.bag.remove(item);
+        }
+        true
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        file::json::fixture::{battle_data, trainer},
+        logic::rand::XorShift128,
+        models::{item::ItemKind, save::STARTER_ITEMS, trainer::BattleAction},
+        services::battle::{engine::TurnActions, trainer::CaughtPlace}
+    };
+
+    /// セーブファイルがない場合も新しいセーブデータのバッグからボールを投げ、捕まえたポケモンを手持ちに加えられる
+    #[test]
+    fn starter_bag_catches_without_a_save_file() {
+        let (_, _, _, items) = battle_data();
+        let mut save = SaveData::new(trainer("satoshi"));
+        let ItemKind::Ball(ball) = items["monster_ball"].kind else {
+            panic!("monster_ball がボールではありません");
+        };
+
+        let mut wild = trainer("short_pants_boy");
+        wild.pokemons[0].status.current_hp = 1;
+        let mut battle = Battle::new(save.trainer.clone(), wild, true, XorShift128::from_seed(1));
+        let mut thrown = 0;
+        while !battle.caught && save.take_item("monster_ball") {
+            thrown += 1;
+            battle.submit_actions(TurnActions { player: BattleAction::Ball(ball), opponent: BattleAction::Fight(1) });
+        }
+        assert!(battle.caught);
+        assert_eq!(save.bag.get("monster_ball").copied().unwrap_or(0), STARTER_ITEMS[0].1 - thrown);
+
+        let caught = battle.opponent.active_pokemon().clone();
+        assert_eq!(battle.player.add_caught(caught, &mut save.storage), CaughtPlace::Party);
+        save.record_battle(&battle);
+        assert_eq!(save.trainer.pokemons.len(), 2);
+        assert_eq!(save.progress.caught, 1);
+    }
+}
@@ -0,0 +1,386 @@
+use std::{collections::BTreeMap, thread};
+
+use crate::{
+    logic::rand::XorShift128,
+    models::{replay::Replay, trainer::Trainer},
+    services::{
+        ai::strategy::{decide_action, decide_replacement, strategy_for},
+        battle::{
+            engine::{Battle, TurnActions},
+            event::{BattleEvent, Side}
+        },
+        replay::ReplayActions
+    }
+};
+
+/// 決着がつかない場合にバトルを打ち切るターン数
+pub const MAX_TURNS: u32 = 1000;
+
+/// 1 回のバトルの結果
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BattleResult {
+    /// 勝った陣営（打ち切りで引き分けの場合は None）
+    pub winner: Option<Side>,
+    /// 経過したターン数
+    pub turns: u32,
+    /// 自分の陣営の集計
+    pub player: SideResult,
+    /// 相手の陣営の集計
+    pub opponent: SideResult,
+}
+
+/// 1 回のバトルでの陣営ごとの集計
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SideResult {
+    /// 手持ちの残りHPの合計
+    pub remaining_hp: u32,
+    /// 手持ちの最大HPの合計
+    pub max_hp: u32,
+    /// (ポケモンの名前, 技の名前) ごとの技の使用回数
+    pub skill_uses: BTreeMap<(String, String), u32>,
+}
+
+impl BattleResult {
+    /// 陣営の集計を取得
+    pub fn side(&self, side: Side) -> &SideResult {
+        match side {
+            Side::Player => &self.player,
+            Side::Opponent => &self.opponent,
+        }
+    }
+
+    fn side_mut(&mut self, side: Side) -> &mut SideResult {
+        match side {
+            Side::Player => &mut self.player,
+            Side::Opponent => &mut self.opponent,
+        }
+    }
+}
+
+/// 複数回のバトルの集計
+///
+/// 合計値のみを保持するため、スレッドごとの集計を足し合わせても結果は実行順に依存しない
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SimulationStats {
+    /// バトルの回数
+    pub battles: u32,
+    /// 決着がつかなかったバトルの回数
+    pub draws: u32,
+    /// ターン数の合計
+    pub total_turns: u64,
+    /// 自分の陣営の集計
+    pub player: SideStats,
+    /// 相手の陣営の集計
+    pub opponent: SideStats,
+}
+
+/// 複数回のバトルでの陣営ごとの集計
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SideStats {
+    /// 勝った回数
+    pub wins: u32,
+    /// バトル終了時の残りHPの合計
+    pub remaining_hp: u64,
+    /// 手持ちの最大HPの合計
+    pub max_hp: u64,
+    /// (ポケモンの名前, 技の名前) ごとの技の使用回数
+    pub skill_uses: BTreeMap<(String, String), u32>,
+}
+
+impl SideStats {
+    fn record(&mut self, result: &SideResult, won: bool) {
+        self.wins += won as u32;
+        self.remaining_hp += result.remaining_hp as u64;
+        self.max_hp += result.max_hp as u64;
+        for (key, count) in &result.skill_uses {
+            *self.skill_uses.entry(key.clone()).or_default() += count;
+        }
+    }
+
+    fn merge(&mut self, other: SideStats) {
+        self.wins += other.wins;
+        self.remaining_hp += other.remaining_hp;
+        self.max_hp += other.max_hp;
+        for (key, count) in other.skill_uses {
+            *self.skill_uses.entry(key).or_default() += count;
+        }
+    }
+}
+
+impl SimulationStats {
+    /// 陣営の集計を取得
+    pub fn side(&self, side: Side) -> &SideStats {
+        match side {
+            Side::Player => &self.player,
+            Side::Opponent => &self.opponent,
+        }
+    }
+
+    /// バトルの結果を集計に加える
+    ///
+    /// # 引数
+    /// * `result` - バトルの結果
+    pub fn record(&mut self, result: &BattleResult) {
+        self.battles += 1;
+        self.draws += result.winner.is_none() as u32;
+        self.total_turns += result.turns as u64;
+        self.player.record(&result.player, result.winner == Some(Side::Player));
+        self.opponent.record(&result.opponent, result.winner == Some(Side::Opponent));
+    }
+
+    /// 別の集計を足し合わせる
+    ///
+    /// # 引数
+    /// * `other` - 足し合わせる集計
+    pub fn merge(&mut self, other: SimulationStats) {
+        self.battles += other.battles;
+        self.draws += other.draws;
+        self.total_turns += other.total_turns;
+        self.player.merge(other.player);
+        self.opponent.merge(other.opponent);
+    }
+
+    /// 陣営の勝率（0.0 〜 1.0）
+    pub fn win_rate(&self, side: Side) -> f64 {
+        self.side(side).wins as f64 / self.battles.max(1) as f64
+    }
+
+    /// 1 回のバトルあたりの平均ターン数
+    pub fn average_turns(&self) -> f64 {
+        self.total_turns as f64 / self.battles.max(1) as f64
+    }
+
+    /// 1 回のバトルあたりの陣営の平均残りHP
+    pub fn average_remaining_hp(&self, side: Side) -> f64 {
+        self.side(side).remaining_hp as f64 / self.battles.max(1) as f64
+    }
+
+    /// 陣営の残りHPの最大HPに対する割合（0.0 〜 1.0）
+    pub fn remaining_hp_ratio(&self, side: Side) -> f64 {
+        let stats = self.side(side);
+        stats.remaining_hp as f64 / stats.max_hp.max(1) as f64
+    }
+}
+
+/// コンピュータ同士でバトルを最後まで進める
+///
+/// 両陣営ともトレーナーの AI で行動と交代先を選択する
+/// `MAX_TURNS` ターンで決着がつかない場合は打ち切り、引き分けとする
+///
+/// # 引数
+/// * `battle` - バトルの状態
+/// * `replay` - 行動を記録するリプレイ（記録しない場合は None）
+///
+/// # 戻り値
+/// * `BattleResult` - バトルの結果
+pub fn run_battle(battle: &mut Battle, mut replay: Option<&mut Replay>) -> BattleResult {
+    let mut result = BattleResult::default();
+
+    let player_strategy = strategy_for(&battle.player);
+    let opponent_strategy = strategy_for(&battle.opponent);
+    while !battle.is_over() && battle.turn < MAX_TURNS {
+        let player = decide_action(battle, Side::Player, player_strategy.as_ref());
+        let opponent = decide_action(battle, Side::Opponent, opponent_strategy.as_ref());
+        let actions = TurnActions { player, opponent };
+        if let Some(replay) = replay.as_deref_mut() {
+            replay.record_turn(actions);
+        }
+        for event in battle.submit_actions(actions) {
+            if let BattleEvent::SkillUsed { side, pokemon, skill } = event {
+                *result.side_mut(side).skill_uses.entry((pokemon, skill)).or_default() += 1;
+            }
+        }
+
+        // ひんしになったポケモンを入れ替える（リプレイの再生と同じく相手の陣営から）
+        for (side, strategy) in [(Side::Opponent, &opponent_strategy), (Side::Player, &player_strategy)] {
+            if battle.is_over() || !battle.needs_replacement(side) {
+                continue;
+            }
+            if let Some(idx) = decide_replacement(battle, side, strategy.as_ref()) {
+                if let Some(replay) = replay.as_deref_mut() {
+                    replay.record_replacement(side, idx);
+                }
+                battle.submit_replacement(side, idx);
+            }
+        }
+    }
+
+    result.winner = battle.winner();
+    result.turns = battle.turn;
+    for side in [Side::Player, Side::Opponent] {
+        let pokemons = &battle.trainer(side).pokemons;
+        let side_result = result.side_mut(side);
+        side_result.remaining_hp = pokemons.iter().map(|pokemon| pokemon.status.current_hp as u32).sum();
+        side_result.max_hp = pokemons.iter().map(|pokemon| pokemon.status.hp.value as u32).sum();
+    }
+
+    result
+}
+
+/// 同じ組み合わせでバトルを繰り返し、結果を集計
+///
+/// `i` 回目のバトルはシード値 `seed + i` の乱数生成器を使用するため、スレッド数によらず結果は同じになり、
+/// 気になるバトルは 1 回だけのシミュレーションで再現できる
+///
+/// # 引数
+/// * `player`   - 自分のトレーナー
+/// * `opponent` - 相手のトレーナー
+/// * `wild`     - やせいのポケモンとのバトルかどうか
+/// * `battles`  - バトルの回数
+/// * `threads`  - 使用するスレッド数
+/// * `seed`     - 1 回目のバトルのシード値
+///
+/// # 戻り値
+/// * `SimulationStats` - バトルの結果の集計
+pub fn run_simulations(
+    player: &Trainer,
+    opponent: &Trainer,
+    wild: bool,
+    battles: u32,
+    threads: u32,
+    seed: u64
+) -> SimulationStats {
+    let threads = threads.clamp(1, battles.max(1));
+
+    thread::scope(|scope| {
+        let handles: Vec<_> = (0..threads)
+            .map(|worker| {
+                scope.spawn(move || {
+                    let mut stats = SimulationStats::default();
+                    for i in (worker..battles).step_by(threads as usize) {
+                        let rng = XorShift128::from_seed(seed.wrapping_add(i as u64));
+                        let mut battle = Battle::new(player.clone(), opponent.clone(), wild, rng);
+                        stats.record(&run_battle(&mut battle, None));
+                    }
+                    stats
+                })
+            })
+            .collect();
+
+        let mut stats = SimulationStats::default();
+        for handle in handles {
+            stats.merge(handle.join().expect("シミュレーションのスレッドが異常終了しました"));
+        }
+        stats
+    })
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{file::json::fixture::trainer, models::trainer::AiLevel};
+
+    fn battle(seed: u64) -> Battle {
+        Battle::new(trainer("satoshi"), trainer("daigo"), false, XorShift128::from_seed(seed))
+    }
+
+    /// MAX_TURNS ターンで決着がつかない場合は引き分けとして集計される
+    #[test]
+    fn battle_is_a_draw_at_the_turn_limit() {
+        let mut battle = Battle::new(trainer("daigo"), trainer("daigo"), false, XorShift128::from_seed(1));
+        battle.turn = MAX_TURNS - 1;
+
+        let result = run_battle(&mut battle, None);
+        assert_eq!(result.winner, None);
+        assert_eq!(result.turns, MAX_TURNS);
+
+        let mut stats = SimulationStats::default();
+        stats.record(&result);
+        assert_eq!(stats.draws, 1);
+        assert_eq!(stats.player.wins + stats.opponent.wins, 0);
+    }
+
+    /// 集計を分けて足し合わせても、1 つの集計に記録した場合と同じになる
+    #[test]
+    fn merged_stats_equal_recording_every_result() {
+        let results: Vec<_> = (0..4).map(|seed| run_battle(&mut battle(seed), None)).collect();
+
+        let mut all = SimulationStats::default();
+        results.iter().for_each(|result| all.record(result));
+
+        let (mut first, mut second) = (SimulationStats::default(), SimulationStats::default());
+        results[..1].iter().for_each(|result| first.record(result));
+        results[1..].iter().for_each(|result| second.record(result));
+        first.merge(second);
+
+        assert_eq!(first, all);
+        assert_eq!(all.battles, 4);
+        assert_eq!(all.player.wins + all.opponent.wins + all.draws, 4);
+    }
+
+    /// スレッド数を変えても集計は同じになる
+    #[test]
+    fn simulations_do_not_depend_on_the_thread_count() {
+        let (player, opponent) = (trainer("satoshi"), trainer("daigo"));
+
+        let single = run_simulations(&player, &opponent, false, 8, 1, 42);
+        assert_eq!(single.battles, 8);
+        for threads in [2, 3, 8] {
+            assert_eq!(run_simulations(&player, &opponent, false, 8, threads, 42), single);
+        }
+    }
+
+    /// 記録した行動をリプレイの開始時の状態から処理し直し、発生したイベントを返す
+    fn play_back(replay: &Replay) -> (Battle, Vec<BattleEvent>) {
+        let mut battle = replay.initial_battle();
+        let mut events = Vec::new();
+        for turn in &replay.turns {
+            events.extend(battle.submit_actions(TurnActions { player: turn.player, opponent: turn.opponent }));
+            for &forget in &turn.skill_choices {
+                events.extend(battle.resolve_pending_skill(forget));
+            }
+            for (side, idx) in [(Side::Opponent, turn.opponent_replacement), (Side::Player, turn.player_replacement)] {
+                if let Some(idx) = idx {
+                    events.extend(battle.submit_replacement(side, idx));
+                }
+            }
+        }
+
+        (battle, events)
+    }
+
+    /// ランダムに行動する AI 同士のバトルはリプレイで同じイベントと勝者を再現できる
+    #[test]
+    fn random_battle_replays_identically() {
+        let (mut player, mut opponent) = (trainer("wataru"), trainer("daigo"));
+        player.ai = AiLevel::Random;
+        opponent.ai = AiLevel::Random;
+
+        for seed in 0..5 {
+            let mut battle = Battle::new(player.clone(), opponent.clone(), false, XorShift128::from_seed(seed));
+            let mut replay = Replay::new(&battle, seed, None);
+            let strategies = [strategy_for(&battle.player), strategy_for(&battle.opponent)];
+            let mut events = Vec::new();
+            while !battle.is_over() {
+                let actions = TurnActions {
+                    player: decide_action(&mut battle, Side::Player, strategies[0].as_ref()),
+                    opponent: decide_action(&mut battle, Side::Opponent, strategies[1].as_ref()),
+                };
+                replay.record_turn(actions);
+                events.extend(battle.submit_actions(actions));
+                for (side, strategy) in [(Side::Opponent, &strategies[1]), (Side::Player, &strategies[0])] {
+                    if battle.is_over() || !battle.needs_replacement(side) {
+                        continue;
+                    }
+                    if let Some(idx) = decide_replacement(&mut battle, side, strategy.as_ref()) {
+                        replay.record_replacement(side, idx);
+                        events.extend(battle.submit_replacement(side, idx));
+                    }
+                }
+            }
+
+            let (replayed, replayed_events) = play_back(&replay);
+            assert_eq!(replayed_events, events);
+            assert_eq!(replayed.winner(), battle.winner());
+            assert!(replayed.winner().is_some());
+
+            // run_battle も同じ行動を記録する
+            let mut simulated = Battle::new(player.clone(), opponent.clone(), false, XorShift128::from_seed(seed));
+            let mut simulated_replay = Replay::new(&simulated, seed, None);
+            let result = run_battle(&mut simulated, Some(&mut simulated_replay));
+            assert_eq!(result.winner, battle.winner());
+            assert_eq!(simulated_replay.turns, replay.turns);
+        }
+    }
+}
@@ -0,0 +1,466 @@
+use std::{collections::{HashMap, HashSet}, fmt};
+
+use serde::Deserialize;
+
+use crate::{
+    dto::pokemon::parse_element,
+    file::json::read_json_entries,
+    models::item::{Item, ItemKind},
+    models::pokemon::{EffectKind, EvolutionCondition, PokemonJson, Skill, SkillType, SpeciesJson, Status, StatusEffect, StatusType, Target},
+    models::trainer::TrainerJson,
+    services::battle::{experience::MAX_LEVEL, learnset::MAX_SKILLS, trainer::MAX_PARTY}
+};
+
+/// 探索 AI の先読みするターン数の上限
+const MAX_SEARCH_DEPTH: u8 = 4;
+
+/// ゲームデータの問題点
+///
+/// ファイル、キー、フィールドで問題の場所を表す
+#[derive(Debug, Clone, PartialEq)]
+pub struct Problem {
+    pub file: &'static str,
+    pub key: Option<String>,
+    pub field: Option<String>,
+    pub message: String,
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.file)?;
+        if let Some(key) = &self.key {
+            write!(f, ": {}", key)?;
+        }
+        if let Some(field) = &self.field {
+            write!(f, ".{}", field)?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+/// 問題点を収集する構造体
+struct Problems {
+    file: &'static str,
+    list: Vec<Problem>,
+}
+
+impl Problems {
+    fn push(&mut self, key: Option<&str>, field: Option<String>, message: String) {
+        self.list.push(Problem { file: self.file, key: key.map(str::to_string), field, message });
+    }
+
+    fn at(&mut self, key: &str, field: impl Into<String>, message: String) {
+        self.push(Some(key), Some(field.into()), message);
+    }
+}
+
+/// ディレクトリ内の JSON ファイルを検証
+///
+/// 読み込めないファイルや値も問題点として報告し、見つかった問題をすべて返す
+///
+/// # 引数
+/// * `dir` - trainer_data.json、pokemon_data.json、skill_data.json、item_data.json を含むディレクトリ
+///
+/// # 戻り値
+/// * `Vec<Problem>` - 見つかった問題点（問題がなければ空）
+pub fn validate_game_data(dir: &str) -> Vec<Problem> {
+    let (skills, mut skill_problems) = read_entries::<Skill>(dir, "skill_data.json");
+    let (species, mut species_problems) = read_entries::<SpeciesJson>(dir, "pokemon_data.json");
+    let (trainers, mut trainer_problems) = read_entries::<TrainerJson>(dir, "trainer_data.json");
+    let (items, mut item_problems) = read_entries::<Item>(dir, "item_data.json");
+
+    for (key, skill) in sorted(&skills) {
+        validate_skill(key, skill, &mut skill_problems);
+    }
+
+    let mut ids: HashMap<&str, &str> = HashMap::new();
+    for (key, entry) in sorted(&species) {
+        validate_species(key, entry, &skills, &mut species_problems);
+        validate_evolutions(key, entry, &species, &items, &mut species_problems);
+        if let Some(other) = ids.insert(entry.id.as_str(), key) {
+            species_problems.at(key, "id", format!("id {} が {} と重複しています", entry.id, other));
+        }
+    }
+
+    for (key, trainer) in sorted(&trainers) {
+        validate_trainer(key, trainer, &species, &skills, &mut trainer_problems);
+    }
+
+    for (key, item) in sorted(&items) {
+        validate_item(key, item, &mut item_problems);
+    }
+
+    [skill_problems, species_problems, trainer_problems, item_problems]
+        .into_iter()
+        .flat_map(|problems| problems.list)
+        .collect()
+}
+
+/// JSON ファイルを読み込み、キーの重複と値の型を検証
+///
+/// # 引数
+/// * `dir`  - JSON ファイルのディレクトリ
+/// * `file` - ファイル名
+///
+/// # 戻り値
+/// * `(HashMap<String, T>, Problems)` - (変換できた値, 見つかった問題点)
+fn read_entries<T>(dir: &str, file: &'static str) -> (HashMap<String, T>, Problems)
+where
+    T: for<'de> Deserialize<'de>
+{
+    let mut problems = Problems { file, list: Vec::new() };
+    let mut values = HashMap::new();
+
+    let entries = match read_json_entries(&format!("{}/{}", dir, file)) {
+        Ok(entries) => entries,
+        Err(err) => {
+            problems.push(None, None, err.to_string());
+            return (values, problems);
+        }
+    };
+
+    let mut seen = HashSet::new();
+    for (key, value) in entries {
+        if !seen.insert(key.clone()) {
+            problems.push(Some(&key), None, "キーが重複しています".to_string());
+        }
+        match serde_json::from_value::<T>(value) {
+            Ok(value) => {
+                values.insert(key, value);
+            }
+            Err(err) => problems.push(Some(&key), None, format!("値が不正です: {}", err)),
+        }
+    }
+
+    (values, problems)
+}
+
+/// キーの順に並べる
+fn sorted<T>(map: &HashMap<String, T>) -> Vec<(&str, &T)> {
+    let mut entries: Vec<(&str, &T)> = map.iter().map(|(key, value)| (key.as_str(), value)).collect();
+    entries.sort_by(|a, b| a.0.cmp(b.0));
+
+    entries
+}
+
+/// 技データを検証
+///
+/// # 引数
+/// * `key`      - 技のキー
+/// * `skill`    - 技データ
+/// * `problems` - 問題点の出力先
+fn validate_skill(key: &str, skill: &Skill, problems: &mut Problems) {
+    if skill.accuracy > 100 {
+        problems.at(key, "accuracy", format!("命中率 {} が 100 を超えています", skill.accuracy));
+    }
+    if skill.pp == 0 {
+        problems.at(key, "pp", "PP が 0 です".to_string());
+    }
+    if skill.critical_rank > 3 {
+        problems.at(key, "critical_rank", format!("急所ランク {} が 3 を超えています", skill.critical_rank));
+    }
+
+    match skill.class {
+        SkillType::PhysicalAttack | SkillType::SpecialAttack if skill.base_atk == 0 => {
+            problems.at(key, "base_atk", "攻撃技の威力が 0 です".to_string());
+        }
+        SkillType::ChangeStatus if skill.skill_effect.is_none() => {
+            problems.at(key, "skill_effect", "変化技に skill_effect がありません".to_string());
+        }
+        SkillType::Struggle => {
+            problems.at(key, "class", "Struggle はデータに定義できません".to_string());
+        }
+        _ => {}
+    }
+
+    let attack = matches!(skill.class, SkillType::PhysicalAttack | SkillType::SpecialAttack);
+    if skill.charge && !attack {
+        problems.at(key, "charge", "溜めが必要な技は攻撃技である必要があります".to_string());
+    }
+    if skill.breaks_screens && !attack {
+        problems.at(key, "breaks_screens", "壁を壊す技は攻撃技である必要があります".to_string());
+    }
+
+    if let Some(skill_effect) = &skill.skill_effect {
+        if skill_effect.status_effect.is_none()
+            && skill_effect.ailment.is_none()
+            && skill_effect.critical_stage == 0
+            && skill_effect.screen.is_none()
+        {
+            problems.at(key, "skill_effect", "status_effect、ailment、critical_stage、screen のいずれも指定されていません".to_string());
+        }
+        if skill_effect.screen.is_some() && skill_effect.target != Target::Self_ {
+            problems.at(key, "skill_effect.target", "壁を張る技の対象は Self_ である必要があります".to_string());
+        }
+        if let Some(status_effect) = &skill_effect.status_effect {
+            validate_status_effect(key, "skill_effect.status_effect", status_effect, problems);
+        }
+    }
+
+    for (idx, secondary_effect) in skill.secondary_effects.iter().enumerate() {
+        let field = format!("secondary_effects[{}]", idx);
+        if secondary_effect.chance == 0 || secondary_effect.chance > 100 {
+            problems.at(key, format!("{}.chance", field), format!("発動率 {} が 1 〜 100 の範囲外です", secondary_effect.chance));
+        }
+        if let EffectKind::StatusChange(status_effect) = &secondary_effect.effect {
+            validate_status_effect(key, &format!("{}.effect", field), status_effect, problems);
+        }
+    }
+}
+
+/// ステータス変化の効果を検証
+///
+/// # 引数
+/// * `key`           - 技のキー
+/// * `field`         - 効果のフィールド名
+/// * `status_effect` - ステータス変化の効果
+/// * `problems`      - 問題点の出力先
+fn validate_status_effect(key: &str, field: &str, status_effect: &StatusEffect, problems: &mut Problems) {
+    if status_effect.target == StatusType::Hp {
+        problems.at(key, format!("{}.target", field), "HP はランク変化の対象にできません".to_string());
+    }
+    // 天候・フィールドはランクを持たないため変化量は検証しない
+    if status_effect.target != StatusType::Field && (status_effect.effect_value == 0 || status_effect.effect_value.abs() > 6) {
+        problems.at(
+            key,
+            format!("{}.effect_value", field),
+            format!("ランクの変化量 {} が ±1 〜 ±6 の範囲外です", status_effect.effect_value),
+        );
+    }
+}
+
+/// 種族データを検証
+///
+/// # 引数
+/// * `key`      - 種族のキー
+/// * `species`  - 種族データ
+/// * `skills`   - 技データ
+/// * `problems` - 問題点の出力先
+fn validate_species(key: &str, species: &SpeciesJson, skills: &HashMap<String, Skill>, problems: &mut Problems) {
+    if species.id != key {
+        problems.at(key, "id", format!("id {} がキーと一致しません", species.id));
+    }
+    if species.base_exp == 0 {
+        problems.at(key, "base_exp", "基礎経験値が 0 です".to_string());
+    }
+    if species.catch_rate == 0 {
+        problems.at(key, "catch_rate", "捕まえやすさが 0 です".to_string());
+    }
+
+    if species.element.is_empty() || species.element.len() > 2 {
+        problems.at(key, "element", format!("タイプの数 {} が 1 〜 2 の範囲外です", species.element.len()));
+    }
+    for (idx, element) in species.element.iter().enumerate() {
+        if parse_element(element).is_none() {
+            problems.at(key, format!("element[{}]", idx), format!("未定義のタイプ {} です", element));
+        }
+    }
+
+    if let Some(level) = species.wild_level {
+        if level == 0 || level > MAX_LEVEL {
+            problems.at(key, "wild_level", format!("レベル {} が 1 〜 {} の範囲外です", level, MAX_LEVEL));
+        } else if !species.learnset.level_up.iter().any(|entry| entry.level <= level) {
+            problems.at(key, "wild_level", format!("レベル {} までに覚える技がありません", level));
+        }
+    }
+
+    let learnset = &species.learnset;
+    for (idx, entry) in learnset.level_up.iter().enumerate() {
+        if entry.level == 0 || entry.level > MAX_LEVEL {
+            problems.at(key, format!("learnset.level_up[{}].level", idx), format!("レベル {} が 1 〜 {} の範囲外です", entry.level, MAX_LEVEL));
+        }
+        if !skills.contains_key(&entry.skill) {
+            problems.at(key, format!("learnset.level_up[{}].skill", idx), format!("未定義の技 {} です", entry.skill));
+        }
+    }
+    for (field, list) in [("tm", &learnset.tm), ("egg", &learnset.egg)] {
+        for (idx, skill) in list.iter().enumerate() {
+            if !skills.contains_key(skill) {
+                problems.at(key, format!("learnset.{}[{}]", field, idx), format!("未定義の技 {} です", skill));
+            }
+        }
+    }
+}
+
+/// ポケモンの進化先と進化の条件を検証
+///
+/// # 引数
+/// * `key`          - 種族のキー
+/// * `species`      - 種族データ
+/// * `species_data` - 種族データ全体
+/// * `items`        - 道具データ
+/// * `problems`     - 問題点の出力先
+fn validate_evolutions(
+    key: &str,
+    species: &SpeciesJson,
+    species_data: &HashMap<String, SpeciesJson>,
+    items: &HashMap<String, Item>,
+    problems: &mut Problems,
+) {
+    for (idx, evolution) in species.evolutions.iter().enumerate() {
+        if evolution.into == key {
+            problems.at(key, format!("evolutions[{}].into", idx), "自分自身に進化します".to_string());
+        } else if !species_data.contains_key(&evolution.into) {
+            problems.at(key, format!("evolutions[{}].into", idx), format!("未定義のポケモン {} です", evolution.into));
+        }
+
+        match &evolution.condition {
+            EvolutionCondition::Level(level) if *level == 0 || *level > MAX_LEVEL => {
+                problems.at(key, format!("evolutions[{}].condition", idx), format!("レベル {} が 1 〜 {} の範囲外です", level, MAX_LEVEL));
+            }
+            EvolutionCondition::Item(item) if item.is_empty() => {
+                problems.at(key, format!("evolutions[{}].condition", idx), "どうぐが指定されていません".to_string());
+            }
+            EvolutionCondition::Item(item) if !items.get(item).is_some_and(|item| item.kind == ItemKind::Evolution) => {
+                problems.at(key, format!("evolutions[{}].condition", idx), format!("{} は item_data.json の進化の道具ではありません", item));
+            }
+            EvolutionCondition::Friendship(0) => {
+                problems.at(key, format!("evolutions[{}].condition", idx), "なつき度が 0 です".to_string());
+            }
+            _ => {}
+        }
+    }
+}
+
+/// トレーナーデータを検証
+///
+/// # 引数
+/// * `key`      - トレーナーのキー
+/// * `trainer`  - トレーナーデータ
+/// * `species`  - 種族データ
+/// * `skills`   - 技データ
+/// * `problems` - 問題点の出力先
+fn validate_trainer(
+    key: &str,
+    trainer: &TrainerJson,
+    species: &HashMap<String, SpeciesJson>,
+    skills: &HashMap<String, Skill>,
+    problems: &mut Problems,
+) {
+    let party = trainer.pokemons.as_deref().unwrap_or_default();
+    if party.is_empty() || party.len() > MAX_PARTY {
+        problems.at(key, "pokemons", format!("手持ちの数 {} が 1 〜 {} の範囲外です", party.len(), MAX_PARTY));
+    }
+    for (idx, pokemon) in party.iter().enumerate() {
+        validate_party_pokemon(key, &format!("pokemons[{}]", idx), pokemon, species, skills, problems);
+    }
+
+    if let Some(active_pokemon) = trainer.active_pokemon {
+        if active_pokemon >= party.len() {
+            problems.at(key, "active_pokemon", format!("{} が pokemons の範囲外です", active_pokemon));
+        }
+    }
+
+    let search = &trainer.search;
+    if search.depth == 0 || search.depth > MAX_SEARCH_DEPTH {
+        problems.at(key, "search.depth", format!("先読みするターン数 {} が 1 〜 {} の範囲外です", search.depth, MAX_SEARCH_DEPTH));
+    }
+    if search.max_nodes == 0 {
+        problems.at(key, "search.max_nodes", "局面数の上限が 0 です".to_string());
+    }
+    if search.time_limit_ms == 0 {
+        problems.at(key, "search.time_limit_ms", "制限時間が 0 です".to_string());
+    }
+}
+
+/// トレーナーの手持ちのポケモンを検証
+///
+/// # 引数
+/// * `key`      - トレーナーのキー
+/// * `field`    - 手持ちのフィールド名
+/// * `pokemon`  - 手持ちのポケモンデータ
+/// * `species`  - 種族データ
+/// * `skills`   - 技データ
+/// * `problems` - 問題点の出力先
+fn validate_party_pokemon(
+    key: &str,
+    field: &str,
+    pokemon: &PokemonJson,
+    species: &HashMap<String, SpeciesJson>,
+    skills: &HashMap<String, Skill>,
+    problems: &mut Problems,
+) {
+    let entry = species.get(&pokemon.species);
+    if entry.is_none() {
+        problems.at(key, format!("{}.species", field), format!("未定義のポケモン {} です", pokemon.species));
+    }
+    if pokemon.nickname.as_deref().is_some_and(|nickname| nickname.trim().is_empty()) {
+        problems.at(key, format!("{}.nickname", field), "ニックネームが空です".to_string());
+    }
+    if pokemon.level == 0 || pokemon.level > MAX_LEVEL {
+        problems.at(key, format!("{}.level", field), format!("レベル {} が 1 〜 {} の範囲外です", pokemon.level, MAX_LEVEL));
+    }
+
+    let ivs = &pokemon.ivs;
+    for (name, value) in [("hp", ivs.hp), ("atk", ivs.atk), ("def", ivs.def), ("sp_atk", ivs.sp_atk), ("sp_def", ivs.sp_def), ("spd", ivs.spd)] {
+        if value > 31 {
+            problems.at(key, format!("{}.ivs.{}", field, name), format!("個体値 {} が 31 を超えています", value));
+        }
+    }
+    let evs = &pokemon.evs;
+    let evs_values = [("hp", evs.hp), ("atk", evs.atk), ("def", evs.def), ("sp_atk", evs.sp_atk), ("sp_def", evs.sp_def), ("spd", evs.spd)];
+    for (name, value) in evs_values {
+        if value > 252 {
+            problems.at(key, format!("{}.evs.{}", field, name), format!("努力値 {} が 252 を超えています", value));
+        }
+    }
+    let evs_total: u32 = evs_values.iter().map(|(_, value)| *value as u32).sum();
+    if evs_total > 510 {
+        problems.at(key, format!("{}.evs", field), format!("努力値の合計 {} が 510 を超えています", evs_total));
+    }
+
+    let Some(entry) = entry else {
+        return;
+    };
+
+    if let Some(current_hp) = pokemon.current_hp {
+        let max_hp = Status::from_base_status(&entry.base_status, &pokemon.ivs, &pokemon.evs, pokemon.nature, pokemon.level).hp.value;
+        if current_hp > max_hp {
+            problems.at(key, format!("{}.current_hp", field), format!("現在のHP {} が最大HP {} を超えています", current_hp, max_hp));
+        }
+    }
+
+    // 技を指定しない場合はレベルまでに覚える技を覚える
+    let Some(party_skills) = &pokemon.skills else {
+        if !entry.learnset.level_up.iter().any(|learn| learn.level <= pokemon.level) {
+            problems.at(key, format!("{}.skills", field), format!("レベル {} までに覚える技がありません", pokemon.level));
+        }
+        return;
+    };
+    if party_skills.is_empty() || party_skills.len() > MAX_SKILLS {
+        problems.at(key, format!("{}.skills", field), format!("技の数 {} が 1 〜 {} の範囲外です", party_skills.len(), MAX_SKILLS));
+    }
+    let mut seen = HashSet::new();
+    for (idx, skill) in party_skills.iter().enumerate() {
+        if !skills.contains_key(skill) {
+            problems.at(key, format!("{}.skills[{}]", field, idx), format!("未定義の技 {} です", skill));
+        }
+        if !seen.insert(skill) {
+            problems.at(key, format!("{}.skills[{}]", field, idx), format!("技 {} が重複しています", skill));
+        }
+        if !entry.learnset.allows(skill, pokemon.level) {
+            problems.at(
+                key,
+                format!("{}.skills[{}]", field, idx),
+                format!("技 {} はレベル {} までに覚えられません（learnset にありません）", skill, pokemon.level)
+            );
+        }
+    }
+}
+
+/// 道具データを検証
+///
+/// # 引数
+/// * `key`      - 道具のキー
+/// * `item`     - 道具データ
+/// * `problems` - 問題点の出力先
+fn validate_item(key: &str, item: &Item, problems: &mut Problems) {
+    if item.name.is_empty() {
+        problems.at(key, "name", "道具名が空です".to_string());
+    }
+    if let ItemKind::Ball(ball) = item.kind {
+        if ball.modifier == 0 && !ball.guaranteed {
+            problems.at(key, "kind.Ball.modifier", "ボールの補正が 0 です".to_string());
+        }
+    }
+}
\ No newline at end of file
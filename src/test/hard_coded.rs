@@ -1,95 +1,168 @@
-use crate::{cli::{battle::start_battle, prompt::{select_pokemon, select_trainer}}, dto::skill::set_skill_list, logic::rand::XorShift128, models::{pokemon::{BufToStatus, ElementType, Pokemon, Status}, trainer::Trainer}};
+use std::{collections::HashMap, sync::Arc};
+
+use pokemon_battle_system::{dto::game_data::BattleData, models::{item::{Ball, Item, ItemKind}, pokemon::{ElementType, GrowthRate, LearnedSkill, Nature, Pokemon, Species, Status, StatusPoints, VolatileStatus}, trainer::{AiLevel, SearchConfig, Trainer}}};
+
+use super::skill::{set_learnset, set_skill_list};
 
 /// データはハードコードで設定
-pub fn test() {
-    // トレーナーを選択
-    let mut trainers = vec![
+/// 
+/// # 戻り値
+/// * `BattleData` - (図鑑番号をキーにした種族, キーごとの技, トレーナーのリスト, 道具)
+pub fn load_hard_coded_data() -> BattleData {
+    let species = ["pikachu", "metagross", "pidgey"]
+        .into_iter()
+        .map(|name| {
+            let pokemon = set_pokemon(name, 0);
+            (pokemon.species.id.clone(), pokemon.species)
+        })
+        .collect();
+
+    let skills = ["pikachu", "metagross", "pidgey"]
+        .into_iter()
+        .flat_map(set_skill_list)
+        .map(|skill| (skill.id.clone(), Arc::new(skill)))
+        .collect();
+
+    let trainers = vec![
         set_trainer("satoshi"),
         set_trainer("daigo"),
         set_trainer("short_pants_boy")
     ];
-    let mut selected_trainer = select_trainer(&mut trainers);
-
-    // トレーナーが選んだポケモンを選択
-    select_pokemon(&mut selected_trainer);
 
-    // バトルするポケモンを取得
-    let mut enemy_pokemon = select_random_enemy_pokemon();
-    let mut self_pokemon = selected_trainer.active_pokemon.clone().unwrap();
+    (species, skills, trainers, set_items())
+}
 
-    // バトル開始
-    start_battle(&mut self_pokemon, &mut enemy_pokemon);
+/// 道具の設定
+///
+/// # 戻り値
+/// * `HashMap<String, Item>` - キーごとの道具
+pub fn set_items() -> HashMap<String, Item> {
+    HashMap::from([
+        (
+            "monster_ball".to_string(),
+            Item { name: "モンスターボール".to_string(), kind: ItemKind::Ball(Ball { modifier: 10, guaranteed: false }) },
+        ),
+        ("thunder_stone".to_string(), Item { name: "かみなりのいし".to_string(), kind: ItemKind::Evolution }),
+    ])
 }
 
 /// ポケモンの設定
 /// 
 /// # 引数
 /// * `pokemon_name` - ポケモン名
+/// * `uid`          - 個体の ID
 /// 
 /// # 戻り値
 /// * `Pokemon` - 初期化したポケモン
-pub fn set_pokemon(pokemon_name: &str) -> Pokemon {
+pub fn set_pokemon(pokemon_name: &str, uid: u64) -> Pokemon {
     let selected_pokemon;
+    let ivs = StatusPoints::max_ivs();
+    let evs = StatusPoints::default();
 
     match pokemon_name {
         "pikachu" => {
-            let skill_list = set_skill_list(pokemon_name);
+            let skill_list = set_skill_list(pokemon_name).into_iter().map(LearnedSkill::new).collect();
+            let base_status = StatusPoints { hp: 35, atk: 55, def: 40, sp_atk: 50, sp_def: 50, spd: 90 };
+            let status = Status::from_base_status(&base_status, &ivs, &evs, Nature::Hardy, 10);
 
-            selected_pokemon = Pokemon {
-                id: "0",
+            let species = Species {
+                id: "0".to_string(),
                 name: "ピカチュウ".to_string(),
-                level: 10,
                 element: vec![ElementType::Electric],
-                status: Status {
-                    hp: BufToStatus { value: 30, buff: 0 },
-                    atk: BufToStatus { value: 19, buff: 0 },
-                    def: BufToStatus { value: 16, buff: 0 },
-                    sp_atk: BufToStatus { value: 18, buff: 0 },
-                    sp_def: BufToStatus { value: 18, buff: 0 },
-                    spd: BufToStatus { value: 26, buff: 0 },
-                    current_hp: 30,
-                },
+                base_exp: 112,
+                growth_rate: GrowthRate::MediumFast,
+                base_status,
+                learnset: set_learnset(pokemon_name),
+                evolutions: Vec::new(),
+                wild_level: Some(10),
+                catch_rate: 190,
+            };
+
+            selected_pokemon = Pokemon {
+                uid,
+                species: Arc::new(species),
+                nickname: None,
+                level: 10,
+                exp: GrowthRate::MediumFast.exp_for_level(10),
+                friendship: Pokemon::base_friendship(),
+                ivs,
+                evs,
+                nature: Nature::Hardy,
+                status,
+                ailment: None,
+                ailment_turns: 0,
+                volatile: VolatileStatus::default(),
                 skills: skill_list,
             };
         }
         "metagross" => {
-            let skill_list = set_skill_list(pokemon_name);
+            let skill_list = set_skill_list(pokemon_name).into_iter().map(LearnedSkill::new).collect();
+            let base_status = StatusPoints { hp: 80, atk: 135, def: 130, sp_atk: 95, sp_def: 90, spd: 70 };
+            let status = Status::from_base_status(&base_status, &ivs, &evs, Nature::Hardy, 68);
 
-            selected_pokemon = Pokemon {
-                id: "1",
+            let species = Species {
+                id: "1".to_string(),
                 name: "メタグロス".to_string(),
-                level: 68,
                 element: vec![ElementType::Steel, ElementType::Psychic],
-                status: Status {
-                    hp: BufToStatus { value: 255, buff: 0 },
-                    atk: BufToStatus { value: 196, buff: 0 },
-                    def: BufToStatus { value: 173, buff: 0 },
-                    sp_atk: BufToStatus { value: 96, buff: 0 },
-                    sp_def: BufToStatus { value: 124, buff: 0 },
-                    spd: BufToStatus { value: 132, buff: 0 },
-                    current_hp: 255,
-                },
-                skills: skill_list
+                base_exp: 270,
+                growth_rate: GrowthRate::Slow,
+                base_status,
+                learnset: set_learnset(pokemon_name),
+                evolutions: Vec::new(),
+                wild_level: Some(68),
+                catch_rate: 3,
+            };
+
+            selected_pokemon = Pokemon {
+                uid,
+                species: Arc::new(species),
+                nickname: None,
+                level: 68,
+                exp: GrowthRate::Slow.exp_for_level(68),
+                friendship: Pokemon::base_friendship(),
+                ivs,
+                evs,
+                nature: Nature::Hardy,
+                status,
+                ailment: None,
+                ailment_turns: 0,
+                volatile: VolatileStatus::default(),
+                skills: skill_list,
             }
         }
         "pidgey" => {
-            let skill_list = set_skill_list(pokemon_name);
+            let skill_list = set_skill_list(pokemon_name).into_iter().map(LearnedSkill::new).collect();
+            let base_status = StatusPoints { hp: 40, atk: 45, def: 40, sp_atk: 35, sp_def: 35, spd: 56 };
+            let status = Status::from_base_status(&base_status, &ivs, &evs, Nature::Hardy, 8);
 
-            selected_pokemon = Pokemon {
-                id: "2",
+            let species = Species {
+                id: "2".to_string(),
                 name: "ポッポ".to_string(),
-                level: 8,
                 element: vec![ElementType::Normal, ElementType::Flying],
-                status: Status {
-                    hp: BufToStatus { value: 25, buff: 0 },
-                    atk: BufToStatus { value: 16, buff: 0 },
-                    def: BufToStatus { value: 13, buff: 0 },
-                    sp_atk: BufToStatus { value: 15, buff: 0 },
-                    sp_def: BufToStatus { value: 15, buff: 0 },
-                    spd: BufToStatus { value: 23, buff: 0 },
-                    current_hp: 25,
-                },
-                skills: skill_list
+                base_exp: 50,
+                growth_rate: GrowthRate::MediumSlow,
+                base_status,
+                learnset: set_learnset(pokemon_name),
+                evolutions: Vec::new(),
+                wild_level: Some(8),
+                catch_rate: 255,
+            };
+
+            selected_pokemon = Pokemon {
+                uid,
+                species: Arc::new(species),
+                nickname: None,
+                level: 8,
+                exp: GrowthRate::MediumSlow.exp_for_level(8),
+                friendship: Pokemon::base_friendship(),
+                ivs,
+                evs,
+                nature: Nature::Hardy,
+                status,
+                ailment: None,
+                ailment_turns: 0,
+                volatile: VolatileStatus::default(),
+                skills: skill_list,
             };
         }
         _ => {
@@ -101,27 +174,6 @@ pub fn set_pokemon(pokemon_name: &str) -> Pokemon {
     selected_pokemon
 }
 
-/// 敵ポケモンをランダムに選択
-/// 
-/// # 戻り値
-/// * `Pokemon` - 初期化したポケモン
-pub fn select_random_enemy_pokemon() -> Pokemon {
-    // TODO JSONから取得したポケモンを設定
-    let pokemon_names = vec![
-        "pikachu",
-        "metagross",
-        "pidgey",
-    ];
-
-    // 乱数生成
-    let rand_idx = XorShift128::random_in_range(0, pokemon_names.len() as u64 - 1) as usize;
-
-    // ランダムに選ばれたポケモン名でポケモンを生成
-    let selected_name = pokemon_names[rand_idx];
-
-    set_pokemon(selected_name)
-}
-
 /// 技の設定
 /// 
 /// # 引数
@@ -132,30 +184,39 @@ pub fn select_random_enemy_pokemon() -> Pokemon {
 pub fn set_trainer(trainer_name: &str) -> Trainer {
     match trainer_name {
         "satoshi" => {
-            let pokemon = set_pokemon("pikachu");
+            let pokemon = set_pokemon("pikachu", 1);
 
             Trainer {
-                name: "サトシ",
-                pokemons: vec![pokemon.clone()],
-                active_pokemon: Some(pokemon)
+                id: "satoshi".to_string(),
+                name: "サトシ".to_string(),
+                pokemons: vec![pokemon],
+                active_idx: 0,
+                ai: AiLevel::Random,
+                search: SearchConfig::default()
             }
         }
         "daigo" => {
-            let pokemon = set_pokemon("metagross");
+            let pokemon = set_pokemon("metagross", 2);
 
             Trainer {
-                name: "ダイゴ",
-                pokemons: vec![pokemon.clone()],
-                active_pokemon: Some(pokemon)
+                id: "daigo".to_string(),
+                name: "ダイゴ".to_string(),
+                pokemons: vec![pokemon],
+                active_idx: 0,
+                ai: AiLevel::Heuristic,
+                search: SearchConfig::default()
             }
         }
         "short_pants_boy" => {
-            let pokemon = set_pokemon("pidgey");
+            let pokemon = set_pokemon("pidgey", 3);
 
             Trainer {
-                name: "たんぱんこぞうのミノル",
-                pokemons: vec![pokemon.clone()],
-                active_pokemon: Some(pokemon)
+                id: "short_pants_boy".to_string(),
+                name: "たんぱんこぞうのミノル".to_string(),
+                pokemons: vec![pokemon],
+                active_idx: 0,
+                ai: AiLevel::Random,
+                search: SearchConfig::default()
             }
         }
         _ => {
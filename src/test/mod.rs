@@ -1 +1,2 @@
-pub mod hard_coded;
\ No newline at end of file
+pub mod hard_coded;
+pub mod skill;
\ No newline at end of file
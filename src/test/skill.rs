@@ -1,4 +1,8 @@
-use crate::models::pokemon::{ElementType, Skill, SkillEffect, SkillType, StatusEffect, StatusType, Target};
+use std::sync::Arc;
+
+use pokemon_battle_system::models::pokemon::{
+    ElementType, Learnset, LevelUpSkill, Skill, SkillEffect, SkillType, StatusEffect, StatusType, Target
+};
 
 
 
@@ -13,45 +17,72 @@ pub fn set_skill_list(pokemon_name: &str) -> Vec<Skill> {
     match pokemon_name {
         "pikachu" => {
             let tailwind = Skill {
+                id: "tail_whip".to_string(),
                 name: "しっぽをふる".to_string(),
                 element: ElementType::Normal,
                 base_atk: 0,
                 accuracy: 100,
+                pp: 30,
                 priority: 0,
+                critical_rank: 0,
+                charge: false,
+                breaks_screens: false,
                 skill_effect: Some(SkillEffect {
-                    status_effect: StatusEffect {
+                    status_effect: Some(StatusEffect {
                         target: StatusType::Def,
                         effect_value: -1,
-                    },
+                    }),
+                    ailment: None,
+                    critical_stage: 0,
+                    screen: None,
                     target: Target::Enemy,
                 }),
+                secondary_effects: vec![],
                 class: SkillType::ChangeStatus,
             };
             let thundershock = Skill {
+                id: "thunder_shock".to_string(),
                 name: "でんきショック".to_string(),
                 element: ElementType::Electric,
                 base_atk: 40,
                 accuracy: 100,
+                pp: 30,
                 priority: 0,
+                critical_rank: 0,
+                charge: false,
+                breaks_screens: false,
                 skill_effect: None,
+                secondary_effects: vec![],
                 class: SkillType::SpecialAttack,
             };
             let quick_attack = Skill {
+                id: "quick_attack".to_string(),
                 name: "でんこうせっか".to_string(),
                 element: ElementType::Normal,
                 base_atk: 40,
                 accuracy: 100,
+                pp: 30,
                 priority: 2,
+                critical_rank: 0,
+                charge: false,
+                breaks_screens: false,
                 skill_effect: None,
+                secondary_effects: vec![],
                 class: SkillType::PhysicalAttack,
             };
             let tackle = Skill {
+                id: "tackle".to_string(),
                 name: "たいあたり".to_string(),
                 element: ElementType::Normal,
                 base_atk: 40,
                 accuracy: 100,
+                pp: 35,
                 priority: 0,
+                critical_rank: 0,
+                charge: false,
+                breaks_screens: false,
                 skill_effect: None,
+                secondary_effects: vec![],
                 class: SkillType::PhysicalAttack,
             };
         
@@ -59,39 +90,63 @@ pub fn set_skill_list(pokemon_name: &str) -> Vec<Skill> {
         },
         "metagross" => {
             let bullet_punch = Skill {
+                id: "bullet_punch".to_string(),
                 name: "バレットパンチ".to_string(),
                 element: ElementType::Steel,
                 base_atk: 40,
                 accuracy: 100,
+                pp: 30,
                 priority: 2,
+                critical_rank: 0,
+                charge: false,
+                breaks_screens: false,
                 skill_effect: None,
+                secondary_effects: vec![],
                 class: SkillType::PhysicalAttack,
             };
             let earthquake = Skill {
+                id: "earthquake".to_string(),
                 name: "じしん".to_string(),
                 element: ElementType::Ground,
                 base_atk: 100,
                 accuracy: 100,
+                pp: 10,
                 priority: 0,
+                critical_rank: 0,
+                charge: false,
+                breaks_screens: false,
                 skill_effect: None,
+                secondary_effects: vec![],
                 class: SkillType::PhysicalAttack,
             };
             let ice_punch = Skill {
+                id: "ice_punch".to_string(),
                 name: "れいとうパンチ".to_string(),
                 element: ElementType::Ice,
                 base_atk: 75,
                 accuracy: 100,
+                pp: 15,
                 priority: 0,
+                critical_rank: 0,
+                charge: false,
+                breaks_screens: false,
                 skill_effect: None,
+                secondary_effects: vec![],
                 class: SkillType::PhysicalAttack,
             };
             let psychic_fangs = Skill {
+                id: "psychic_fangs".to_string(),
                 name: "サイコファング".to_string(),
                 element: ElementType::Psychic,
                 base_atk: 85,
                 accuracy: 100,
+                pp: 10,
                 priority: 0,
+                critical_rank: 0,
+                charge: false,
+                breaks_screens: true,
                 skill_effect: None,
+                secondary_effects: vec![],
                 class: SkillType::PhysicalAttack,
             };
         
@@ -99,45 +154,72 @@ pub fn set_skill_list(pokemon_name: &str) -> Vec<Skill> {
         },
         "pidgey" => {
             let thundershock = Skill {
+                id: "sky_attack".to_string(),
                 name: "ゴッドバード".to_string(),
                 element: ElementType::Flying,
                 base_atk: 120,
                 accuracy: 50,
+                pp: 5,
                 priority: 0,
+                critical_rank: 0,
+                charge: false,
+                breaks_screens: false,
                 skill_effect: None,
+                secondary_effects: vec![],
                 class: SkillType::SpecialAttack,
             };
             let growl = Skill {
+                id: "growl".to_string(),
                 name: "なきごえ".to_string(),
                 element: ElementType::Normal,
                 base_atk: 0,
                 accuracy: 100,
+                pp: 40,
                 priority: 0,
+                critical_rank: 0,
+                charge: false,
+                breaks_screens: false,
                 skill_effect: Some(SkillEffect {
-                    status_effect: StatusEffect {
+                    status_effect: Some(StatusEffect {
                         target: StatusType::Atk,
                         effect_value: -1,
-                    },
+                    }),
+                    ailment: None,
+                    critical_stage: 0,
+                    screen: None,
                     target: Target::Enemy,
                 }),
+                secondary_effects: vec![],
                 class: SkillType::ChangeStatus,
             };
             let quick_attack = Skill {
+                id: "quick_attack".to_string(),
                 name: "でんこうせっか".to_string(),
                 element: ElementType::Normal,
                 base_atk: 40,
                 accuracy: 100,
+                pp: 30,
                 priority: 2,
+                critical_rank: 0,
+                charge: false,
+                breaks_screens: false,
                 skill_effect: None,
+                secondary_effects: vec![],
                 class: SkillType::PhysicalAttack,
             };
             let tackle = Skill {
+                id: "horn_drill".to_string(),
                 name: "つのドリル".to_string(),
                 element: ElementType::Normal,
                 base_atk: 40,
                 accuracy: 30,
+                pp: 5,
                 priority: 0,
+                critical_rank: 0,
+                charge: false,
+                breaks_screens: false,
                 skill_effect: None,
+                secondary_effects: vec![],
                 class: SkillType::OneHitKO,
             };
         
@@ -148,4 +230,22 @@ pub fn set_skill_list(pokemon_name: &str) -> Vec<Skill> {
             unreachable!()
         }
     }
-}
\ No newline at end of file
+}
+
+/// 覚えられる技の設定
+///
+/// ハードコードしたデータでは、最初から覚えている技をレベル 1 で覚える技とする
+///
+/// # 引数
+/// * `pokemon_name` - ポケモン名
+///
+/// # 戻り値
+/// * `Learnset` - 覚えられる技
+pub fn set_learnset(pokemon_name: &str) -> Learnset {
+    let level_up = set_skill_list(pokemon_name)
+        .into_iter()
+        .map(|skill| LevelUpSkill { level: 1, skill: Arc::new(skill) })
+        .collect();
+
+    Learnset { level_up, tm: Vec::new(), egg: Vec::new() }
+}